@@ -34,8 +34,10 @@
 
     # Search
     ripgrep
-    fzf
     ast-grep
+    typos
+    codespell
+    vale
 
     # Text processing
     sd
@@ -55,18 +57,21 @@
     procs
     tokei
     hyperfine
+    smartmontools # smartctl: disk SMART health
 
     # Network
     xh
     doggo
     usql
     curlie # curl with better output
+    openssh # ssh -G, ssh-add, ssh-keygen, ssh-keyscan
 
     # Web search
     ddgr # DuckDuckGo CLI with JSON output
 
     # Git forges
     gh # GitHub CLI
+    act # Local GitHub Actions runs
     glab # GitLab CLI
 
     # Containers
@@ -79,10 +84,13 @@
     skopeo # Registry operations
     crane # Low-level registry tool
     trivy # Security scanner
+    syft # SBOM: image packages and licenses
 
     # Kubernetes
     kubectl # K8s CLI
     kubernetes-helm # Helm charts
+    kube-linter # Manifest best-practice checks
+    polaris # Manifest audits
     stern # Multi-pod logs
     kustomize # Manifest building
 
@@ -97,6 +105,10 @@
 
     # Utility
     file
+    binutils # readelf, objdump
+    binwalk
+    gnupg # verify_release signatures
+    cosign
 
     # Reference
     tealdeer
@@ -106,6 +118,7 @@
 
     # Archives
     ouch
+    libarchive # bsdtar: archive listings, single-file extraction, conversion
 
     # Task queue
     pueue
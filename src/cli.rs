@@ -15,7 +15,6 @@ pub const KNOWN_TOOLS: &[&str] = &[
     // Search
     "rg",
     "sg",
-    "fzf",
     // Git
     "git",
     "gh",
@@ -102,7 +101,7 @@ pub fn print_tools() {
             "Filesystem",
             &["eza", "bat", "fd", "duf", "dust", "rip"][..],
         ),
-        ("Search", &["rg", "sg", "fzf"]),
+        ("Search", &["rg", "sg"]),
         ("Git", &["git", "gh", "glab", "delta"]),
         (
             "Text",
@@ -0,0 +1,205 @@
+// modern-cli-mcp/src/clients.rs
+//! Per-client output settings.
+//!
+//! MCP clients differ widely in how much tool output they accept in one
+//! response. The client announces its name at initialize; a JSON config maps
+//! names to an output cap, dual-response behavior, a pagination threshold,
+//! and the locale and templates of dual-response summaries, which then apply
+//! to every call in that session:
+//!
+//! ```json
+//! {
+//!   "default": { "max_output_bytes": 200000 },
+//!   "clients": {
+//!     "claude-code": { "page_bytes": 60000 },
+//!     "cursor*": { "page_bytes": 20000, "dual_response": false },
+//!     "team-de-*": { "dual_response": true, "locale": "de" },
+//!     "ops-*": { "summary_templates": { "kubectl/pods": "{{ items|length }} pods" } }
+//!   }
+//! }
+//! ```
+//!
+//! Names match case-insensitively; a trailing `*` matches by prefix, and the
+//! longest matching pattern wins over `default`. A client's summary
+//! templates are added to the default ones, replacing those of the same type.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Output settings; unset fields fall back to `default`, then to the CLI flags
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientSettings {
+    /// Hard cap on one text block; the rest is dropped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_bytes: Option<usize>,
+    /// Override `--dual-response` for this client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dual_response: Option<bool>,
+    /// Text blocks larger than this are split into pages fetched with `output_page`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_bytes: Option<usize>,
+    /// Locale for dual-response summaries (see `locale`); English when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Summary type to minijinja template (see `templates`); these win over
+    /// the locale catalog
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub summary_templates: BTreeMap<String, String>,
+}
+
+impl ClientSettings {
+    /// Fill unset fields from `fallback`
+    fn or(self, fallback: &ClientSettings) -> ClientSettings {
+        ClientSettings {
+            max_output_bytes: self.max_output_bytes.or(fallback.max_output_bytes),
+            dual_response: self.dual_response.or(fallback.dual_response),
+            page_bytes: self.page_bytes.or(fallback.page_bytes),
+            locale: self.locale.or_else(|| fallback.locale.clone()),
+            summary_templates: {
+                let mut templates = fallback.summary_templates.clone();
+                templates.extend(self.summary_templates);
+                templates
+            },
+        }
+    }
+}
+
+/// Client name patterns mapped to settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    #[serde(default)]
+    pub default: ClientSettings,
+    #[serde(default)]
+    pub clients: HashMap<String, ClientSettings>,
+}
+
+impl ClientConfig {
+    /// Load `path`, or `~/.config/modern-cli-mcp/clients.json` if it exists
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let path: PathBuf = match path {
+            Some(p) => p.to_path_buf(),
+            None => match dirs::config_dir() {
+                Some(dir) => {
+                    let p = dir.join("modern-cli-mcp").join("clients.json");
+                    if !p.exists() {
+                        return Ok(Self::default());
+                    }
+                    p
+                }
+                None => return Ok(Self::default()),
+            },
+        };
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    /// Settings for a client name, merged over `default`
+    pub fn resolve(&self, client: &str) -> ClientSettings {
+        let client = client.to_ascii_lowercase();
+        let matched = self
+            .clients
+            .iter()
+            .filter_map(|(pattern, settings)| {
+                let pattern = pattern.to_ascii_lowercase();
+                // An exact name outranks a prefix pattern covering the same text
+                let rank = match pattern.strip_suffix('*') {
+                    Some(prefix) => client.starts_with(prefix).then(|| prefix.len() * 2),
+                    None => (client == pattern).then(|| pattern.len() * 2 + 1),
+                };
+                rank.map(|rank| (rank, settings))
+            })
+            .max_by_key(|(rank, _)| *rank)
+            .map(|(_, settings)| settings.clone());
+        matched.unwrap_or_default().or(&self.default)
+    }
+}
+
+/// Cut `text` to at most `max` bytes on a char boundary, noting what was dropped
+pub fn truncate_output(text: &mut String, max: usize) -> bool {
+    if text.len() <= max {
+        return false;
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let dropped = text.len() - end;
+    text.truncate(end);
+    text.push_str(&format!("\n[output truncated: {} bytes omitted]", dropped));
+    true
+}
+
+/// Byte ranges of the pages of `text`, each at most `page_bytes` long.
+/// Pages end at a line break when one falls in the second half of the page.
+pub fn page_bounds(text: &str, page_bytes: usize) -> Vec<(usize, usize)> {
+    let page_bytes = page_bytes.max(1);
+    let mut bounds = vec![];
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + page_bytes).min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == start {
+            // A single char wider than the page
+            end = start + text[start..].chars().next().map_or(1, char::len_utf8);
+        } else if end < text.len() {
+            if let Some(nl) = text[start..end].rfind('\n') {
+                if nl >= page_bytes / 2 {
+                    end = start + nl + 1;
+                }
+            }
+        }
+        bounds.push((start, end));
+        start = end;
+    }
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_client_settings() {
+        let config: ClientConfig = serde_json::from_str(
+            r#"{
+                "default": {"max_output_bytes": 1000, "page_bytes": 500},
+                "clients": {
+                    "cursor*": {"page_bytes": 100, "dual_response": true},
+                    "cursor-lite": {"page_bytes": 50}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let cursor = config.resolve("Cursor-IDE");
+        assert_eq!(cursor.page_bytes, Some(100));
+        assert_eq!(cursor.dual_response, Some(true));
+        assert_eq!(cursor.max_output_bytes, Some(1000));
+
+        assert_eq!(config.resolve("cursor-lite").page_bytes, Some(50));
+        assert!(cursor.summary_templates.is_empty());
+        assert_eq!(config.resolve("other"), config.default);
+        assert!(serde_json::from_str::<ClientConfig>(r#"{"default":{"page":1}}"#).is_err());
+    }
+
+    #[test]
+    fn test_pages_and_truncation() {
+        let text = "line one\nline two\nline three\n";
+        let bounds = page_bounds(text, 12);
+        let pages: Vec<&str> = bounds.iter().map(|&(s, e)| &text[s..e]).collect();
+        assert_eq!(pages.concat(), text);
+        assert_eq!(pages[0], "line one\n");
+        assert!(pages.iter().all(|p| p.len() <= 12));
+
+        let mut wide = "é".repeat(10);
+        assert!(truncate_output(&mut wide, 5));
+        assert!(wide.starts_with("éé\n[output truncated: 16 bytes omitted]"));
+        assert_eq!(page_bounds("éé", 1).len(), 2);
+    }
+}
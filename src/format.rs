@@ -63,6 +63,24 @@ pub fn format_rg_summary(output: &str, pattern: &str) -> String {
     )
 }
 
+/// Format structured ripgrep match summary
+pub fn format_rg_json_summary(json: &str, pattern: &str) -> String {
+    if let Ok(v) = serde_json::from_str::<Value>(json) {
+        let total = v.get("total_matches").and_then(|t| t.as_u64()).unwrap_or(0);
+        let files = v.get("files_matched").and_then(|f| f.as_u64()).unwrap_or(0);
+        let truncated = v.get("truncated").and_then(|t| t.as_bool()) == Some(true);
+        format!(
+            "Found {} matches in {} files for '{}'{}",
+            total,
+            files,
+            pattern,
+            if truncated { " (truncated)" } else { "" }
+        )
+    } else {
+        format!("Search completed for '{}'", pattern)
+    }
+}
+
 /// Format git status summary
 pub fn format_git_status_summary(json: &str) -> String {
     if let Ok(v) = serde_json::from_str::<Value>(json) {
@@ -212,6 +230,53 @@ pub fn format_file_read_summary(path: &str, line_count: usize) -> String {
     format!("Read {} ({} lines)", path, line_count)
 }
 
+/// Format file outline summary
+pub fn format_file_outline_summary(result: &Value) -> String {
+    let path = result["path"].as_str().unwrap_or("");
+    if let Some(matches) = result["matches"].as_array() {
+        let ranges: Vec<String> = matches
+            .iter()
+            .map(|m| {
+                format!(
+                    "{} {} (lines {}-{})",
+                    m["kind"].as_str().unwrap_or(""),
+                    m["qualified"].as_str().unwrap_or(""),
+                    m["line_start"],
+                    m["line_end"]
+                )
+            })
+            .collect();
+        return format!("Read {} from {}", ranges.join(", "), path);
+    }
+
+    let symbols = result["symbols"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "Outline of {} ({} lines): {} symbols",
+        path,
+        result["total_lines"],
+        symbols.len()
+    );
+    for s in symbols.iter().take(40) {
+        let depth = if s["container"].is_string() {
+            "    "
+        } else {
+            "  "
+        };
+        out.push_str(&format!(
+            "\n{}{} {} L{}-{}",
+            depth,
+            s["kind"].as_str().unwrap_or(""),
+            s["name"].as_str().unwrap_or(""),
+            s["line_start"],
+            s["line_end"]
+        ));
+    }
+    if symbols.len() > 40 {
+        out.push_str(&format!("\n  ... {} more", symbols.len() - 40));
+    }
+    out
+}
+
 /// Format file write summary
 pub fn format_file_write_summary(path: &str, bytes: usize) -> String {
     format!("Wrote {} ({} bytes)", path, bytes)
@@ -253,3 +318,1525 @@ pub fn format_text_summary(tool: &str, input_lines: usize, output_lines: usize)
         tool, input_lines, output_lines
     )
 }
+
+/// Format compose wait summary
+pub fn format_compose_wait_summary(
+    ready: usize,
+    total: usize,
+    secs: f64,
+    all_ready: bool,
+) -> String {
+    if all_ready {
+        format!(
+            "compose wait: {}/{} services ready in {:.1}s",
+            ready, total, secs
+        )
+    } else {
+        format!(
+            "compose wait: {}/{} services ready after {:.1}s (not ready)",
+            ready, total, secs
+        )
+    }
+}
+
+/// Format hyperfine benchmark summary
+pub fn format_hyperfine_summary(result: &serde_json::Value) -> String {
+    let empty = vec![];
+    let results = result["results"].as_array().unwrap_or(&empty);
+    let mut parts: Vec<String> = results
+        .iter()
+        .map(|r| {
+            format!(
+                "{}: {:.2}ms ± {:.2}ms",
+                r["command"].as_str().unwrap_or("?"),
+                r["mean"].as_f64().unwrap_or(0.0) * 1000.0,
+                r["stddev"].as_f64().unwrap_or(0.0) * 1000.0
+            )
+        })
+        .collect();
+    if let Some(regressions) = result["comparison"]["regressions"].as_u64() {
+        parts.push(format!(
+            "{} regression(s) vs {}",
+            regressions,
+            result["comparison"]["against"]
+                .as_str()
+                .unwrap_or("baseline")
+        ));
+    }
+    if let Some(unmatched) = result["comparison"]["unmatched"]
+        .as_array()
+        .filter(|u| !u.is_empty())
+    {
+        parts.push(format!("{} command(s) not in both runs", unmatched.len()));
+    }
+    parts.join("; ")
+}
+
+/// Format test run summary from parsed TAP results
+pub fn format_test_summary(runner: &str, result: &serde_json::Value) -> String {
+    let summary = &result["summary"];
+    let count = |field: &str| summary[field].as_u64().unwrap_or(0);
+    let mut text = format!(
+        "{}: {} tests, {} passed, {} failed",
+        runner,
+        count("total"),
+        count("passed"),
+        count("failed")
+    );
+    if count("skipped") > 0 {
+        text.push_str(&format!(", {} skipped", count("skipped")));
+    }
+    if let Some(first) = result["failures"].get(0).and_then(|f| f.as_str()) {
+        text.push_str(&format!(" (first failure: {})", first));
+    }
+    text
+}
+
+/// Format log summary
+pub fn format_logparse_summary(result: &serde_json::Value) -> String {
+    let levels = &result["levels"];
+    let count = |level: &str| levels[level].as_u64().unwrap_or(0);
+    let mut text = format!(
+        "{} lines: {} error, {} fatal, {} warn",
+        result["lines"].as_u64().unwrap_or(0),
+        count("error"),
+        count("fatal"),
+        count("warn")
+    );
+    if let Some(top) = result["top_errors"].get(0) {
+        text.push_str(&format!(
+            "; top error ({}x): {}",
+            top["count"].as_u64().unwrap_or(0),
+            top["pattern"].as_str().unwrap_or_default()
+        ));
+    }
+    text
+}
+
+/// Format git repository discovery summary
+pub fn format_git_discover_summary(result: &serde_json::Value) -> String {
+    let repos = result["repos"].as_array().cloned().unwrap_or_default();
+    let dirty = repos.iter().filter(|r| r["dirty"] == true).count();
+    let ahead = repos
+        .iter()
+        .filter(|r| r["ahead"].as_i64().unwrap_or(0) > 0)
+        .count();
+    let behind = repos
+        .iter()
+        .filter(|r| r["behind"].as_i64().unwrap_or(0) > 0)
+        .count();
+    let failed = repos.iter().filter(|r| !r["error"].is_null()).count();
+    let mut text = format!(
+        "Found {} repositories under {}: {} dirty, {} ahead, {} behind",
+        repos.len(),
+        result["root"].as_str().unwrap_or("."),
+        dirty,
+        ahead,
+        behind
+    );
+    if failed > 0 {
+        text.push_str(&format!(", {} unreadable", failed));
+    }
+    text
+}
+
+/// Format multi-repository git operation summary
+pub fn format_git_foreach_summary(result: &serde_json::Value) -> String {
+    let mut text = format!(
+        "git {} in {} repositories: {} succeeded, {} failed",
+        result["operation"].as_str().unwrap_or("?"),
+        result["count"].as_u64().unwrap_or(0),
+        result["succeeded"].as_u64().unwrap_or(0),
+        result["failed"].as_u64().unwrap_or(0)
+    );
+    if result["operation"] == "status" {
+        let dirty = result["repos"]
+            .as_array()
+            .map(|r| r.iter().filter(|r| r["result"]["dirty"] == true).count())
+            .unwrap_or(0);
+        text.push_str(&format!(", {} dirty", dirty));
+    }
+    text
+}
+
+/// Format patch export summary (format_patch / export_diff)
+pub fn format_git_patch_summary(result: &serde_json::Value) -> String {
+    let files = result["files_changed"].as_u64().unwrap_or(0);
+    if files == 0 {
+        return "No changes to export".to_string();
+    }
+    let mut text = String::from("Patch: ");
+    if let Some(commits) = result["commits"].as_u64() {
+        text.push_str(&format!("{} commits, ", commits));
+    }
+    text.push_str(&format!(
+        "{} files, +{} -{}",
+        files,
+        result["additions"].as_u64().unwrap_or(0),
+        result["deletions"].as_u64().unwrap_or(0)
+    ));
+    if let Some(output) = result["output"].as_str() {
+        text.push_str(&format!(" (written to {})", output));
+    }
+    text
+}
+
+/// Format directory diff summary
+pub fn format_diff_dirs_summary(result: &Value) -> String {
+    let count = |key: &str| result[key].as_array().map_or(0, |a| a.len());
+    let delta = result["size_delta"].as_i64().unwrap_or(0);
+    let mut out = format!(
+        "{} vs {}: {} added, {} removed, {} modified, {} unchanged ({}{} bytes)",
+        result["dir_a"].as_str().unwrap_or(""),
+        result["dir_b"].as_str().unwrap_or(""),
+        count("added"),
+        count("removed"),
+        count("modified"),
+        result["unchanged"],
+        if delta >= 0 { "+" } else { "" },
+        delta
+    );
+    for (key, mark) in [("added", "+"), ("removed", "-"), ("modified", "~")] {
+        for entry in result[key].as_array().into_iter().flatten().take(10) {
+            out.push_str(&format!(
+                "\n  {} {}",
+                mark,
+                entry["path"].as_str().unwrap_or("")
+            ));
+        }
+        if count(key) > 10 {
+            out.push_str(&format!("\n  {} ... {} more", mark, count(key) - 10));
+        }
+    }
+    if result["truncated"].as_bool() == Some(true) {
+        out.push_str("\n  (file limit reached; comparison incomplete)");
+    }
+    out
+}
+
+/// Format archive listing summary
+pub fn format_archive_list_summary(result: &Value) -> String {
+    let files = result["files"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "{}: {} entries, {} bytes uncompressed",
+        result["archive"].as_str().unwrap_or(""),
+        files.len(),
+        result["total_size"]
+    );
+    for f in files.iter().take(30) {
+        out.push_str(&format!(
+            "\n  {:>10}  {}  {}",
+            f["size"],
+            f["mtime"].as_str().unwrap_or(""),
+            f["path"].as_str().unwrap_or("")
+        ));
+    }
+    if files.len() > 30 {
+        out.push_str(&format!("\n  ... {} more", files.len() - 30));
+    }
+    out
+}
+
+/// Format OpenAPI operation listing summary
+pub fn format_openapi_summary(result: &Value) -> String {
+    let ops = result["operations"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "{}: {} operations{}",
+        result["title"].as_str().unwrap_or(""),
+        ops.len(),
+        result["base_url"]
+            .as_str()
+            .map(|u| format!(" at {}", u))
+            .unwrap_or_default()
+    );
+    for op in ops.iter().take(50) {
+        let required: Vec<&str> = op["params"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|p| p["required"].as_bool() == Some(true))
+            .filter_map(|p| p["name"].as_str())
+            .collect();
+        out.push_str(&format!(
+            "\n  {} {} {} ({}){}",
+            op["id"].as_str().unwrap_or(""),
+            op["method"].as_str().unwrap_or(""),
+            op["path"].as_str().unwrap_or(""),
+            if required.is_empty() {
+                "-".to_string()
+            } else {
+                required.join(", ")
+            },
+            op["summary"]
+                .as_str()
+                .map(|s| format!(" - {}", s))
+                .unwrap_or_default()
+        ));
+    }
+    if ops.len() > 50 {
+        out.push_str(&format!("\n  ... {} more", ops.len() - 50));
+    }
+    out
+}
+
+/// Format DNS resolver comparison summary
+pub fn format_dns_compare_summary(result: &Value) -> String {
+    let mut out = format!(
+        "{} {}: {}",
+        result["domain"].as_str().unwrap_or(""),
+        result["record_type"].as_str().unwrap_or(""),
+        if result["consistent"].as_bool() == Some(true) {
+            "all resolvers agree".to_string()
+        } else {
+            format!(
+                "resolvers disagree (reference: {})",
+                result["reference"].as_str().unwrap_or("")
+            )
+        }
+    );
+    for answer in result["answers"].as_array().into_iter().flatten() {
+        let resolver = answer["resolver"].as_str().unwrap_or("");
+        if let Some(err) = answer["error"].as_str() {
+            out.push_str(&format!("\n  {:<14} error: {}", resolver, err));
+            continue;
+        }
+        let records: Vec<String> = answer["records"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|r| match r["ttl"].as_u64() {
+                Some(ttl) => format!("{} (ttl {}s)", r["value"].as_str().unwrap_or(""), ttl),
+                None => r["value"].as_str().unwrap_or("").to_string(),
+            })
+            .collect();
+        let differs = result["differences"]
+            .as_array()
+            .is_some_and(|d| d.iter().any(|d| d["resolver"] == answer["resolver"]));
+        out.push_str(&format!(
+            "\n  {} {:<14} {}",
+            if differs { "!" } else { " " },
+            resolver,
+            if records.is_empty() {
+                "(no records)".to_string()
+            } else {
+                records.join(", ")
+            }
+        ));
+    }
+    out
+}
+
+/// Format certificate/key inspection summary
+pub fn format_key_inspect_summary(result: &Value) -> String {
+    let items = result["items"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "{}: {} object{}",
+        result["path"].as_str().unwrap_or(""),
+        items.len(),
+        if items.len() == 1 { "" } else { "s" }
+    );
+    for item in &items {
+        let mut line = format!(
+            "\n  [{}] {}",
+            item["index"],
+            item["kind"].as_str().unwrap_or("")
+        );
+        if let Some(subject) = item["subject"].as_str() {
+            line.push_str(&format!(" {}", subject));
+        }
+        let key = &item["key"];
+        if let Some(algorithm) = key["algorithm"].as_str() {
+            line.push_str(&format!(", {}", algorithm));
+            if let Some(bits) = key["bits"].as_u64() {
+                line.push_str(&format!(" {}", bits));
+            }
+            if let Some(curve) = key["curve"].as_str() {
+                line.push_str(&format!(" ({})", curve));
+            }
+        }
+        if let Some(days) = item["days_remaining"].as_i64() {
+            let end = item["not_after"].as_str().unwrap_or("");
+            if days < 0 {
+                line.push_str(&format!(", EXPIRED {} ({} days ago)", end, -days));
+            } else {
+                line.push_str(&format!(", expires {} ({} days)", end, days));
+            }
+        }
+        if item["encrypted"].as_bool() == Some(true) {
+            line.push_str(", passphrase protected");
+        }
+        if let Some(err) = item["error"].as_str() {
+            line.push_str(&format!(", error: {}", err));
+        }
+        out.push_str(&line);
+    }
+    for m in result["matches"].as_array().into_iter().flatten() {
+        out.push_str(&format!(
+            "\n  {} {} {}",
+            m["certificate"].as_str().unwrap_or(""),
+            if m["matches"].as_bool() == Some(true) {
+                "matches"
+            } else {
+                "does NOT match"
+            },
+            m["key"].as_str().unwrap_or("")
+        ));
+    }
+    out
+}
+
+/// Format container image report summary
+pub fn format_image_report_summary(result: &Value) -> String {
+    let mb = |bytes: &Value| bytes.as_u64().unwrap_or(0) as f64 / 1_000_000.0;
+    let mut out = format!(
+        "{} ({}): {} layers, {:.1} MB compressed, user {}",
+        result["image"].as_str().unwrap_or(""),
+        result["platform"].as_str().unwrap_or(""),
+        result["layer_count"],
+        mb(&result["compressed_size"]),
+        result["config"]["user"].as_str().unwrap_or("")
+    );
+    if let Some(digest) = result["digest"].as_str() {
+        out.push_str(&format!("\n  digest: {}", digest));
+    }
+    let base = &result["base_image"];
+    if let Some(name) = base["name"].as_str() {
+        out.push_str(&format!(
+            "\n  base: {} (from {})",
+            name,
+            base["source"].as_str().unwrap_or("")
+        ));
+    }
+    let packages = &result["packages"];
+    if packages.is_object() {
+        let licenses: Vec<String> = packages["licenses"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .take(5)
+            .map(|l| format!("{} {}", l["license"].as_str().unwrap_or(""), l["count"]))
+            .collect();
+        out.push_str(&format!(
+            "\n  packages: {} ({} OS), {} without license; top licenses: {}",
+            packages["total"],
+            packages["os_packages"],
+            packages["without_license"],
+            if licenses.is_empty() {
+                "none".to_string()
+            } else {
+                licenses.join(", ")
+            }
+        ));
+    }
+    if let Some(err) = result["packages_error"].as_str() {
+        out.push_str(&format!("\n  packages unavailable: {}", err));
+    }
+    for layer in result["largest_layers"].as_array().into_iter().flatten() {
+        let mut step = layer["created_by"].as_str().unwrap_or("?").to_string();
+        if step.chars().count() > 80 {
+            step = format!("{}…", step.chars().take(80).collect::<String>());
+        }
+        out.push_str(&format!(
+            "\n  [{}] {:.1} MB {}",
+            layer["index"],
+            mb(&layer["size"]),
+            step
+        ));
+    }
+    out
+}
+
+/// Format Kubernetes lint summary
+pub fn format_kube_lint_summary(result: &Value) -> String {
+    let findings = result["findings"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "{} {}: {}",
+        result["linter"].as_str().unwrap_or(""),
+        result["path"].as_str().unwrap_or(""),
+        if findings.is_empty() {
+            "no findings".to_string()
+        } else {
+            format!(
+                "{} finding{} ({} danger, {} warning)",
+                findings.len(),
+                if findings.len() == 1 { "" } else { "s" },
+                result["danger"],
+                result["warning"]
+            )
+        }
+    );
+    for finding in findings.iter().take(20) {
+        let mut object = finding["object"].as_str().unwrap_or("").to_string();
+        if let Some(container) = finding["container"].as_str() {
+            object.push_str(&format!(" [{}]", container));
+        }
+        out.push_str(&format!(
+            "\n  {} {} {}: {}",
+            finding["severity"].as_str().unwrap_or(""),
+            object,
+            finding["check"].as_str().unwrap_or(""),
+            finding["message"].as_str().unwrap_or("")
+        ));
+    }
+    if findings.len() > 20 {
+        out.push_str(&format!("\n  ... and {} more", findings.len() - 20));
+    }
+    out
+}
+
+/// Format GitHub Actions failing step summary
+pub fn format_run_logs_summary(result: &Value) -> String {
+    let failures = result["failures"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "run {} ({}): {}, {} failing step{}",
+        result["run_id"],
+        result["workflow"].as_str().unwrap_or(""),
+        result["conclusion"].as_str().unwrap_or("in progress"),
+        failures.len(),
+        if failures.len() == 1 { "" } else { "s" }
+    );
+    for failure in &failures {
+        out.push_str(&format!(
+            "\n  {} / {}: {}",
+            failure["job"].as_str().unwrap_or(""),
+            failure["step"].as_str().unwrap_or("(job)"),
+            failure["conclusion"].as_str().unwrap_or("")
+        ));
+        let last = failure["errors"]
+            .as_array()
+            .and_then(|e| e.first())
+            .or(failure["lines"].as_array().and_then(|l| l.last()));
+        if let Some(line) = last.and_then(|l| l.as_str()) {
+            out.push_str(&format!("\n    {}", line));
+        }
+    }
+    out
+}
+
+/// Format act local workflow run summary
+pub fn format_act_summary(result: &Value) -> String {
+    let jobs = result["jobs"].as_array().cloned().unwrap_or_default();
+    let failed: Vec<&Value> = jobs.iter().filter(|j| j["result"] == "failure").collect();
+    let mut out = format!(
+        "act {}{}: {} job{}, {} failed",
+        result["event"].as_str().unwrap_or(""),
+        if result["dry_run"] == true {
+            " (dry run)"
+        } else {
+            ""
+        },
+        jobs.len(),
+        if jobs.len() == 1 { "" } else { "s" },
+        failed.len()
+    );
+    for job in failed {
+        let steps: Vec<&str> = job["failed_steps"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.as_str())
+            .collect();
+        out.push_str(&format!(
+            "\n  {}: {}",
+            job["job"].as_str().unwrap_or(""),
+            if steps.is_empty() {
+                "failed".to_string()
+            } else {
+                format!("failed at {}", steps.join(", "))
+            }
+        ));
+        if let Some(line) = job["output_tail"]
+            .as_array()
+            .and_then(|l| {
+                l.iter()
+                    .rev()
+                    .find(|l| l.as_str().is_some_and(|s| !s.trim().is_empty()))
+            })
+            .and_then(|l| l.as_str())
+        {
+            out.push_str(&format!("\n    {}", line.trim()));
+        }
+    }
+    out
+}
+
+/// Format GitLab CI lint summary
+pub fn format_gitlab_ci_lint_summary(result: &Value) -> String {
+    let list = |key: &str| -> Vec<String> {
+        result[key]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()
+    };
+    let (errors, warnings) = (list("errors"), list("warnings"));
+    let mut out = format!(
+        "{}: {}, {} jobs, {} errors, {} warnings",
+        result["source"].as_str().unwrap_or(""),
+        if result["valid"] == true {
+            "valid"
+        } else {
+            "invalid"
+        },
+        result["jobs"].as_array().map_or(0, |j| j.len()),
+        errors.len(),
+        warnings.len()
+    );
+    for error in &errors {
+        out.push_str(&format!("\n  error: {}", error));
+    }
+    for warning in &warnings {
+        out.push_str(&format!("\n  warning: {}", warning));
+    }
+    out
+}
+
+/// Format GitLab CI jobs summary
+pub fn format_gitlab_ci_jobs_summary(result: &Value) -> String {
+    let jobs = result["jobs"].as_array().map_or(0, |j| j.len());
+    let mut out = format!(
+        "{}: {} jobs, {} templates, {} includes",
+        result["source"].as_str().unwrap_or(""),
+        jobs,
+        result["templates"].as_array().map_or(0, |t| t.len()),
+        result["includes"].as_array().map_or(0, |i| i.len())
+    );
+    for stage in result["by_stage"].as_array().into_iter().flatten() {
+        let names: Vec<&str> = stage["jobs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|j| j.as_str())
+            .collect();
+        if !names.is_empty() {
+            out.push_str(&format!(
+                "\n  {}: {}",
+                stage["stage"].as_str().unwrap_or(""),
+                names.join(", ")
+            ));
+        }
+    }
+    for problem in result["problems"].as_array().into_iter().flatten() {
+        out.push_str(&format!(
+            "\n  problem in {}: {}",
+            problem["job"].as_str().unwrap_or(""),
+            problem["message"].as_str().unwrap_or("")
+        ));
+    }
+    out
+}
+
+/// Format editorconfig normalization summary
+pub fn format_normalize_summary(result: &Value) -> String {
+    let mut out = format!(
+        "{}{}: {} of {} files {}",
+        result["path"].as_str().unwrap_or(""),
+        if result["dry_run"] == true {
+            " (dry run)"
+        } else {
+            ""
+        },
+        result["files_changed"],
+        result["files_checked"],
+        if result["dry_run"] == true {
+            "would change"
+        } else {
+            "normalized"
+        }
+    );
+    if result["without_rules"].as_u64().unwrap_or(0) > 0 {
+        out.push_str(&format!(
+            ", {} without .editorconfig rules",
+            result["without_rules"]
+        ));
+    }
+    for file in result["changed"].as_array().into_iter().flatten() {
+        let c = &file["changes"];
+        let mut parts = vec![];
+        if c["line_endings"] == true {
+            parts.push("line endings".to_string());
+        }
+        if let Some(n) = c["trailing_whitespace_lines"].as_u64().filter(|n| *n > 0) {
+            parts.push(format!("{} trailing whitespace", n));
+        }
+        if let Some(n) = c["reindented_lines"].as_u64().filter(|n| *n > 0) {
+            parts.push(format!("{} reindented", n));
+        }
+        if let Some(change) = c["final_newline"].as_str() {
+            parts.push(format!("final newline {}", change));
+        }
+        out.push_str(&format!(
+            "\n  {}: {}",
+            file["path"].as_str().unwrap_or(""),
+            parts.join(", ")
+        ));
+    }
+    out
+}
+
+/// Format spelling and prose findings summary
+pub fn format_prose_summary(result: &Value) -> String {
+    let findings = result["findings"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "{} {}: {}",
+        result["checker"].as_str().unwrap_or(""),
+        result["path"].as_str().unwrap_or(""),
+        if findings.is_empty() {
+            "no findings".to_string()
+        } else {
+            format!(
+                "{} finding{} in {} files ({} fixable{})",
+                findings.len(),
+                if findings.len() == 1 { "" } else { "s" },
+                result["by_file"].as_object().map_or(0, |m| m.len()),
+                result["fixable"],
+                if result["applied"] == true {
+                    ", applied"
+                } else {
+                    ""
+                }
+            )
+        }
+    );
+    for finding in findings.iter().take(20) {
+        out.push_str(&format!(
+            "\n  {}:{}: {}",
+            finding["path"].as_str().unwrap_or(""),
+            finding["line"],
+            finding["message"].as_str().unwrap_or("")
+        ));
+    }
+    if findings.len() > 20 {
+        out.push_str(&format!("\n  ... and {} more", findings.len() - 20));
+    }
+    out
+}
+
+/// Format i18n coverage summary
+pub fn format_i18n_summary(result: &Value) -> String {
+    let locales = result["locales"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "{}: {} translation keys in {} source files, {} locale{}",
+        result["path"].as_str().unwrap_or(""),
+        result["keys_used"],
+        result["sources_scanned"],
+        locales.len(),
+        if locales.len() == 1 { "" } else { "s" }
+    );
+    for locale in &locales {
+        let mut line = format!(
+            "\n  {}: {}%",
+            locale["locale"].as_str().unwrap_or(""),
+            locale["coverage"]
+        );
+        for key in ["missing", "untranslated", "unused"] {
+            if let Some(n) = locale[key]["count"].as_u64().filter(|n| *n > 0) {
+                line.push_str(&format!(", {} {}", n, key));
+            }
+        }
+        out.push_str(&line);
+    }
+    if let Some(n) = result["in_no_catalog"]["count"].as_u64().filter(|n| *n > 0) {
+        out.push_str(&format!("\n  {} keys are in no catalog", n));
+    }
+    for error in result["errors"].as_array().into_iter().flatten() {
+        out.push_str(&format!(
+            "\n  {}: {}",
+            error["path"].as_str().unwrap_or(""),
+            error["error"].as_str().unwrap_or("")
+        ));
+    }
+    out
+}
+
+/// Format SSH tunnel summary
+pub fn format_tunnel_summary(result: &Value) -> String {
+    let tunnels = result["tunnels"].as_array().cloned().unwrap_or_default();
+    let action = result["action"].as_str().unwrap_or("list");
+    let mut out = match action {
+        "open" => "tunnel opened".to_string(),
+        "stop" => format!("tunnels stopped: {}", tunnels.len()),
+        _ => format!("tunnels: {}", tunnels.len()),
+    };
+    for tunnel in &tunnels {
+        out.push_str(&format!(
+            "\n  #{} {} -{} {} ({})",
+            tunnel["id"],
+            tunnel["host"].as_str().unwrap_or(""),
+            if tunnel["direction"] == "remote" {
+                "R"
+            } else {
+                "L"
+            },
+            tunnel["forward"].as_str().unwrap_or(""),
+            tunnel["status"].as_str().unwrap_or("")
+        ));
+        if action == "list" && tunnel["status"] != "running" {
+            if let Some(last) = tunnel["stderr"].as_array().and_then(|l| l.last()) {
+                out.push_str(&format!(": {}", last.as_str().unwrap_or("")));
+            }
+        }
+    }
+    out
+}
+
+pub fn format_ssh_config_summary(result: &Value) -> String {
+    let agent = if result["agent"]["available"] == true {
+        format!(
+            "{} agent keys",
+            result["agent"]["keys"].as_array().map_or(0, |k| k.len())
+        )
+    } else {
+        "no agent".to_string()
+    };
+    let Some(host) = result["host"].as_str() else {
+        let hosts = result["hosts"].as_array().cloned().unwrap_or_default();
+        let mut out = format!("ssh config: {} hosts, {}", hosts.len(), agent);
+        for host in hosts.iter().filter(|h| h["wildcard"] != true) {
+            let patterns: Vec<&str> = host["patterns"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|p| p.as_str())
+                .collect();
+            out.push_str(&format!("\n  {}", patterns.join(" ")));
+            if let Some(hostname) = host["hostname"].as_str() {
+                out.push_str(&format!(" → {}", hostname));
+            }
+        }
+        return out;
+    };
+    let known = &result["known_hosts"];
+    let mut out = format!(
+        "ssh {}: {}@{}:{}, {}, host key {}",
+        host,
+        result["user"].as_str().unwrap_or(""),
+        result["hostname"].as_str().unwrap_or(""),
+        result["port"],
+        agent,
+        known["verdict"].as_str().unwrap_or("")
+    );
+    if known["updated"] == true {
+        out.push_str(" (known_hosts updated)");
+    }
+    for identity in result["identities"].as_array().into_iter().flatten() {
+        let state = match (identity["exists"] == true, identity["in_agent"] == true) {
+            (_, true) => "in agent",
+            (true, false) => "not in agent",
+            (false, false) => "missing",
+        };
+        out.push_str(&format!(
+            "\n  {} ({})",
+            identity["path"].as_str().unwrap_or(""),
+            state
+        ));
+    }
+    for hint in result["hints"].as_array().into_iter().flatten() {
+        out.push_str(&format!("\n  ! {}", hint.as_str().unwrap_or("")));
+    }
+    out
+}
+
+/// Format buildx bake plan summary
+pub fn format_bake_plan_summary(result: &Value) -> String {
+    let targets = result["targets"].as_array().cloned().unwrap_or_default();
+    let mut out = format!("bake plan: {} targets", targets.len());
+    let list = |v: &Value| {
+        v.as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    for target in &targets {
+        out.push_str(&format!(
+            "\n  {}: {}",
+            target["name"].as_str().unwrap_or(""),
+            target["dockerfile"].as_str().unwrap_or("Dockerfile")
+        ));
+        for (label, key) in [
+            ("tags", "tags"),
+            ("platforms", "platforms"),
+            ("cache from", "cache_from"),
+            ("cache to", "cache_to"),
+        ] {
+            let values = list(&target[key]);
+            if !values.is_empty() {
+                out.push_str(&format!("\n    {}: {}", label, values));
+            }
+        }
+    }
+    out
+}
+
+/// Format compose config summary
+pub fn format_compose_config_summary(result: &Value) -> String {
+    let runtime = result["runtime"].as_str().unwrap_or("compose");
+    if result["valid"] != true {
+        let mut out = format!(
+            "{} config: invalid\n  {}",
+            runtime,
+            result["error"].as_str().unwrap_or("")
+        );
+        for warning in result["warnings"].as_array().into_iter().flatten() {
+            out.push_str(&format!("\n  warning: {}", warning.as_str().unwrap_or("")));
+        }
+        return out;
+    }
+    let services = result["services"].as_array().cloned().unwrap_or_default();
+    let mut out = format!("{} config: valid, {} services", runtime, services.len());
+    for svc in &services {
+        let source = svc["image"]
+            .as_str()
+            .or_else(|| svc["build"].as_str().map(|_| "build"))
+            .unwrap_or("?");
+        let mut line = format!("\n  {}: {}", svc["name"].as_str().unwrap_or(""), source);
+        if let Some(ports) = svc["ports"].as_array().filter(|p| !p.is_empty()) {
+            let ports: Vec<&str> = ports.iter().filter_map(|p| p.as_str()).collect();
+            line.push_str(&format!(" [{}]", ports.join(", ")));
+        }
+        out.push_str(&line);
+    }
+    for warning in result["warnings"].as_array().into_iter().flatten() {
+        out.push_str(&format!("\n  warning: {}", warning.as_str().unwrap_or("")));
+    }
+    out
+}
+
+/// Format bounded log follow summary
+pub fn format_log_follow_summary(result: &Value) -> String {
+    let streams = result["streams"].as_array().cloned().unwrap_or_default();
+    let mut out = format!(
+        "{} lines from {} stream{} in {}s{}",
+        result["lines"],
+        streams.len(),
+        if streams.len() == 1 { "" } else { "s" },
+        result["followed_secs"],
+        if result["resumed"] == true {
+            format!(", resumed ({} already seen)", result["already_seen"])
+        } else {
+            String::new()
+        }
+    );
+    for stream in &streams {
+        out.push_str(&format!(
+            "\n  {}/{}: {} lines",
+            stream["pod"].as_str().unwrap_or(""),
+            stream["container"].as_str().unwrap_or(""),
+            stream["count"]
+        ));
+        if let Some(last) = stream["lines"].as_array().and_then(|l| l.last()) {
+            let message = last["message"].as_str().unwrap_or("");
+            let message = if message.chars().count() > 100 {
+                format!("{}…", message.chars().take(100).collect::<String>())
+            } else {
+                message.to_string()
+            };
+            out.push_str(&format!(", last: {}", message));
+        }
+    }
+    if result["stream_ended"] == true {
+        out.push_str("\n  stream ended before the window closed");
+    }
+    out
+}
+
+/// Format code watch summary
+pub fn format_code_watch_summary(result: &Value) -> String {
+    let mut out = format!(
+        "{}: {} after {} runs in {}s ({} failed)",
+        result["command"].as_str().unwrap_or(""),
+        result["state"].as_str().unwrap_or(""),
+        result["runs"],
+        result["watched_secs"],
+        result["failed_runs"]
+    );
+    for event in result["transitions"].as_array().into_iter().flatten() {
+        let trigger = event["trigger"].as_array().cloned().unwrap_or_default();
+        out.push_str(&format!(
+            "\n  run {} at {}s: {}",
+            event["run"],
+            event["at_secs"],
+            event["transition"].as_str().unwrap_or("")
+        ));
+        match trigger.len() {
+            0 => {}
+            1 => out.push_str(&format!(" after {}", trigger[0].as_str().unwrap_or(""))),
+            n => out.push_str(&format!(" after {} changed files", n)),
+        }
+        if let Some(output) = event["output"].as_str() {
+            for line in output
+                .lines()
+                .rev()
+                .take(5)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+            {
+                out.push_str(&format!("\n    {}", line));
+            }
+        }
+    }
+    out
+}
+
+/// Format search everywhere summary
+pub fn format_search_everywhere_summary(result: &Value) -> String {
+    let mut out = format!(
+        "\"{}\": {} hits",
+        result["query"].as_str().unwrap_or(""),
+        result["total"]
+    );
+    for source in result["sources"].as_array().into_iter().flatten() {
+        let name = source["source"].as_str().unwrap_or("");
+        if source["status"] == "error" {
+            out.push_str(&format!(
+                "\n  {}: {}",
+                name,
+                source["error"].as_str().unwrap_or("failed")
+            ));
+            continue;
+        }
+        out.push_str(&format!("\n  {}: {}", name, source["count"]));
+        for hit in source["results"].as_array().into_iter().flatten().take(3) {
+            let line = match name {
+                "code" => format!(
+                    "{}:{} {}",
+                    hit["path"].as_str().unwrap_or(""),
+                    hit["line"],
+                    hit["text"].as_str().unwrap_or("")
+                ),
+                "context" => format!(
+                    "{} ({})",
+                    hit["key"].as_str().unwrap_or(""),
+                    hit["scope"].as_str().unwrap_or("")
+                ),
+                "tasks" => format!(
+                    "#{} [{}] {}",
+                    hit["id"],
+                    hit["status"].as_str().unwrap_or(""),
+                    hit["content"].as_str().unwrap_or("")
+                ),
+                _ => format!(
+                    "#{} [{}] {}",
+                    hit["number"],
+                    hit["state"].as_str().unwrap_or(""),
+                    hit["title"].as_str().unwrap_or("")
+                ),
+            };
+            let line = if line.chars().count() > 120 {
+                format!("{}…", line.chars().take(120).collect::<String>())
+            } else {
+                line
+            };
+            out.push_str(&format!("\n    {}", line));
+        }
+    }
+    out
+}
+
+/// Format binary headers summary
+pub fn format_binary_headers_summary(result: &Value) -> String {
+    let mut out = format!(
+        "{}: {}",
+        result["path"].as_str().unwrap_or(""),
+        [
+            result["class"].as_str(),
+            result["type"].as_str(),
+            result["format"].as_str().filter(|f| *f != "elf"),
+            result["machine"].as_str(),
+            result["linking"].as_str(),
+        ]
+        .iter()
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+    );
+    if let Some(interp) = result["interpreter"].as_str() {
+        out.push_str(&format!("\n  interpreter: {}", interp));
+    }
+    let needed: Vec<&str> = result["needed"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|n| n.as_str())
+        .collect();
+    if !needed.is_empty() {
+        out.push_str(&format!("\n  needs: {}", needed.join(", ")));
+    }
+    if let Some(glibc) = result["glibc_required"].as_str() {
+        out.push_str(&format!("\n  glibc >= {}", glibc));
+    }
+    let check = &result["run_check"];
+    if check["runnable"] == true {
+        out.push_str("\n  runnable on this host");
+    }
+    for problem in check["problems"].as_array().into_iter().flatten() {
+        out.push_str(&format!("\n  problem: {}", problem.as_str().unwrap_or("")));
+    }
+    out
+}
+
+/// Format release verification summary
+pub fn format_verify_release_summary(result: &Value) -> String {
+    let mut out = format!(
+        "{}: {}",
+        result["path"].as_str().unwrap_or(""),
+        if result["verified"] == true {
+            "verified"
+        } else {
+            "NOT verified"
+        }
+    );
+    let checksum = &result["checksum"];
+    if !checksum.is_null() {
+        out.push_str(&match checksum["error"].as_str() {
+            Some(error) => format!("\n  checksum: {}", error),
+            None => format!(
+                "\n  {}: {}",
+                checksum["algorithm"].as_str().unwrap_or("checksum"),
+                if checksum["matched"] == true {
+                    "matches".to_string()
+                } else {
+                    format!(
+                        "MISMATCH (expected {}, got {})",
+                        checksum["expected"].as_str().unwrap_or(""),
+                        checksum["actual"].as_str().unwrap_or("")
+                    )
+                }
+            ),
+        });
+    }
+    let signature = &result["signature"];
+    if !signature.is_null() {
+        out.push_str(&format!(
+            "\n  {} signature on {}: {}",
+            signature["type"].as_str().unwrap_or(""),
+            signature["signed_file"].as_str().unwrap_or(""),
+            signature["status"].as_str().unwrap_or("")
+        ));
+        if let Some(signer) = signature["signer"].as_str() {
+            out.push_str(&format!(" ({})", signer));
+        }
+    }
+    for warning in result["warnings"].as_array().into_iter().flatten() {
+        out.push_str(&format!("\n  note: {}", warning.as_str().unwrap_or("")));
+    }
+    out
+}
+
+/// Format process detail summary
+pub fn format_proc_detail_summary(result: &Value) -> String {
+    let cmdline: Vec<&str> = result["cmdline"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|a| a.as_str())
+        .collect();
+    let mut out = format!("PID {}", result["pid"]);
+    if let Some(name) = result["status"]["name"].as_str() {
+        out.push_str(&format!(" ({})", name));
+    }
+    out.push_str(&format!(": {}", cmdline.join(" ")));
+    if let Some(cwd) = result["cwd"].as_str() {
+        out.push_str(&format!("\n  cwd: {}", cwd));
+    }
+    match result["environment"]["error"].as_str() {
+        Some(err) => out.push_str(&format!("\n  {}", err)),
+        None => {
+            let vars = result["environment"].as_object();
+            let masked = vars.map_or(0, |v| {
+                v.values()
+                    .filter(|v| v.as_str().is_some_and(|s| s.contains("********")))
+                    .count()
+            });
+            out.push_str(&format!(
+                "\n  env: {} variables ({} masked)",
+                vars.map_or(0, |v| v.len()),
+                masked
+            ));
+        }
+    }
+    match result["open_files"].as_array() {
+        Some(files) => {
+            let count = |kind: &str| files.iter().filter(|f| f["kind"] == kind).count();
+            out.push_str(&format!(
+                "\n  open files: {} ({} files, {} sockets, {} pipes)",
+                result["open_file_count"],
+                count("file"),
+                count("socket"),
+                count("pipe")
+            ));
+        }
+        None => {
+            if let Some(err) = result["open_files"]["error"].as_str() {
+                out.push_str(&format!("\n  {}", err));
+            }
+        }
+    }
+    let listening: Vec<String> = result["listening"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|s| {
+            format!(
+                "{} {}",
+                s["protocol"].as_str().unwrap_or(""),
+                s["local"].as_str().unwrap_or("")
+            )
+        })
+        .collect();
+    if !listening.is_empty() {
+        out.push_str(&format!("\n  listening: {}", listening.join(", ")));
+    }
+    out
+}
+
+/// Format disk health summary
+pub fn format_disk_health_summary(result: &Value) -> String {
+    let mut out = if result["healthy"].as_bool() == Some(true) {
+        "Disks healthy: no warnings".to_string()
+    } else {
+        format!("Disk health: {} warning(s)", result["warning_count"])
+    };
+    if let Some(err) = result["smart_error"].as_str() {
+        out.push_str(&format!("\n  SMART unavailable: {}", err));
+    }
+    for disk in result["disks"].as_array().into_iter().flatten() {
+        let device = disk["device"].as_str().unwrap_or("");
+        if let Some(err) = disk["error"].as_str() {
+            out.push_str(&format!("\n  {}: {}", device, err));
+            continue;
+        }
+        let verdict = match disk["passed"].as_bool() {
+            Some(true) => "PASSED",
+            Some(false) => "FAILED",
+            None => "unknown",
+        };
+        out.push_str(&format!(
+            "\n  {} {}: SMART {}",
+            device,
+            disk["model"].as_str().unwrap_or(""),
+            verdict
+        ));
+        if let Some(t) = disk["temperature_c"].as_u64() {
+            out.push_str(&format!(", {}°C", t));
+        }
+        for warning in disk["warnings"].as_array().into_iter().flatten() {
+            out.push_str(&format!("\n    ! {}", warning.as_str().unwrap_or("")));
+        }
+    }
+    for mount in result["mounts"].as_array().into_iter().flatten() {
+        let warnings = mount["warnings"].as_array().cloned().unwrap_or_default();
+        if warnings.is_empty() {
+            continue;
+        }
+        out.push_str(&format!(
+            "\n  {} ({})",
+            mount["target"].as_str().unwrap_or(""),
+            mount["source"].as_str().unwrap_or("")
+        ));
+        for warning in warnings {
+            out.push_str(&format!("\n    ! {}", warning.as_str().unwrap_or("")));
+        }
+    }
+    out
+}
+
+/// Format resolved time summary
+pub fn format_when_summary(result: &Value) -> String {
+    let times = |entry: &Value| -> Vec<String> {
+        entry["times"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|t| t["display"].as_str().map(String::from))
+            .collect()
+    };
+    let expression = result["expression"].as_str().unwrap_or("");
+    if result["kind"] == "cron" {
+        let occurrences = result["occurrences"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let mut out = format!(
+            "{} ({}): next {} occurrence(s)",
+            expression,
+            result["timezone"].as_str().unwrap_or(""),
+            occurrences.len()
+        );
+        for occurrence in &occurrences {
+            out.push_str(&format!(
+                "\n  {} ({})",
+                times(occurrence).join(" | "),
+                occurrence["relative"].as_str().unwrap_or("")
+            ));
+        }
+        return out;
+    }
+    let mut out = format!(
+        "{} -> {} ({})",
+        expression,
+        result["utc"].as_str().unwrap_or(""),
+        result["relative"].as_str().unwrap_or("")
+    );
+    for time in times(result) {
+        out.push_str(&format!("\n  {}", time));
+    }
+    for note in result["notes"].as_array().into_iter().flatten() {
+        out.push_str(&format!("\n  note: {}", note.as_str().unwrap_or("")));
+    }
+    out
+}
+
+/// Format text statistics summary
+pub fn format_text_stats_summary(result: &Value) -> String {
+    let mut out = format!(
+        "{}: {} chars ({} graphemes), {} words, {} lines, {} bytes",
+        result["source"].as_str().unwrap_or(""),
+        result["chars"],
+        result["graphemes"],
+        result["words"],
+        result["lines"],
+        result["bytes"]
+    );
+    out.push_str(&format!(
+        "\n  encoding: {}{}{}, line endings: {}",
+        result["encoding"]["name"].as_str().unwrap_or(""),
+        if result["encoding"]["bom"] == true {
+            " with BOM"
+        } else {
+            ""
+        },
+        if result["encoding"]["lossy"] == true {
+            " (malformed bytes replaced)"
+        } else {
+            ""
+        },
+        result["line_endings"].as_str().unwrap_or("")
+    ));
+    let language = &result["language"];
+    if let Some(name) = language["name"].as_str() {
+        out.push_str(&format!(
+            "\n  language: {} ({}, {} script, confidence {}{})",
+            name,
+            language["code"].as_str().unwrap_or(""),
+            language["script"].as_str().unwrap_or(""),
+            language["confidence"],
+            if language["reliable"] == true {
+                ""
+            } else {
+                ", unreliable"
+            }
+        ));
+    }
+    out
+}
+
+/// Format workspace summary
+pub fn format_workspace_summary(result: &serde_json::Value) -> String {
+    let mut parts: Vec<String> = vec![];
+    if let Some(types) = result["project_types"].as_array() {
+        if !types.is_empty() {
+            let types: Vec<&str> = types.iter().filter_map(|t| t.as_str()).collect();
+            parts.push(types.join("/"));
+        }
+    }
+    let git = &result["git"];
+    if !git.is_null() {
+        parts.push(format!(
+            "branch {} ({} changed)",
+            git["branch"]["head"].as_str().unwrap_or("?"),
+            git["files"].as_array().map(|f| f.len()).unwrap_or(0)
+        ));
+    }
+    if let Some(code) = result["code_stats"]["code"].as_u64() {
+        parts.push(format!("{} lines of code", code));
+    }
+    if let Some(runners) = result["task_runners"].as_object() {
+        let targets: usize = runners
+            .values()
+            .filter_map(|t| t.as_array())
+            .map(|t| t.len())
+            .sum();
+        if targets > 0 {
+            parts.push(format!("{} task targets", targets));
+        }
+    }
+    parts.push(format!(
+        "{} open tasks",
+        result["open_tasks"]
+            .as_array()
+            .map(|t| t.len())
+            .unwrap_or(0)
+    ));
+    format!("Workspace: {}", parts.join(", "))
+}
+
+/// Format deployment self-test summary
+pub fn format_selftest_summary(result: &serde_json::Value) -> String {
+    let mut out = format!(
+        "Self-test {}: {} passed, {} failed, {} missing ({} ms)",
+        if result["healthy"].as_bool().unwrap_or(false) {
+            "healthy"
+        } else {
+            "UNHEALTHY"
+        },
+        result["passed"].as_u64().unwrap_or(0),
+        result["failed"].as_u64().unwrap_or(0),
+        result["missing"].as_u64().unwrap_or(0),
+        result["duration_ms"].as_u64().unwrap_or(0)
+    );
+    for group in result["groups"].as_array().into_iter().flatten() {
+        let status = group["status"].as_str().unwrap_or("?");
+        out.push_str(&format!(
+            "\n  {:<8} {:<11} {:>6} ms  {}",
+            status.to_uppercase(),
+            group["group"].as_str().unwrap_or("?"),
+            group["duration_ms"].as_u64().unwrap_or(0),
+            group["tool"].as_str().unwrap_or("?")
+        ));
+        if let Some(detail) = group["detail"].as_str() {
+            out.push_str(&format!(": {}", detail));
+        }
+    }
+    out
+}
+
+/// Format workspace trust summary
+pub fn format_trust_summary(result: &serde_json::Value) -> String {
+    let path = result["path"].as_str().unwrap_or("?");
+    let state = match result["action"].as_str().unwrap_or("review") {
+        "granted" => "now trusted".to_string(),
+        "revoked" => "no longer trusted".to_string(),
+        _ if !result["enforced"].as_bool().unwrap_or(true) => {
+            "trusted (trust checks are off)".to_string()
+        }
+        _ if result["trusted"].as_bool().unwrap_or(false) => "trusted".to_string(),
+        _ => "not trusted; confirm with the user before trusting".to_string(),
+    };
+    let mut out = format!("{}: {}", path, state);
+    if let Some(remote) = result["remote"].as_str() {
+        out.push_str(&format!("\nRemote: {}", remote));
+    }
+    let runs: Vec<&str> = ["runs_code", "git_hooks"]
+        .iter()
+        .flat_map(|key| result[*key].as_array().into_iter().flatten())
+        .filter_map(|v| v.as_str())
+        .collect();
+    if !runs.is_empty() {
+        out.push_str(&format!("\nRuns code: {}", runs.join(", ")));
+    }
+    out
+}
+
+/// Format package registry lookup summary
+pub fn format_registry_summary(result: &serde_json::Value) -> String {
+    let mut summary = format!(
+        "{} ({}): latest {}",
+        result["package"].as_str().unwrap_or("?"),
+        result["registry"].as_str().unwrap_or("?"),
+        result["latest"].as_str().unwrap_or("unknown")
+    );
+    if let Some(released) = result["latest_released"].as_str() {
+        summary.push_str(&format!(" ({})", released.get(..10).unwrap_or(released)));
+    }
+    let downloads = &result["downloads"];
+    for (key, label) in [
+        ("total", "downloads"),
+        ("last_month", "downloads/month"),
+        ("last_week", "downloads/week"),
+    ] {
+        if let Some(n) = downloads[key].as_u64() {
+            summary.push_str(&format!(", {} {}", n, label));
+            break;
+        }
+    }
+    if let Some(requirement) = result["requirement"].as_str() {
+        summary.push_str(&format!(
+            "; {} -> {}",
+            requirement,
+            result["latest_matching"]["version"]
+                .as_str()
+                .unwrap_or("no match")
+        ));
+    }
+    if let Some(deprecated) = result["deprecated"].as_str() {
+        summary.push_str(&format!("; DEPRECATED: {}", deprecated));
+    }
+    summary
+}
+
+/// Format recently touched files summary
+pub fn format_recent_files_summary(result: &serde_json::Value) -> String {
+    let files = result["files"].as_array().cloned().unwrap_or_default();
+    let session = result["session"].as_str().unwrap_or("?");
+    if files.is_empty() {
+        return format!("No files touched in session {}", session);
+    }
+    let (mut edited, mut read_only) = (0, 0);
+    for file in &files {
+        if file["writes"].as_i64().unwrap_or(0) + file["edits"].as_i64().unwrap_or(0) > 0 {
+            edited += 1;
+        } else {
+            read_only += 1;
+        }
+    }
+    let latest: Vec<String> = files
+        .iter()
+        .take(3)
+        .map(|f| {
+            format!(
+                "{} ({})",
+                f["path"].as_str().unwrap_or("?"),
+                f["last_action"].as_str().unwrap_or("?")
+            )
+        })
+        .collect();
+    format!(
+        "{} files in session {} ({} changed, {} read only); latest: {}",
+        files.len(),
+        session,
+        edited,
+        read_only,
+        latest.join(", ")
+    )
+}
+
+/// Format forge webhook events poll summary
+pub fn format_events_poll_summary(result: &serde_json::Value) -> String {
+    let events = result["events"].as_array().cloned().unwrap_or_default();
+    if events.is_empty() {
+        return format!(
+            "No new forge events (cursor {})",
+            result["next_since"].as_i64().unwrap_or(0)
+        );
+    }
+    let mut counts: std::collections::BTreeMap<String, usize> = Default::default();
+    for event in &events {
+        let key = format!(
+            "{}:{}",
+            event["source"].as_str().unwrap_or("?"),
+            event["event"].as_str().unwrap_or("?")
+        );
+        *counts.entry(key).or_default() += 1;
+    }
+    let counts: Vec<String> = counts
+        .into_iter()
+        .map(|(k, n)| format!("{} {}", n, k))
+        .collect();
+    format!(
+        "{} forge events ({}), next_since {}{}",
+        events.len(),
+        counts.join(", "),
+        result["next_since"],
+        if result["more"] == true {
+            ", more pending"
+        } else {
+            ""
+        }
+    )
+}
@@ -68,6 +68,7 @@ impl ToolGroup {
                 "Filesystem - Hardlink",
                 "Filesystem - File Type",
                 "Filesystem - Permissions",
+                "Filesystem - Verify Release",
             ],
             ToolGroup::FileOps => &[
                 "File - Read",
@@ -75,14 +76,23 @@ impl ToolGroup {
                 "File - Edit",
                 "File - Append",
                 "File - Patch",
+                "File - Outline Read",
+                "Code - Normalize",
+                "Code - Watch",
             ],
             ToolGroup::Search => &[
                 "Search - Content (ripgrep)",
                 "Search - Fuzzy (fzf)",
                 "Search - Web (DuckDuckGo)",
                 "Search - AST (ast-grep)",
+                "Search - AST Scan (ast-grep)",
+                "Search - Replace Project",
+                "Docs - Prose",
                 "Code - Symbols",
                 "Code - References",
+                "Code - I18n",
+                "Search - Everywhere",
+                "Code - Registry",
             ],
             ToolGroup::Text => &[
                 "Text - JSON (jq)",
@@ -96,6 +106,8 @@ impl ToolGroup {
                 "Text - Data Process (miller)",
                 "Text - CSV (xsv)",
                 "Text - Find Replace (sad)",
+                "Text - Log Parse",
+                "Text - Stats",
             ],
             ToolGroup::Git => &[
                 "Git - Status",
@@ -106,6 +118,10 @@ impl ToolGroup {
                 "Git - Checkout",
                 "Git - Branch",
                 "Git - Stash",
+                "Git - Discover",
+                "Git - Foreach",
+                "Git - Format Patch",
+                "Git - Export Diff",
             ],
             ToolGroup::GitHub => &[
                 "GitHub - Auth Login",
@@ -117,6 +133,8 @@ impl ToolGroup {
                 "GitHub - Release",
                 "GitHub - Workflow",
                 "GitHub - Workflow Run",
+                "GitHub - Run Logs",
+                "GitHub - Act (local CI)",
                 "GitHub - API",
             ],
             ToolGroup::GitLab => &[
@@ -125,6 +143,8 @@ impl ToolGroup {
                 "GitLab - Issue",
                 "GitLab - Merge Request",
                 "GitLab - Pipeline",
+                "GitLab - CI Lint",
+                "GitLab - CI Jobs",
             ],
             ToolGroup::Kubernetes => &[
                 "Kubernetes - Get",
@@ -133,12 +153,16 @@ impl ToolGroup {
                 "Kubernetes - Describe",
                 "Kubernetes - Logs",
                 "Kubernetes - Exec",
+                "Kubernetes - Wait",
+                "Kubernetes - Wait Ready",
                 "Kubernetes - Multi-Logs (stern)",
                 "Kubernetes - Helm",
                 "Kubernetes - Kustomize",
+                "Kubernetes - Lint",
             ],
             ToolGroup::Container => &[
                 "Container - Podman",
+                "Container - Exec",
                 "Container - Compose",
                 "Container - Buildx",
                 "Container - Build (buildah)",
@@ -146,43 +170,68 @@ impl ToolGroup {
                 "Container - Registry Low-level (crane)",
                 "Container - Image Analyze (dive)",
                 "Security - Scan (trivy)",
+                "Container - Image Report",
+                "Security - Inspect Key",
             ],
             ToolGroup::Network => &[
                 "Network - HTTP (xh)",
                 "Network - SQL (usql)",
                 "Network - DNS (doggo)",
+                "Network - OpenAPI",
+                "Network - SSH Config",
+                "Network - Tunnel",
             ],
             ToolGroup::System => &[
                 "Shell - Execute",
                 "Shell - Nix",
                 "System - Processes (procs)",
+                "System - Process Detail",
+                "System - Disk Health",
                 "System - Benchmark (hyperfine)",
                 "System - Info",
                 "Test - Shell (bats)",
                 "System - Code Stats (tokei)",
+                "Binary - Strings",
+                "Binary - Headers",
+                "Binary - Scan (binwalk)",
             ],
             ToolGroup::Archive => &[
                 "Archive - Compress (ouch)",
                 "Archive - Decompress (ouch)",
                 "Archive - List (ouch)",
+                "Archive - Extract File",
+                "Archive - Convert",
             ],
             ToolGroup::Reference => &[
                 "Reference - TLDR",
                 "Reference - Cheatsheets (navi)",
                 "Reference - Regex Generator (grex)",
+                "Reference - When",
+            ],
+            ToolGroup::Diff => &[
+                "Diff - Files (delta)",
+                "Diff - Structural (difft)",
+                "Diff - Directories",
             ],
-            ToolGroup::Diff => &["Diff - Files (delta)", "Diff - Structural (difft)"],
             ToolGroup::Mcp => &[
                 "MCP - Auth Check",
                 "MCP - Task Create",
                 "MCP - Task List",
                 "MCP - Task Update",
                 "MCP - Task Delete",
+                "MCP - Task Schedule",
+                "MCP - Task History",
                 "MCP - Context Get",
                 "MCP - Context Set",
                 "MCP - Context List",
                 "MCP - Cache Get",
                 "MCP - Cache Set",
+                "MCP - Workspace Summary",
+                "MCP - Events Poll",
+                "MCP - Recent Files",
+                "MCP - Trust Workspace",
+                "MCP - Self Test",
+                "MCP - Output Page",
             ],
         }
     }
@@ -233,20 +282,20 @@ impl ToolGroup {
     pub fn description(&self) -> &'static str {
         match self {
             ToolGroup::Filesystem => "List directories (eza), view files (bat), find files (fd), disk usage (duf/dust), trash management, copy/move/mkdir",
-            ToolGroup::FileOps => "Read, write, edit, append, and patch files",
-            ToolGroup::Search => "Search content (ripgrep), fuzzy find (fzf), web search, AST-based code search, symbols and references",
+            ToolGroup::FileOps => "Read, write, edit, append, and patch files; apply .editorconfig whitespace rules",
+            ToolGroup::Search => "Search content (ripgrep), fuzzy find (fzf), web search, AST-based code search, symbols and references, package registry lookups (crates.io/npm/PyPI)",
             ToolGroup::Text => "JSON (jq), YAML (yq), HTML (htmlq/pup), CSV (xsv), data processing (miller), find/replace (sd/sad)",
             ToolGroup::Git => "Status, diff, log, add, commit, checkout, branch, stash operations",
-            ToolGroup::GitHub => "Repository, issue, PR, release, workflow, and API operations via gh CLI",
-            ToolGroup::GitLab => "Issue, merge request, and pipeline operations via glab CLI",
-            ToolGroup::Kubernetes => "kubectl get/apply/delete/describe/logs/exec, Helm charts, Kustomize, multi-pod logs (stern)",
-            ToolGroup::Container => "Podman/Docker containers, compose orchestration (podman-compose/docker compose), buildx multi-platform builds, buildah OCI images, registry operations (skopeo/crane), image analysis (dive), security scanning (trivy)",
-            ToolGroup::Network => "HTTP requests (xh), SQL queries (usql), DNS lookups",
-            ToolGroup::System => "Shell execution, Nix shells, process listing (procs), benchmarking (hyperfine), system info, shell tests (bats), code stats (tokei)",
+            ToolGroup::GitHub => "Repository, issue, PR, release, workflow, failing run logs, local workflow runs (act), and API operations via gh CLI",
+            ToolGroup::GitLab => "Issue, merge request, and pipeline operations via glab CLI; CI config lint and job listing",
+            ToolGroup::Kubernetes => "kubectl get/apply/delete/describe/logs/exec/wait, rollout readiness, Helm charts (install and authoring: create/lint/package), Kustomize, multi-pod logs (stern), manifest linting (kube-linter/polaris)",
+            ToolGroup::Container => "Podman/Docker containers and exec into running ones, compose orchestration and config validation (podman-compose/docker compose), buildx multi-platform builds, bake and build cache management, buildah OCI images, registry operations (skopeo/crane), image analysis (dive), security scanning (trivy), image reports with packages and licenses (syft), certificate/key inspection",
+            ToolGroup::Network => "HTTP requests (xh), OpenAPI operations, SQL queries (usql), DNS lookups, SSH config checks and tunnels",
+            ToolGroup::System => "Shell execution, Nix shells, process listing (procs) and per-PID detail, disk health (smartctl), benchmarking (hyperfine), system info, shell tests (bats), code stats (tokei)",
             ToolGroup::Archive => "Compress, decompress, and list archives (ouch) - supports tar.gz, zip, 7z, xz, bz2, zstd",
-            ToolGroup::Reference => "Command help (tldr), cheatsheets (navi), regex generation (grex)",
+            ToolGroup::Reference => "Command help (tldr), cheatsheets (navi), regex generation (grex), time and cron resolution across timezones",
             ToolGroup::Diff => "File diffs with syntax highlighting (delta), structural/AST-aware diffs (difftastic)",
-            ToolGroup::Mcp => "MCP task tracking, context storage, and caching for session state",
+            ToolGroup::Mcp => "MCP task tracking, context storage, caching and workspace trust for session state",
         }
     }
 
@@ -436,6 +485,95 @@ impl AgentProfile {
         }
     }
 
+    /// Tool-selection guidance for this profile's typical workflow.
+    pub fn guidance(&self) -> &'static str {
+        match self {
+            AgentProfile::Explore => "Start with MCP - Workspace Summary, map the tree with eza/fd, then ripgrep and Code - Symbols before reading whole files.",
+            AgentProfile::Architect => "Survey structure with eza, tokei and Code - Symbols; use ast-grep for cross-cutting patterns and tldr for unfamiliar tools.",
+            AgentProfile::Review => "Begin from Git - Diff or GitHub - Pull Request diff; use difftastic for refactors and Code - References to check call sites.",
+            AgentProfile::Test => "Read the code under test, run suites through Shell - Execute or bats, and track failures with MCP tasks.",
+            AgentProfile::Generator => "Locate code with ripgrep/fd, edit with File - Edit or File - Patch, verify with Shell - Execute, then Git - Status.",
+            AgentProfile::Reflector => "Read files and Git - Log history; prefer read-only tools and record findings in MCP context.",
+            AgentProfile::Curator => "Search playbooks with ripgrep, update them with File - Edit, and keep notes in MCP context.",
+            AgentProfile::Docs => "Find docs with fd, check commands with tldr, edit with File - Edit, and run Docs - Prose before committing; bat renders files with highlighting.",
+            AgentProfile::Lint => "Run linters with Shell - Execute, find patterns with ast-grep scan, and fix with Search - Replace Project or File - Edit.",
+            AgentProfile::Api => "Call endpoints with xh, reshape responses with jq/yq, and save fixtures with File - Write.",
+            AgentProfile::DevDeploy => "Build with buildx, apply with kubectl, then Kubernetes - Wait Ready; follow logs with stern.",
+            AgentProfile::Full => "Prefer the specific tool over Shell - Execute; use suggest_tools when unsure which tool fits.",
+        }
+    }
+
+    /// Tools this profile reaches for first, in priority order.
+    pub fn preferred_tools(&self) -> &'static [&'static str] {
+        match self {
+            AgentProfile::Explore => &[
+                "MCP - Workspace Summary",
+                "Filesystem - List (eza)",
+                "Filesystem - Find (fd)",
+                "Search - Content (ripgrep)",
+                "Code - Symbols",
+            ],
+            AgentProfile::Architect => &[
+                "Filesystem - List (eza)",
+                "System - Code Stats (tokei)",
+                "Code - Symbols",
+                "Search - AST (ast-grep)",
+                "Reference - TLDR",
+            ],
+            AgentProfile::Review => &[
+                "Git - Diff",
+                "Diff - Structural (difft)",
+                "Code - References",
+                "Search - Content (ripgrep)",
+                "Git - Log",
+            ],
+            AgentProfile::Test => &[
+                "Shell - Execute",
+                "Test - Shell (bats)",
+                "Code - Watch",
+                "File - Read",
+                "Search - Content (ripgrep)",
+            ],
+            AgentProfile::Generator => &[
+                "Search - Content (ripgrep)",
+                "File - Read",
+                "File - Edit",
+                "File - Patch",
+                "Shell - Execute",
+                "Git - Status",
+            ],
+            AgentProfile::Reflector => &["File - Read", "Git - Log", "Git - Diff"],
+            AgentProfile::Curator => &["Search - Content (ripgrep)", "File - Read", "File - Edit"],
+            AgentProfile::Docs => &[
+                "Filesystem - Find (fd)",
+                "File - Read",
+                "File - Edit",
+                "Docs - Prose",
+                "Reference - TLDR",
+            ],
+            AgentProfile::Lint => &[
+                "Shell - Execute",
+                "Search - AST Scan (ast-grep)",
+                "Search - Replace Project",
+                "File - Edit",
+            ],
+            AgentProfile::Api => &[
+                "Network - HTTP (xh)",
+                "Text - JSON (jq)",
+                "Text - YAML (yq)",
+                "File - Write",
+            ],
+            AgentProfile::DevDeploy => &[
+                "Container - Buildx",
+                "Kubernetes - Apply",
+                "Kubernetes - Wait Ready",
+                "Kubernetes - Multi-Logs (stern)",
+                "GitHub - Workflow Run",
+            ],
+            AgentProfile::Full => &[],
+        }
+    }
+
     /// Tool count for pre-expanded groups.
     pub fn pre_expanded_tool_count(&self) -> usize {
         self.pre_expanded_groups()
@@ -490,6 +628,340 @@ pub fn find_tool_group(tool_name: &str) -> Option<ToolGroup> {
         .copied()
 }
 
+/// Tools commonly used together with `tool_name`.
+pub fn related_tools(tool_name: &str) -> &'static [&'static str] {
+    match tool_name {
+        "Filesystem - List (eza)" => &[
+            "Filesystem - Find (fd)",
+            "Filesystem - Directory Size (dust)",
+        ],
+        "Filesystem - Find (fd)" => &["Search - Content (ripgrep)", "File - Read"],
+        "Filesystem - View (bat)" => &["File - Read", "Search - Content (ripgrep)"],
+        "Filesystem - Trash (rip)" => &[
+            "Filesystem - Trash List (rip)",
+            "Filesystem - Trash Restore (rip)",
+        ],
+        "File - Read" => &["File - Edit", "Code - Symbols"],
+        "File - Edit" => &["File - Patch", "Git - Diff"],
+        "File - Patch" => &["File - Edit", "Git - Diff"],
+        "File - Write" => &["File - Read", "Git - Status"],
+        "Search - Content (ripgrep)" => &["Code - Symbols", "Code - References", "File - Read"],
+        "Search - AST (ast-grep)" => &["Search - AST Scan (ast-grep)", "Search - Replace Project"],
+        "Search - Replace Project" => &["Search - AST (ast-grep)", "Git - Diff"],
+        "Docs - Prose" => &["File - Edit", "Git - Diff"],
+        "Code - Watch" => &["File - Edit", "Text - Log Parse"],
+        "Search - Everywhere" => &["Search - Content (ripgrep)", "GitHub - Search"],
+        "Binary - Headers" => &["Binary - Strings", "Binary - Scan (binwalk)"],
+        "Code - Symbols" => &["Code - References", "File - Read"],
+        "Code - References" => &["Code - Symbols", "Search - Content (ripgrep)"],
+        "Code - Registry" => &["Search - Web (DuckDuckGo)", "File - Edit"],
+        "Text - JSON (jq)" => &["Text - YAML (yq)", "Text - JSON Grep (gron)"],
+        "Text - YAML (yq)" => &["Text - JSON (jq)", "Text - Universal (dasel)"],
+        "Text - Log Parse" => &["Kubernetes - Logs", "Search - Content (ripgrep)"],
+        "Git - Status" => &["Git - Diff", "Git - Add", "Git - Commit"],
+        "Git - Diff" => &["Diff - Structural (difft)", "Git - Commit"],
+        "Git - Add" => &["Git - Commit", "Git - Status"],
+        "Git - Commit" => &["Git - Log", "GitHub - Pull Request"],
+        "Git - Log" => &["Git - Diff"],
+        "GitHub - Pull Request" => &["GitHub - Workflow Run", "Git - Diff"],
+        "GitHub - Workflow Run" => &[
+            "GitHub - Run Logs",
+            "GitHub - Workflow",
+            "GitHub - Pull Request",
+        ],
+        "GitHub - Run Logs" => &["GitHub - Act (local CI)", "GitHub - Workflow Run"],
+        "GitLab - Merge Request" => &["GitLab - Pipeline"],
+        "GitLab - CI Jobs" => &["GitLab - CI Lint", "GitLab - Pipeline"],
+        "GitLab - CI Lint" => &["GitLab - CI Jobs", "GitLab - Pipeline"],
+        "Kubernetes - Apply" => &["Kubernetes - Wait Ready", "Kubernetes - Get"],
+        "Kubernetes - Lint" => &["Kubernetes - Apply"],
+        "Kubernetes - Get" => &["Kubernetes - Describe", "Kubernetes - Logs"],
+        "Kubernetes - Logs" => &["Kubernetes - Multi-Logs (stern)", "Text - Log Parse"],
+        "Kubernetes - Wait Ready" => &["Kubernetes - Describe", "Kubernetes - Logs"],
+        "Container - Compose" => &["Container - Podman", "Text - Log Parse"],
+        "Container - Exec" => &["Container - Podman", "Container - Compose"],
+        "Container - Buildx" => &[
+            "Container - Image Analyze (dive)",
+            "Security - Scan (trivy)",
+        ],
+        "Container - Image Report" => &[
+            "Security - Scan (trivy)",
+            "Container - Image Analyze (dive)",
+        ],
+        "Network - HTTP (xh)" => &["Text - JSON (jq)"],
+        "Network - OpenAPI" => &["Network - HTTP (xh)", "Text - JSON (jq)"],
+        "Network - SSH Config" => &["Security - Inspect Key", "Network - Tunnel"],
+        "Network - Tunnel" => &["Network - SSH Config", "Network - SQL (usql)"],
+        "Shell - Execute" => &["MCP - Task Schedule", "System - Processes (procs)"],
+        "System - Processes (procs)" => &["System - Process Detail"],
+        "System - Benchmark (hyperfine)" => &["System - Code Stats (tokei)"],
+        "Archive - List (ouch)" => &["Archive - Extract File", "Archive - Decompress (ouch)"],
+        "Diff - Files (delta)" => &["Diff - Structural (difft)"],
+        "Diff - Directories" => &["Diff - Files (delta)", "Diff - Structural (difft)"],
+        "MCP - Task Create" => &["MCP - Task List", "MCP - Task Schedule"],
+        "MCP - Task Schedule" => &["MCP - Task History", "Reference - When"],
+        "MCP - Workspace Summary" => &["Git - Status", "Code - Symbols", "MCP - Recent Files"],
+        "MCP - Recent Files" => &["Git - Diff", "File - Read"],
+        "MCP - Trust Workspace" => &["MCP - Workspace Summary", "Git - Status"],
+        "MCP - Self Test" => &["MCP - Auth Check", "System - Info"],
+        _ => &[],
+    }
+}
+
+/// Task words that point at tools whose names don't contain them.
+const KEYWORDS: &[(&str, &[&str])] = &[
+    ("grep", &["Search - Content (ripgrep)"]),
+    ("search", &["Search - Content (ripgrep)"]),
+    ("usage", &["Code - References"]),
+    ("callers", &["Code - References"]),
+    ("function", &["Code - Symbols"]),
+    ("definition", &["Code - Symbols"]),
+    (
+        "refactor",
+        &["Search - Replace Project", "Search - AST (ast-grep)"],
+    ),
+    ("rename", &["Search - Replace Project"]),
+    (
+        "replace",
+        &["Search - Replace Project", "Text - Substitute (sd)"],
+    ),
+    ("ls", &["Filesystem - List (eza)"]),
+    ("tree", &["Filesystem - List (eza)"]),
+    ("delete", &["Filesystem - Trash (rip)"]),
+    ("remove", &["Filesystem - Trash (rip)"]),
+    (
+        "size",
+        &[
+            "Filesystem - Directory Size (dust)",
+            "Filesystem - Disk Usage (duf)",
+        ],
+    ),
+    ("modify", &["File - Edit"]),
+    ("change", &["File - Edit", "Git - Diff"]),
+    ("create", &["File - Write"]),
+    ("checksum", &["Filesystem - Verify Release"]),
+    ("signature", &["Filesystem - Verify Release"]),
+    ("gpg", &["Filesystem - Verify Release"]),
+    ("cosign", &["Filesystem - Verify Release"]),
+    ("binary", &["Binary - Headers", "Binary - Strings"]),
+    ("elf", &["Binary - Headers"]),
+    ("readelf", &["Binary - Headers"]),
+    ("glibc", &["Binary - Headers"]),
+    ("firmware", &["Binary - Scan (binwalk)"]),
+    ("everywhere", &["Search - Everywhere"]),
+    ("seen", &["Search - Everywhere"]),
+    ("i18n", &["Code - I18n"]),
+    ("crate", &["Code - Registry"]),
+    ("npm", &["Code - Registry"]),
+    ("pypi", &["Code - Registry"]),
+    ("dependency", &["Code - Registry"]),
+    ("translation", &["Code - I18n"]),
+    ("locale", &["Code - I18n"]),
+    ("spelling", &["Docs - Prose"]),
+    ("typos", &["Docs - Prose"]),
+    ("prose", &["Docs - Prose"]),
+    ("watch", &["Code - Watch"]),
+    ("rerun", &["Code - Watch"]),
+    ("editorconfig", &["Code - Normalize"]),
+    ("whitespace", &["Code - Normalize"]),
+    ("indentation", &["Code - Normalize"]),
+    ("json", &["Text - JSON (jq)"]),
+    ("yaml", &["Text - YAML (yq)"]),
+    ("toml", &["Text - Universal (dasel)"]),
+    ("csv", &["Text - CSV (xsv)"]),
+    ("html", &["Text - HTML Query (htmlq)"]),
+    ("logs", &["Text - Log Parse", "Kubernetes - Logs"]),
+    ("errors", &["Text - Log Parse"]),
+    ("history", &["Git - Log"]),
+    ("blame", &["Git - Log"]),
+    ("changes", &["Git - Status", "Git - Diff"]),
+    ("pr", &["GitHub - Pull Request"]),
+    ("review", &["GitHub - Pull Request", "Git - Diff"]),
+    ("ci", &["GitHub - Workflow Run", "GitLab - Pipeline"]),
+    ("failing", &["GitHub - Run Logs"]),
+    (
+        "actions",
+        &["GitHub - Workflow Run", "GitHub - Act (local CI)"],
+    ),
+    ("mr", &["GitLab - Merge Request"]),
+    ("stages", &["GitLab - CI Jobs"]),
+    ("pod", &["Kubernetes - Get", "Kubernetes - Logs"]),
+    ("pods", &["Kubernetes - Get", "Kubernetes - Logs"]),
+    ("deploy", &["Kubernetes - Apply", "Kubernetes - Wait Ready"]),
+    ("rollout", &["Kubernetes - Wait Ready"]),
+    ("chart", &["Kubernetes - Helm"]),
+    ("lint", &["Kubernetes - Lint"]),
+    ("manifest", &["Kubernetes - Lint", "Kubernetes - Apply"]),
+    ("docker", &["Container - Podman", "Container - Compose"]),
+    ("exec", &["Container - Exec", "Kubernetes - Exec"]),
+    ("bake", &["Container - Buildx"]),
+    (
+        "image",
+        &["Container - Buildx", "Container - Image Analyze (dive)"],
+    ),
+    ("vulnerabilities", &["Security - Scan (trivy)"]),
+    ("cve", &["Security - Scan (trivy)"]),
+    ("sbom", &["Container - Image Report"]),
+    ("license", &["Container - Image Report"]),
+    ("licenses", &["Container - Image Report"]),
+    ("certificate", &["Security - Inspect Key"]),
+    ("cert", &["Security - Inspect Key"]),
+    ("pem", &["Security - Inspect Key"]),
+    ("api", &["Network - HTTP (xh)", "Network - OpenAPI"]),
+    ("openapi", &["Network - OpenAPI"]),
+    ("swagger", &["Network - OpenAPI"]),
+    ("propagation", &["Network - DNS (doggo)"]),
+    ("ssh", &["Network - SSH Config"]),
+    ("known_hosts", &["Network - SSH Config"]),
+    ("publickey", &["Network - SSH Config"]),
+    ("tunnel", &["Network - Tunnel"]),
+    ("bastion", &["Network - Tunnel", "Network - SSH Config"]),
+    ("forward", &["Network - Tunnel"]),
+    ("request", &["Network - HTTP (xh)"]),
+    ("curl", &["Network - HTTP (xh)"]),
+    ("database", &["Network - SQL (usql)"]),
+    ("query", &["Network - SQL (usql)"]),
+    ("run", &["Shell - Execute"]),
+    ("command", &["Shell - Execute"]),
+    ("build", &["Shell - Execute"]),
+    ("test", &["Shell - Execute", "Test - Shell (bats)"]),
+    ("tests", &["Shell - Execute", "Test - Shell (bats)"]),
+    ("benchmark", &["System - Benchmark (hyperfine)"]),
+    ("performance", &["System - Benchmark (hyperfine)"]),
+    ("loc", &["System - Code Stats (tokei)"]),
+    (
+        "zip",
+        &["Archive - Compress (ouch)", "Archive - Decompress (ouch)"],
+    ),
+    (
+        "extract",
+        &["Archive - Decompress (ouch)", "Archive - Extract File"],
+    ),
+    ("convert", &["Archive - Convert"]),
+    ("unpack", &["Archive - Decompress (ouch)"]),
+    ("regex", &["Reference - Regex Generator (grex)"]),
+    ("help", &["Reference - TLDR"]),
+    (
+        "compare",
+        &[
+            "Diff - Files (delta)",
+            "Diff - Structural (difft)",
+            "Diff - Directories",
+        ],
+    ),
+    ("pid", &["System - Process Detail"]),
+    ("smart", &["System - Disk Health"]),
+    (
+        "disk",
+        &["System - Disk Health", "Filesystem - Disk Usage (duf)"],
+    ),
+    ("listening", &["System - Process Detail"]),
+    ("todo", &["MCP - Task Create", "MCP - Task List"]),
+    ("remember", &["MCP - Context Set"]),
+    ("cron", &["MCP - Task Schedule", "Reference - When"]),
+    ("timezone", &["Reference - When"]),
+    ("when", &["Reference - When"]),
+    ("schedule", &["MCP - Task Schedule"]),
+    ("webhook", &["MCP - Events Poll"]),
+    ("overview", &["MCP - Workspace Summary"]),
+    ("resume", &["MCP - Recent Files", "MCP - Workspace Summary"]),
+    ("trust", &["MCP - Trust Workspace"]),
+    ("selftest", &["MCP - Self Test"]),
+    ("healthy", &["MCP - Self Test"]),
+];
+
+/// Words too common to say anything about the right tool.
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "into", "that", "this", "all", "files", "file", "how",
+    "what", "which", "want", "need", "use", "using", "tool", "find",
+];
+
+/// A ranked tool recommendation.
+#[derive(Debug, Clone)]
+pub struct ToolSuggestion {
+    pub tool: &'static str,
+    pub group: ToolGroup,
+    pub score: u32,
+    /// Task words that matched
+    pub matched: Vec<String>,
+}
+
+/// Rank tools for a task description.
+///
+/// Task words are matched against tool names, group descriptions and a
+/// keyword table; tools the active profile prefers or pre-expands are
+/// boosted, but only tools with at least one textual match are returned.
+pub fn suggest_tools(
+    task: &str,
+    profile: Option<AgentProfile>,
+    limit: usize,
+) -> Vec<ToolSuggestion> {
+    let words: Vec<String> = task
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 2 && !STOPWORDS.contains(w))
+        .map(String::from)
+        .collect();
+    // "find" alone is a stopword, but finding files is still fd
+    let wants_files = task.to_lowercase().contains("find file");
+
+    let mut suggestions: Vec<ToolSuggestion> = vec![];
+    for group in ToolGroup::ALL {
+        let group_text = format!("{} {}", group.id(), group.description()).to_lowercase();
+        let group_words: HashSet<&str> = group_text.split(|c: char| !c.is_alphanumeric()).collect();
+        for tool in group.tools() {
+            let name = tool.to_lowercase();
+            let name_words: Vec<&str> = name.split(|c: char| !c.is_alphanumeric()).collect();
+            let mut score = 0;
+            let mut matched = vec![];
+            for word in &words {
+                let mut hit = 0;
+                if name_words.contains(&word.as_str()) {
+                    hit += 3;
+                }
+                if KEYWORDS
+                    .iter()
+                    .any(|(k, tools)| k == word && tools.contains(tool))
+                {
+                    hit += 3;
+                }
+                if hit == 0 && word.len() >= 4 && group_words.contains(word.as_str()) {
+                    hit += 1;
+                }
+                if hit > 0 {
+                    score += hit;
+                    matched.push(word.clone());
+                }
+            }
+            if wants_files && *tool == "Filesystem - Find (fd)" {
+                score += 3;
+                matched.push("find file".to_string());
+            }
+            if score == 0 {
+                continue;
+            }
+            if let Some(profile) = profile {
+                if profile.preferred_tools().contains(tool) {
+                    score += 2;
+                }
+                if profile.pre_expanded_groups().contains(group) {
+                    score += 1;
+                }
+            }
+            suggestions.push(ToolSuggestion {
+                tool,
+                group: *group,
+                score,
+                matched,
+            });
+        }
+    }
+
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.tool.cmp(b.tool)));
+    suggestions.truncate(limit);
+    suggestions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -514,6 +986,53 @@ mod tests {
         assert_eq!(AgentProfile::from_str("FULL").unwrap(), AgentProfile::Full);
     }
 
+    #[test]
+    fn test_hint_tables_reference_real_tools() {
+        let exists = |tool: &str| find_tool_group(tool).is_some();
+        for profile in AgentProfile::ALL {
+            for tool in profile.preferred_tools() {
+                assert!(
+                    exists(tool),
+                    "{} prefers unknown tool {}",
+                    profile.id(),
+                    tool
+                );
+            }
+        }
+        for group in ToolGroup::ALL {
+            for tool in group.tools() {
+                for related in related_tools(tool) {
+                    assert!(exists(related), "{} links unknown tool {}", tool, related);
+                }
+            }
+        }
+        for (keyword, tools) in KEYWORDS {
+            for tool in *tools {
+                assert!(
+                    exists(tool),
+                    "keyword {} maps to unknown tool {}",
+                    keyword,
+                    tool
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_suggest_tools() {
+        let top = suggest_tools("parse the json response from the api", None, 3);
+        assert_eq!(top[0].tool, "Text - JSON (jq)");
+
+        let top = suggest_tools(
+            "wait for the deployment rollout",
+            Some(AgentProfile::DevDeploy),
+            3,
+        );
+        assert_eq!(top[0].tool, "Kubernetes - Wait Ready");
+
+        assert!(suggest_tools("zzz qqq", Some(AgentProfile::Full), 5).is_empty());
+    }
+
     #[test]
     fn test_group_parsing() {
         assert_eq!(ToolGroup::from_str("k8s").unwrap(), ToolGroup::Kubernetes);
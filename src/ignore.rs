@@ -6,11 +6,16 @@
 //! 2. Walk up directory tree looking for .agentignore files
 //!
 //! Tools should NOT respect .gitignore, ONLY .agentignore.
+//!
+//! Paths are checked both as given (with `..` folded) and as they resolve on
+//! disk, so neither a symlink nor a traversal reaches an ignored file, and a
+//! file inside an ignored directory is ignored too. With allowed roots set,
+//! anything resolving outside them is rejected.
 
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use parking_lot::RwLock;
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
 /// Compiled ignore patterns with caching
@@ -20,6 +25,53 @@ pub struct AgentIgnore {
     global: Option<Gitignore>,
     /// Per-directory cache of compiled patterns
     cache: RwLock<HashMap<PathBuf, Arc<Gitignore>>>,
+    /// Canonical directories paths must stay under (empty: anywhere)
+    roots: Vec<PathBuf>,
+}
+
+/// `path` with `.` and `..` folded lexically, without touching the disk
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// `path` as the filesystem resolves it: relative to the call's working
+/// directory, with symlinks and `..` resolved. The longest existing prefix is
+/// canonicalized and any not yet existing rest folded onto it, so a file
+/// about to be created is placed where it will really end up.
+pub fn canonical(path: &Path) -> PathBuf {
+    let path = crate::workdir::resolve(path);
+    let components: Vec<Component> = path.components().collect();
+    for split in (1..=components.len()).rev() {
+        let prefix: PathBuf = components[..split].iter().collect();
+        if let Ok(real) = prefix.canonicalize() {
+            let rest: PathBuf = components[split..].iter().collect();
+            return normalize(&real.join(rest));
+        }
+    }
+    normalize(&path)
+}
+
+/// Whether `gitignore` ignores `path` or any directory above it
+fn matched_or_parents(gitignore: &Gitignore, path: &Path, is_dir: bool) -> bool {
+    if gitignore.matched(path, is_dir).is_ignore() {
+        return true;
+    }
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(gitignore.path()) && *dir != gitignore.path())
+        .any(|dir| gitignore.matched(dir, true).is_ignore())
 }
 
 impl AgentIgnore {
@@ -29,9 +81,22 @@ impl AgentIgnore {
         Ok(Self {
             global,
             cache: RwLock::new(HashMap::new()),
+            roots: Vec::new(),
         })
     }
 
+    /// Restrict paths to these directories (and their contents)
+    pub fn with_roots(mut self, roots: &[PathBuf]) -> Result<Self, String> {
+        self.roots = roots
+            .iter()
+            .map(|root| {
+                root.canonicalize()
+                    .map_err(|e| format!("Allowed root {}: {}", root.display(), e))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
     /// Load ~/.config/agent/ignore if exists
     fn load_global_ignore() -> Result<Option<Gitignore>, String> {
         let config_dir = match dirs::config_dir() {
@@ -53,18 +118,26 @@ impl AgentIgnore {
         }
     }
 
-    /// Check if path should be ignored
+    /// Check if path should be ignored, as given or as it resolves on disk
     pub fn is_ignored(&self, path: &Path) -> bool {
-        let path = match path.canonicalize() {
-            Ok(p) => p,
-            Err(_) => path.to_path_buf(),
-        };
+        let given = normalize(&crate::workdir::resolve(path));
+        let real = canonical(path);
+        self.matches(&real) || (given != real && self.matches(&given))
+    }
 
+    /// Whether an absolute, normalized path or one of its directories is
+    /// matched by the global or an enclosing .agentignore
+    fn matches(&self, path: &Path) -> bool {
         let is_dir = path.is_dir();
 
         // Check global ignore first
         if let Some(ref global) = self.global {
-            if global.matched(&path, is_dir).is_ignore() {
+            if global.matched(path, is_dir).is_ignore()
+                || path
+                    .ancestors()
+                    .skip(1)
+                    .any(|dir| global.matched(dir, true).is_ignore())
+            {
                 return true;
             }
         }
@@ -76,7 +149,7 @@ impl AgentIgnore {
             if ignore_file.exists() {
                 let patterns = self.get_or_load_patterns(dir);
                 if let Some(patterns) = patterns {
-                    if patterns.matched(&path, is_dir).is_ignore() {
+                    if matched_or_parents(&patterns, path, is_dir) {
                         return true;
                     }
                 }
@@ -104,9 +177,10 @@ impl AgentIgnore {
         }
 
         // Walk up from working_dir adding .agentignore files
+        let working_dir = crate::workdir::resolve(working_dir);
         let working_dir = match working_dir.canonicalize() {
             Ok(p) => p,
-            Err(_) => working_dir.to_path_buf(),
+            Err(_) => working_dir,
         };
 
         let mut current = Some(working_dir.as_path());
@@ -130,8 +204,17 @@ impl AgentIgnore {
             .collect()
     }
 
-    /// Validate path is not ignored, return error if it is
+    /// Validate path is not ignored and stays within the allowed roots,
+    /// return error if it does not
     pub fn validate_path(&self, path: &Path) -> Result<(), String> {
+        let real = canonical(path);
+        if !self.roots.is_empty() && !self.roots.iter().any(|root| real.starts_with(root)) {
+            return Err(format!(
+                "Path is outside the allowed roots: {} (resolves to {})",
+                path.display(),
+                real.display()
+            ));
+        }
         if self.is_ignored(path) {
             Err(format!(
                 "Path is blocked by .agentignore: {}",
@@ -184,11 +267,23 @@ impl AgentIgnore {
     }
 }
 
+/// Directory walker over `root` that skips only what .agentignore ignores:
+/// the .gitignore, hidden-file and other standard filters are off
+pub fn walker(root: &Path, ignore: &Arc<AgentIgnore>) -> ignore::WalkBuilder {
+    let ignore = Arc::clone(ignore);
+    let mut builder = ignore::WalkBuilder::new(root);
+    builder
+        .standard_filters(false)
+        .filter_entry(move |e| e.depth() == 0 || !ignore.is_ignored(e.path()));
+    builder
+}
+
 impl Default for AgentIgnore {
     fn default() -> Self {
         Self::new().unwrap_or(Self {
             global: None,
             cache: RwLock::new(HashMap::new()),
+            roots: Vec::new(),
         })
     }
 }
@@ -199,6 +294,7 @@ impl Clone for AgentIgnore {
         Self {
             global: self.global.clone(),
             cache: RwLock::new(HashMap::new()),
+            roots: self.roots.clone(),
         }
     }
 }
@@ -324,4 +420,89 @@ mod tests {
         assert!(ignore.validate_path(&allowed).is_ok());
         assert!(ignore.validate_path(&blocked).is_err());
     }
+
+    #[test]
+    fn test_traversal_and_symlink_escapes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        fs::write(root.join(".agentignore"), "secrets/\n").unwrap();
+        fs::create_dir_all(root.join("secrets")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("secrets/key.pem"), "").unwrap();
+        std::os::unix::fs::symlink(root.join("secrets"), root.join("src/keys")).unwrap();
+
+        let ignore = AgentIgnore::default();
+
+        // Inside an ignored directory, including files not created yet
+        assert!(ignore.is_ignored(&root.join("secrets/key.pem")));
+        assert!(ignore.is_ignored(&root.join("secrets/new/file.txt")));
+        // Through `..` and through a symlink
+        assert!(ignore.is_ignored(&root.join("src/../secrets/key.pem")));
+        assert!(ignore.is_ignored(&root.join("src/keys/key.pem")));
+        assert!(!ignore.is_ignored(&root.join("src/main.rs")));
+
+        assert_eq!(
+            canonical(&root.join("src/keys/new/../key.pem")),
+            root.join("secrets/key.pem")
+        );
+        assert_eq!(
+            normalize(Path::new("/a/b/../c/./d")),
+            PathBuf::from("/a/c/d")
+        );
+    }
+
+    #[test]
+    fn test_walker_applies_only_agentignore() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join(".agentignore"), "*.secret\n").unwrap();
+        fs::write(temp.path().join(".gitignore"), "build/\n").unwrap();
+        fs::create_dir_all(temp.path().join("build")).unwrap();
+        fs::write(temp.path().join("build/out.txt"), "").unwrap();
+        fs::write(temp.path().join(".env.example"), "").unwrap();
+        fs::write(temp.path().join("key.secret"), "").unwrap();
+
+        let ignore = Arc::new(AgentIgnore::default());
+        let mut files: Vec<PathBuf> = walker(temp.path(), &ignore)
+            .build()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+            .map(|e| e.path().strip_prefix(temp.path()).unwrap().to_path_buf())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                ".agentignore",
+                ".env.example",
+                ".gitignore",
+                "build/out.txt"
+            ]
+            .map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn test_allowed_roots() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let outside = TempDir::new().unwrap();
+        fs::create_dir_all(root.join("project")).unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.join("project/escape")).unwrap();
+
+        let ignore = AgentIgnore::default()
+            .with_roots(&[root.join("project")])
+            .unwrap();
+
+        assert!(ignore.validate_path(&root.join("project/new.txt")).is_ok());
+        assert!(ignore
+            .validate_path(&root.join("project/../other.txt"))
+            .unwrap_err()
+            .contains("outside the allowed roots"));
+        assert!(ignore
+            .validate_path(&root.join("project/escape/file"))
+            .is_err());
+        assert!(AgentIgnore::default()
+            .with_roots(&[root.join("missing")])
+            .is_err());
+    }
 }
@@ -0,0 +1,139 @@
+// modern-cli-mcp/src/locale.rs
+//! Localized dual-response summaries.
+//!
+//! The format module writes summaries in English. A locale catalog maps
+//! summary types to templates (see `templates`) in that language:
+//!
+//! ```json
+//! { "file/read": "{{ path }}: {{ lines_returned }} von {{ total_lines }} Zeilen gelesen" }
+//! ```
+//!
+//! Catalogs can be partial: summary types without a template, and templates
+//! that do not fit a response (a missing or null field), keep the English text.
+//!
+//! Catalogs are `<locale>.json` files in ~/.config/modern-cli-mcp/locales,
+//! layered over the built-in ones; `de-AT` falls back to `de`.
+
+use crate::templates;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Summary type to template
+pub type Catalog = HashMap<String, String>;
+
+const BUILTIN: &[(&str, &str)] = &[("de", include_str!("locales/de.json"))];
+
+/// Catalogs by normalized locale
+#[derive(Debug, Default)]
+pub struct Locales {
+    catalogs: HashMap<String, Catalog>,
+}
+
+impl Locales {
+    /// Built-in catalogs, extended and overridden by the `<locale>.json`
+    /// files in `dir`. Unreadable files are logged and skipped.
+    pub fn load(dir: Option<&Path>) -> Self {
+        let mut catalogs: HashMap<String, Catalog> = BUILTIN
+            .iter()
+            .map(|(locale, json)| {
+                let catalog = serde_json::from_str(json).expect("built-in catalog is valid JSON");
+                (locale.to_string(), catalog)
+            })
+            .collect();
+        let files = dir
+            .and_then(|d| std::fs::read_dir(d).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "json"));
+        for path in files {
+            let Some(locale) = path.file_stem().map(|s| normalize(&s.to_string_lossy())) else {
+                continue;
+            };
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| serde_json::from_str::<Catalog>(&text).map_err(|e| e.to_string()));
+            match parsed {
+                Ok(catalog) => catalogs.entry(locale).or_default().extend(catalog),
+                Err(e) => tracing::warn!("Skipping locale catalog {}: {}", path.display(), e),
+            }
+        }
+        Self { catalogs }
+    }
+
+    /// ~/.config/modern-cli-mcp/locales over the built-in catalogs
+    pub fn load_default() -> Self {
+        let dir = dirs::config_dir().map(|d| d.join("modern-cli-mcp").join("locales"));
+        Self::load(dir.as_deref())
+    }
+
+    /// The localized summary for a response, or None to keep the English one
+    pub fn render(&self, locale: &str, uri: &str, summary: &str, data: &str) -> Option<String> {
+        let locale = normalize(locale);
+        let kind = templates::summary_type(uri)?;
+        let language = locale.split('-').next().unwrap_or(&locale);
+        let template = [locale.as_str(), language]
+            .iter()
+            .filter_map(|l| self.catalogs.get(*l))
+            .find_map(|c| c.get(kind))?;
+        templates::render(template, data, summary)
+    }
+}
+
+/// `de_DE.UTF-8` and `de-de` both become `de-de`
+fn normalize(locale: &str) -> String {
+    let locale = locale.split(['.', '@']).next().unwrap_or(locale);
+    locale.replace('_', "-").to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_with_fallbacks() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("de_AT.json"),
+            r#"{"network/tunnels": "Tunnel offen: {{ tunnels|length }}"}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("broken.json"), "{").unwrap();
+        let locales = Locales::load(Some(dir.path()));
+        let data = r#"{"tunnels": [], "path": "/a", "bytes_written": 5}"#;
+
+        assert_eq!(
+            locales.render(
+                "de_AT.UTF-8",
+                "data://network/tunnels.json",
+                "tunnels: 0",
+                data
+            ),
+            Some("Tunnel offen: 0".into())
+        );
+        // de-AT has no file/write template, so the built-in de one applies
+        assert_eq!(
+            locales.render("de-AT", "data://file/write.json", "", data),
+            Some("/a: 5 Bytes geschrieben".into())
+        );
+        // Null fields are not printed as "none"; the English summary is kept
+        assert_eq!(
+            locales.render(
+                "de",
+                "data://network/ssh_config.json",
+                "",
+                r#"{"host": "h", "user": null, "hostname": "h", "port": 22, "known_hosts": {"verdict": "match"}}"#
+            ),
+            None
+        );
+        assert_eq!(
+            locales.render("fr", "data://file/write.json", "", data),
+            None
+        );
+        assert_eq!(
+            locales.render("de", "data://git/diff.txt", "", "not json"),
+            None
+        );
+    }
+}
@@ -1,10 +1,18 @@
 // modern-cli-mcp/src/main.rs
 mod cli;
+mod clients;
 mod format;
 mod groups;
 mod ignore;
+mod locale;
+mod resources;
+mod scheduler;
 mod state;
+mod templates;
 mod tools;
+mod trust;
+mod webhook;
+mod workdir;
 
 use anyhow::Result;
 use clap::Parser;
@@ -49,6 +57,63 @@ struct Args {
     /// and raw structured data (for LLM processing) in a single response.
     #[arg(long, env = "MCP_DUAL_RESPONSE")]
     dual_response: bool,
+
+    /// Listen for GitHub/GitLab webhooks on this port. Received events are
+    /// available through the events_poll tool.
+    #[arg(long, env = "MCP_WEBHOOK_PORT")]
+    webhook_port: Option<u16>,
+
+    /// Address the webhook listener binds to.
+    #[arg(long, env = "MCP_WEBHOOK_BIND", default_value = "127.0.0.1")]
+    webhook_bind: std::net::IpAddr,
+
+    /// Shared secret for webhook deliveries (GitHub signature or GitLab token).
+    #[arg(long, env = "MCP_WEBHOOK_SECRET", hide_env_values = true)]
+    webhook_secret: Option<String>,
+
+    /// Prompt-injection handling for external content (web search, HTTP,
+    /// forge issues/PRs): off, flag (warn in response metadata), or sanitize
+    /// (also strip invisible characters and chat-template tokens).
+    #[arg(long, env = "MCP_INJECTION_SCAN", default_value = "flag")]
+    injection_scan: tools::ScanMode,
+
+    /// JSON file mapping client names to output limits, dual-response and
+    /// pagination settings (default: ~/.config/modern-cli-mcp/clients.json).
+    #[arg(long, env = "MCP_CLIENT_CONFIG")]
+    client_config: Option<std::path::PathBuf>,
+
+    /// Locale for dual-response summaries (e.g. de), using the built-in and
+    /// ~/.config/modern-cli-mcp/locales catalogs. Client config takes precedence.
+    #[arg(long, env = "MCP_LOCALE")]
+    locale: Option<String>,
+
+    /// Directories tool paths must resolve into, after following symlinks
+    /// and `..` (comma-separated; default: no restriction beyond .agentignore).
+    #[arg(
+        long = "allowed-root",
+        env = "MCP_ALLOWED_ROOTS",
+        value_delimiter = ','
+    )]
+    allowed_roots: Vec<std::path::PathBuf>,
+
+    /// Require trusted workspaces for file writes, git commits and shell
+    /// commands (trust with --trust or the trust_workspace tool).
+    #[arg(
+        long,
+        env = "MCP_WORKSPACE_TRUST",
+        default_value_t = true,
+        action = clap::ArgAction::Set
+    )]
+    workspace_trust: bool,
+
+    /// Directories trusted for this run, in addition to those trusted
+    /// through trust_workspace (comma-separated).
+    #[arg(long = "trust", env = "MCP_TRUSTED_WORKSPACES", value_delimiter = ',')]
+    trusted: Vec<std::path::PathBuf>,
+
+    /// Run scheduled tasks (see task_schedule) while the server is up.
+    #[arg(long, env = "MCP_SCHEDULER")]
+    scheduler: bool,
 }
 
 fn print_profiles() {
@@ -190,17 +255,67 @@ async fn main() -> Result<()> {
         tracing::info!("Dual-response mode enabled (formatted + raw data)");
     }
 
+    let mut client_config = match clients::ClientConfig::load(args.client_config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if client_config.default.locale.is_none() {
+        client_config.default.locale = args.locale;
+    }
+
+    if let Some(port) = args.webhook_port {
+        let state = state::StateManager::new().map_err(anyhow::Error::msg)?;
+        let addr = std::net::SocketAddr::new(args.webhook_bind, port);
+        let secret = args.webhook_secret;
+        tokio::spawn(async move {
+            if let Err(e) = webhook::serve(addr, secret, state).await {
+                tracing::error!("Webhook listener failed: {}", e);
+            }
+        });
+    }
+
+    let trust = match trust::Trust::load(args.workspace_trust, &args.trusted) {
+        Ok(trust) => std::sync::Arc::new(trust),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if !args.workspace_trust {
+        tracing::info!("Workspace trust checks disabled");
+    }
+
+    if args.scheduler {
+        let state = state::StateManager::new().map_err(anyhow::Error::msg)?;
+        tokio::spawn(scheduler::run(state, trust.clone()));
+    }
+
     tracing::info!("Starting Modern CLI Tools MCP server");
 
-    let service = ModernCliTools::new_with_config(
+    let tools = ModernCliTools::new_with_config(
         profile,
         args.dynamic_toolsets,
         pre_enabled_toolsets,
         args.dual_response,
-    )
-    .serve(stdio())
-    .await
-    .inspect_err(|e| {
+        args.injection_scan,
+        client_config,
+    );
+    let tools = match tools.with_allowed_roots(&args.allowed_roots) {
+        Ok(tools) => tools,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let tools = tools.with_trust(trust);
+    if !args.allowed_roots.is_empty() {
+        tracing::info!("Paths restricted to {:?}", args.allowed_roots);
+    }
+
+    let service = tools.serve(stdio()).await.inspect_err(|e| {
         tracing::error!("Server error: {:?}", e);
     })?;
 
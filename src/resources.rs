@@ -0,0 +1,307 @@
+// modern-cli-mcp/src/resources.rs
+//! `file://` resources and change subscriptions.
+//!
+//! Clients can read files and directories as MCP resources and subscribe to
+//! them. A subscription starts a filesystem watcher; changes are debounced
+//! and announced with `notifications/resources/updated` for the subscribed
+//! URI, and the next read of a directory resource lists what changed since
+//! the previous read. Paths matched by `.agentignore` are neither readable
+//! nor reported.
+
+use crate::ignore::AgentIgnore;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use rmcp::model::ResourceUpdatedNotificationParam;
+use rmcp::{Peer, RoleServer};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Quiet period before a burst of changes is announced
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Changes kept per subscription between reads
+const MAX_PENDING_CHANGES: usize = 500;
+
+/// Largest file returned by a resource read
+const MAX_READ_BYTES: u64 = 1024 * 1024;
+
+/// One observed change under a subscribed path
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    pub path: String,
+    pub kind: &'static str,
+}
+
+struct Subscription {
+    _watcher: RecommendedWatcher,
+    changes: Arc<Mutex<Vec<Change>>>,
+}
+
+/// Active resource subscriptions, keyed by URI
+#[derive(Default)]
+pub struct Subscriptions {
+    active: Mutex<HashMap<String, Subscription>>,
+}
+
+impl std::fmt::Debug for Subscriptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscriptions")
+            .field("uris", &self.active.lock().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Subscriptions {
+    /// Watch the path behind `uri` and notify `peer` when it changes.
+    /// Subscribing to an already watched URI is a no-op.
+    pub fn subscribe(
+        &self,
+        uri: &str,
+        peer: Peer<RoleServer>,
+        ignore: Arc<AgentIgnore>,
+    ) -> Result<(), String> {
+        let path = uri_to_path(uri)?;
+        ignore.validate_path(&path)?;
+        if !path.exists() {
+            return Err(format!("Path not found: {}", path.display()));
+        }
+        if self.active.lock().contains_key(uri) {
+            return Ok(());
+        }
+
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+        let pending = changes.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            let Some(kind) = change_kind(&event.kind) else {
+                return;
+            };
+            let mut recorded = false;
+            for path in event.paths.iter().filter(|p| !ignore.is_ignored(p)) {
+                let change = Change {
+                    path: path.display().to_string(),
+                    kind,
+                };
+                let mut pending = pending.lock();
+                if !pending.contains(&change) && pending.len() < MAX_PENDING_CHANGES {
+                    pending.push(change);
+                }
+                recorded = true;
+            }
+            if recorded {
+                let _ = tx.send(());
+            }
+        })
+        .map_err(|e| format!("Failed to start watcher: {}", e))?;
+        watcher
+            .watch(&path, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+
+        // Ends when the watcher (and with it the sender) is dropped
+        let notify_uri = uri.to_string();
+        tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                tokio::time::sleep(DEBOUNCE).await;
+                while rx.try_recv().is_ok() {}
+                let param = ResourceUpdatedNotificationParam {
+                    uri: notify_uri.clone(),
+                };
+                if let Err(e) = peer.notify_resource_updated(param).await {
+                    tracing::debug!("Dropping subscription {}: {}", notify_uri, e);
+                    break;
+                }
+            }
+        });
+
+        tracing::info!("Watching {} for resource subscription", path.display());
+        self.active.lock().insert(
+            uri.to_string(),
+            Subscription {
+                _watcher: watcher,
+                changes,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop watching `uri`; returns whether it was subscribed
+    pub fn unsubscribe(&self, uri: &str) -> bool {
+        self.active.lock().remove(uri).is_some()
+    }
+
+    /// Subscribed URIs
+    pub fn uris(&self) -> Vec<String> {
+        let mut uris: Vec<String> = self.active.lock().keys().cloned().collect();
+        uris.sort();
+        uris
+    }
+
+    /// Take the changes recorded for `uri` since the last call
+    fn drain_changes(&self, uri: &str) -> Option<Vec<Change>> {
+        self.active
+            .lock()
+            .get(uri)
+            .map(|s| std::mem::take(&mut *s.changes.lock()))
+    }
+
+    /// Read a file (text) or directory (JSON listing) resource
+    pub fn read(&self, uri: &str, ignore: &AgentIgnore) -> Result<(String, &'static str), String> {
+        let path = uri_to_path(uri)?;
+        ignore.validate_path(&path)?;
+        let meta = std::fs::metadata(&path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+
+        if meta.is_file() {
+            if meta.len() > MAX_READ_BYTES {
+                return Err(format!(
+                    "{} is {} bytes; resources are limited to {} bytes",
+                    path.display(),
+                    meta.len(),
+                    MAX_READ_BYTES
+                ));
+            }
+            let text = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Cannot read {} as text: {}", path.display(), e))?;
+            return Ok((text, "text/plain"));
+        }
+
+        let mut entries: Vec<serde_json::Value> = std::fs::read_dir(&path)
+            .map_err(|e| format!("Cannot list {}: {}", path.display(), e))?
+            .filter_map(Result::ok)
+            .filter(|e| !ignore.is_ignored(&e.path()))
+            .map(|e| {
+                let meta = e.metadata().ok();
+                json!({
+                    "name": e.file_name().to_string_lossy(),
+                    "type": match &meta {
+                        Some(m) if m.is_dir() => "directory",
+                        Some(m) if m.is_symlink() => "symlink",
+                        _ => "file",
+                    },
+                    "size": meta.as_ref().filter(|m| m.is_file()).map(|m| m.len()),
+                    "modified": meta
+                        .and_then(|m| m.modified().ok())
+                        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+        let mut listing = json!({
+            "path": path.display().to_string(),
+            "entries": entries,
+        });
+        if let Some(changes) = self.drain_changes(uri) {
+            listing["changes"] = json!(changes);
+        }
+        Ok((listing.to_string(), "application/json"))
+    }
+}
+
+/// Map a notify event onto created/modified/removed/renamed; access and
+/// metadata-only events are not changes
+fn change_kind(kind: &EventKind) -> Option<&'static str> {
+    use notify::event::ModifyKind;
+    match kind {
+        EventKind::Create(_) => Some("created"),
+        EventKind::Remove(_) => Some("removed"),
+        EventKind::Modify(ModifyKind::Name(_)) => Some("renamed"),
+        EventKind::Modify(ModifyKind::Metadata(_)) => None,
+        EventKind::Modify(_) => Some("modified"),
+        EventKind::Access(_) | EventKind::Any | EventKind::Other => None,
+    }
+}
+
+/// `file:///abs/path` (percent-encoded) to a path
+pub fn uri_to_path(uri: &str) -> Result<PathBuf, String> {
+    let rest = uri
+        .strip_prefix("file://")
+        .ok_or_else(|| format!("Unsupported resource URI: {} (expected file://)", uri))?;
+    // Drop an authority ("localhost") if present
+    let path = match rest.find('/') {
+        Some(0) => rest,
+        Some(i) if &rest[..i] == "localhost" => &rest[i..],
+        _ => return Err(format!("Resource URI must be an absolute path: {}", uri)),
+    };
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = path
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format!("Invalid escape in resource URI: {}", uri))?;
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    let path = String::from_utf8(decoded).map_err(|_| format!("Invalid UTF-8 in {}", uri))?;
+    Ok(PathBuf::from(path))
+}
+
+/// Path to a `file://` URI, escaping what a URI can't carry literally
+pub fn path_to_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_uris() {
+        let path = Path::new("/tmp/my dir/naïve.rs");
+        let uri = path_to_uri(path);
+        assert_eq!(uri, "file:///tmp/my%20dir/na%C3%AFve.rs");
+        assert_eq!(uri_to_path(&uri).unwrap(), path);
+        assert_eq!(
+            uri_to_path("file://localhost/etc").unwrap(),
+            Path::new("/etc")
+        );
+        assert!(uri_to_path("data://mcp/x.json").is_err());
+        assert!(uri_to_path("file://host/etc").is_err());
+    }
+
+    #[test]
+    fn test_read_directory_resource() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "hello").unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+
+        let subs = Subscriptions::default();
+        let ignore = AgentIgnore::default();
+        let uri = path_to_uri(dir.path());
+        let (listing, mime) = subs.read(&uri, &ignore).unwrap();
+        assert_eq!(mime, "application/json");
+        let listing: serde_json::Value = serde_json::from_str(&listing).unwrap();
+        assert_eq!(listing["entries"][0]["name"], "a.txt");
+        assert_eq!(listing["entries"][0]["size"], 5);
+        assert_eq!(listing["entries"][1]["type"], "directory");
+
+        let (text, _) = subs
+            .read(&path_to_uri(&dir.path().join("a.txt")), &ignore)
+            .unwrap();
+        assert_eq!(text, "hello");
+    }
+}
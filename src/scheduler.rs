@@ -0,0 +1,220 @@
+// modern-cli-mcp/src/scheduler.rs
+//! Scheduled task runner.
+//!
+//! Tasks in the state database can carry a cron schedule and a command.
+//! When the server runs with `--scheduler`, due commands are executed here
+//! and each run's outcome is appended to the task's history. Runs are
+//! claimed through the database, so several servers sharing one state file
+//! never execute the same occurrence twice. Each run re-checks that its
+//! working directory is still trusted, so revoking trust stops it.
+
+use crate::state::{StateManager, TaskRun, TaskSchedule};
+use crate::tools::{CommandExecutor, ExecOptions};
+use crate::trust::Trust;
+use chrono::{Local, TimeZone};
+use croner::Cron;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often due schedules are checked
+const TICK: Duration = Duration::from_secs(15);
+
+/// Default timeout for a scheduled command
+const DEFAULT_TIMEOUT_SECS: u64 = 600;
+
+/// Output kept per run (tail of stdout + stderr)
+const OUTPUT_TAIL_BYTES: usize = 4000;
+
+/// Parse a cron expression (5 fields, or an alias such as `@daily`)
+pub fn parse_cron(expr: &str) -> Result<Cron, String> {
+    Cron::new(expr)
+        .parse()
+        .map_err(|e| format!("Invalid cron expression '{}': {}", expr, e))
+}
+
+/// Upcoming occurrences strictly after `after` (unix seconds, local time)
+pub fn upcoming(expr: &str, after: i64, count: usize) -> Result<Vec<i64>, String> {
+    let cron = parse_cron(expr)?;
+    let mut at = Local
+        .timestamp_opt(after, 0)
+        .single()
+        .ok_or_else(|| format!("Invalid timestamp {}", after))?;
+    let mut times = Vec::with_capacity(count);
+    for _ in 0..count {
+        at = cron
+            .find_next_occurrence(&at, false)
+            .map_err(|e| format!("No upcoming run for '{}': {}", expr, e))?;
+        times.push(at.timestamp());
+    }
+    Ok(times)
+}
+
+/// Render unix seconds as local RFC 3339 time
+pub fn format_local_time(ts: i64) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|| ts.to_string())
+}
+
+/// Check for due schedules forever
+pub async fn run(state: StateManager, trust: Arc<Trust>) {
+    tracing::info!("Task scheduler started");
+    let executor = CommandExecutor::new();
+    loop {
+        if let Err(e) = tick(&state, &executor, &trust).await {
+            tracing::warn!("Scheduler tick failed: {}", e);
+        }
+        tokio::time::sleep(TICK).await;
+    }
+}
+
+async fn tick(
+    state: &StateManager,
+    executor: &CommandExecutor,
+    trust: &Arc<Trust>,
+) -> Result<(), String> {
+    let now = chrono::Utc::now().timestamp();
+    for schedule in state.schedule_list(Some(now))? {
+        // Missed occurrences (server was down) collapse into this one run
+        let next = match upcoming(&schedule.cron, now, 1) {
+            Ok(next) => next[0],
+            Err(e) => {
+                tracing::warn!("Task {}: {}", schedule.task_id, e);
+                continue;
+            }
+        };
+        if !state.schedule_claim(schedule.task_id, schedule.next_run, next)? {
+            continue;
+        }
+        let state = state.clone();
+        let executor = executor.clone();
+        let trust = trust.clone();
+        tokio::spawn(async move {
+            let run = execute(&executor, &schedule, &trust).await;
+            tracing::info!(
+                "Scheduled task {} finished (success: {})",
+                run.task_id,
+                run.success
+            );
+            if let Err(e) = state.task_run_record(&run) {
+                tracing::warn!("Failed to record run of task {}: {}", run.task_id, e);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Run a schedule's command once
+pub async fn execute(
+    executor: &CommandExecutor,
+    schedule: &TaskSchedule,
+    trust: &Trust,
+) -> TaskRun {
+    let started_at = chrono::Utc::now().timestamp();
+    let started = std::time::Instant::now();
+
+    let cwd = Path::new(schedule.cwd.as_deref().unwrap_or("."));
+    let result = match shellwords::split(&schedule.command) {
+        _ if !trust.is_trusted(cwd) => Err(format!(
+            "Not run: {} is not a trusted workspace",
+            crate::ignore::canonical(cwd).display()
+        )),
+        Ok(argv) if !argv.is_empty() => {
+            let args: Vec<&str> = argv[1..].iter().map(|s| s.as_str()).collect();
+            let options = ExecOptions {
+                working_dir: schedule.cwd.as_deref(),
+                timeout_secs: Some(schedule.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)),
+                ..Default::default()
+            };
+            executor.run_with_options(&argv[0], &args, options).await
+        }
+        Ok(_) => Err("Empty command".to_string()),
+        Err(e) => Err(format!("Invalid command line: {}", e)),
+    };
+
+    let (success, exit_code, output) = match result {
+        Ok(out) => {
+            let combined = if out.stderr.is_empty() {
+                out.stdout
+            } else {
+                format!("{}{}", out.stdout, out.stderr)
+            };
+            (
+                out.success,
+                out.exit_code,
+                tail(&combined, OUTPUT_TAIL_BYTES),
+            )
+        }
+        Err(e) => (false, None, e),
+    };
+
+    TaskRun {
+        id: 0,
+        task_id: schedule.task_id,
+        started_at,
+        duration_ms: started.elapsed().as_millis() as i64,
+        success,
+        exit_code,
+        output,
+    }
+}
+
+/// Keep the last `max` bytes, where errors usually are
+fn tail(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut start = text.len() - max;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("...{}", &text[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upcoming() {
+        let now = chrono::Utc::now().timestamp();
+        let runs = upcoming("*/15 * * * *", now, 3).unwrap();
+        assert_eq!(runs.len(), 3);
+        assert!(runs[0] > now && runs[0] - now <= 15 * 60);
+        assert_eq!(runs[1] - runs[0], 15 * 60);
+        assert!(upcoming("@daily", now, 1).is_ok());
+        assert!(parse_cron("61 * * * *").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_records_output() {
+        let schedule = TaskSchedule {
+            task_id: 1,
+            cron: "@hourly".to_string(),
+            command: "sh -c 'echo hello; exit 3'".to_string(),
+            cwd: None,
+            timeout_secs: Some(10),
+            enabled: true,
+            next_run: 0,
+            last_run: None,
+        };
+        let run = execute(&CommandExecutor::new(), &schedule, &Trust::default()).await;
+        assert!(!run.success);
+        assert_eq!(run.exit_code, Some(3));
+        assert_eq!(run.output.trim(), "hello");
+
+        // Untrusted (or revoked) working directories are not run
+        let dir = tempfile::tempdir().unwrap();
+        let schedule = TaskSchedule {
+            cwd: Some(dir.path().to_string_lossy().to_string()),
+            ..schedule
+        };
+        let strict = Trust::with_file(true, &[], None).unwrap();
+        let run = execute(&CommandExecutor::new(), &schedule, &strict).await;
+        assert_eq!(run.exit_code, None);
+        assert!(run.output.starts_with("Not run:"));
+    }
+}
@@ -1,6 +1,8 @@
 // modern-cli-mcp/src/state.rs
 //! Internal state management using SQLite for operational data.
-//! Handles auth state, caching, tasks, and context storage.
+//! Handles auth state, caching, tasks (including scheduled runs and their
+//! history), context storage, received forge webhook events, and the files
+//! each session read or changed.
 
 #![allow(dead_code)] // Many methods reserved for future MCP tool expansion
 
@@ -113,6 +115,104 @@ pub struct ContextEntry {
     pub scope: ContextScope,
 }
 
+/// Named benchmark run (normalized hyperfine results)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRun {
+    pub name: String,
+    pub results: serde_json::Value,
+    pub created_at: i64,
+}
+
+/// Recurring command attached to a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSchedule {
+    pub task_id: i64,
+    /// Cron expression (5 fields, or an alias such as `@hourly`)
+    pub cron: String,
+    /// Command line, split with shell quoting rules
+    pub command: String,
+    pub cwd: Option<String>,
+    pub timeout_secs: Option<u64>,
+    pub enabled: bool,
+    pub next_run: i64,
+    pub last_run: Option<i64>,
+}
+
+/// One execution of a scheduled task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRun {
+    pub id: i64,
+    pub task_id: i64,
+    pub started_at: i64,
+    pub duration_ms: i64,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    /// Tail of combined stdout/stderr
+    pub output: String,
+}
+
+/// Runs kept per task; older history is pruned on insert
+const TASK_RUN_LIMIT: i64 = 50;
+
+/// Webhook event received from a git forge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeEvent {
+    pub id: i64,
+    pub source: String,
+    pub event: String,
+    pub action: Option<String>,
+    pub repo: Option<String>,
+    pub summary: serde_json::Value,
+    pub payload: Option<serde_json::Value>,
+    pub received_at: i64,
+}
+
+/// Number of forge events retained; older ones are pruned on insert
+const FORGE_EVENT_LIMIT: i64 = 1000;
+
+/// How a file tool touched a file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAction {
+    Read,
+    Write,
+    Edit,
+}
+
+impl std::fmt::Display for FileAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileAction::Read => write!(f, "read"),
+            FileAction::Write => write!(f, "write"),
+            FileAction::Edit => write!(f, "edit"),
+        }
+    }
+}
+
+/// A file touched during a session, with counts per action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFile {
+    pub session: String,
+    pub path: String,
+    pub reads: i64,
+    pub writes: i64,
+    pub edits: i64,
+    pub last_action: String,
+    pub first_at: i64,
+    pub last_at: i64,
+}
+
+/// A session that touched files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSession {
+    pub session: String,
+    pub files: i64,
+    pub last_at: i64,
+}
+
+/// Sessions whose touched files are retained; older ones are pruned on start
+pub const FILE_SESSION_LIMIT: i64 = 20;
+
 impl StateManager {
     /// Create a new state manager, initializing the database
     pub fn new() -> Result<Self, String> {
@@ -126,6 +226,9 @@ impl StateManager {
 
         let conn = Connection::open(&db_path)
             .map_err(|e| format!("Failed to open state database: {}", e))?;
+        // The webhook listener writes through its own connection
+        conn.busy_timeout(std::time::Duration::from_secs(5))
+            .map_err(|e| format!("Failed to configure state database: {}", e))?;
 
         let manager = Self {
             conn: Arc::new(Mutex::new(conn)),
@@ -187,6 +290,29 @@ impl StateManager {
                 updated_at INTEGER NOT NULL
             );
 
+            -- Recurring commands attached to tasks
+            CREATE TABLE IF NOT EXISTS task_schedules (
+                task_id INTEGER PRIMARY KEY,
+                cron TEXT NOT NULL,
+                command TEXT NOT NULL,
+                cwd TEXT,
+                timeout_secs INTEGER,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                next_run INTEGER NOT NULL,
+                last_run INTEGER
+            );
+
+            -- Scheduled task run history
+            CREATE TABLE IF NOT EXISTS task_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id INTEGER NOT NULL,
+                started_at INTEGER NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                success INTEGER NOT NULL,
+                exit_code INTEGER,
+                output TEXT NOT NULL
+            );
+
             -- Key-value context storage
             CREATE TABLE IF NOT EXISTS context (
                 key TEXT NOT NULL,
@@ -195,6 +321,40 @@ impl StateManager {
                 PRIMARY KEY (key, scope)
             );
 
+            -- Named benchmark runs for cross-session comparison
+            CREATE TABLE IF NOT EXISTS benchmarks (
+                name TEXT PRIMARY KEY,
+                results TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+
+            -- Webhook events received from git forges
+            CREATE TABLE IF NOT EXISTS forge_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                source TEXT NOT NULL,
+                event TEXT NOT NULL,
+                action TEXT,
+                repo TEXT,
+                summary TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                received_at INTEGER NOT NULL
+            );
+
+            -- Files read or changed per session
+            CREATE TABLE IF NOT EXISTS recent_files (
+                session TEXT NOT NULL,
+                path TEXT NOT NULL,
+                reads INTEGER NOT NULL DEFAULT 0,
+                writes INTEGER NOT NULL DEFAULT 0,
+                edits INTEGER NOT NULL DEFAULT 0,
+                last_action TEXT NOT NULL,
+                first_at INTEGER NOT NULL,
+                last_at INTEGER NOT NULL,
+                -- Orders touches within the same second
+                seq INTEGER NOT NULL,
+                PRIMARY KEY (session, path)
+            );
+
             -- Index for cache cleanup
             CREATE INDEX IF NOT EXISTS idx_cache_expiry
                 ON tool_cache(created_at, ttl_secs);
@@ -202,6 +362,14 @@ impl StateManager {
             -- Index for task status queries
             CREATE INDEX IF NOT EXISTS idx_task_status
                 ON tasks(status);
+
+            -- Index for run history per task
+            CREATE INDEX IF NOT EXISTS idx_task_runs_task
+                ON task_runs(task_id, id);
+
+            -- Index for the latest sessions and files
+            CREATE INDEX IF NOT EXISTS idx_recent_files_time
+                ON recent_files(seq);
             "#,
         )
         .map_err(|e| format!("Failed to initialize schema: {}", e))?;
@@ -459,6 +627,11 @@ impl StateManager {
             return Err(format!("Task {} not found", id));
         }
 
+        conn.execute("DELETE FROM task_schedules WHERE task_id = ?", params![id])
+            .map_err(|e| e.to_string())?;
+        conn.execute("DELETE FROM task_runs WHERE task_id = ?", params![id])
+            .map_err(|e| e.to_string())?;
+
         Ok(())
     }
 
@@ -469,10 +642,154 @@ impl StateManager {
         let deleted = conn
             .execute("DELETE FROM tasks", [])
             .map_err(|e| e.to_string())?;
+        conn.execute_batch("DELETE FROM task_schedules; DELETE FROM task_runs;")
+            .map_err(|e| e.to_string())?;
 
         Ok(deleted as u64)
     }
 
+    /// Attach or replace the schedule of an existing task
+    pub fn schedule_set(&self, schedule: &TaskSchedule) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let exists: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM tasks WHERE id = ?",
+                params![schedule.task_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        if exists.is_none() {
+            return Err(format!("Task {} not found", schedule.task_id));
+        }
+
+        conn.execute(
+            "INSERT OR REPLACE INTO task_schedules (task_id, cron, command, cwd, timeout_secs, enabled, next_run, last_run)
+             VALUES (?, ?, ?, ?, ?, ?, ?, (SELECT last_run FROM task_schedules WHERE task_id = ?))",
+            params![
+                schedule.task_id,
+                schedule.cron,
+                schedule.command,
+                schedule.cwd,
+                schedule.timeout_secs.map(|t| t as i64),
+                schedule.enabled as i32,
+                schedule.next_run,
+                schedule.task_id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(())
+    }
+
+    /// List schedules, optionally only enabled ones due at or before `due_by`
+    pub fn schedule_list(&self, due_by: Option<i64>) -> Result<Vec<TaskSchedule>, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT task_id, cron, command, cwd, timeout_secs, enabled, next_run, last_run FROM task_schedules
+                 WHERE ?1 IS NULL OR (enabled = 1 AND next_run <= ?1) ORDER BY next_run",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map(params![due_by], |row| {
+                let enabled: i32 = row.get(5)?;
+                let timeout: Option<i64> = row.get(4)?;
+                Ok(TaskSchedule {
+                    task_id: row.get(0)?,
+                    cron: row.get(1)?,
+                    command: row.get(2)?,
+                    cwd: row.get(3)?,
+                    timeout_secs: timeout.map(|t| t as u64),
+                    enabled: enabled != 0,
+                    next_run: row.get(6)?,
+                    last_run: row.get(7)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| e.to_string())
+    }
+
+    /// Claim a due run by advancing `next_run`.
+    ///
+    /// Returns false when another process sharing the database already
+    /// claimed this occurrence.
+    pub fn schedule_claim(&self, task_id: i64, due: i64, next_run: i64) -> Result<bool, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let affected = conn
+            .execute(
+                "UPDATE task_schedules SET next_run = ?, last_run = ? WHERE task_id = ? AND next_run = ? AND enabled = 1",
+                params![next_run, Self::now(), task_id, due],
+            )
+            .map_err(|e| e.to_string())?;
+
+        Ok(affected == 1)
+    }
+
+    /// Record a scheduled run, returning its ID
+    pub fn task_run_record(&self, run: &TaskRun) -> Result<i64, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        conn.execute(
+            "INSERT INTO task_runs (task_id, started_at, duration_ms, success, exit_code, output) VALUES (?, ?, ?, ?, ?, ?)",
+            params![
+                run.task_id,
+                run.started_at,
+                run.duration_ms,
+                run.success as i32,
+                run.exit_code,
+                run.output
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        let id = conn.last_insert_rowid();
+
+        conn.execute(
+            "DELETE FROM task_runs WHERE task_id = ?1 AND id NOT IN
+             (SELECT id FROM task_runs WHERE task_id = ?1 ORDER BY id DESC LIMIT ?2)",
+            params![run.task_id, TASK_RUN_LIMIT],
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(id)
+    }
+
+    /// Run history, newest first, optionally for one task
+    pub fn task_runs(&self, task_id: Option<i64>, limit: usize) -> Result<Vec<TaskRun>, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, task_id, started_at, duration_ms, success, exit_code, output FROM task_runs
+                 WHERE ?1 IS NULL OR task_id = ?1 ORDER BY id DESC LIMIT ?2",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map(params![task_id, limit as i64], |row| {
+                let success: i32 = row.get(4)?;
+                Ok(TaskRun {
+                    id: row.get(0)?,
+                    task_id: row.get(1)?,
+                    started_at: row.get(2)?,
+                    duration_ms: row.get(3)?,
+                    success: success != 0,
+                    exit_code: row.get(5)?,
+                    output: row.get(6)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| e.to_string())
+    }
+
     // ========================================================================
     // CONTEXT
     // ========================================================================
@@ -560,6 +877,260 @@ impl StateManager {
 
         Ok(deleted as u64)
     }
+
+    // ========================================================================
+    // BENCHMARKS
+    // ========================================================================
+
+    /// Save (or replace) a named benchmark run
+    pub fn benchmark_save(&self, name: &str, results: &serde_json::Value) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO benchmarks (name, results, created_at) VALUES (?, ?, ?)",
+            params![name, results.to_string(), Self::now()],
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(())
+    }
+
+    /// Get a named benchmark run
+    pub fn benchmark_get(&self, name: &str) -> Result<Option<BenchmarkRun>, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let result: Option<(String, i64)> = conn
+            .query_row(
+                "SELECT results, created_at FROM benchmarks WHERE name = ?",
+                params![name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+
+        Ok(result.map(|(results, created_at)| BenchmarkRun {
+            name: name.to_string(),
+            results: serde_json::from_str(&results).unwrap_or_default(),
+            created_at,
+        }))
+    }
+
+    /// List saved benchmark runs, newest first
+    pub fn benchmark_list(&self) -> Result<Vec<BenchmarkRun>, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let mut stmt = conn
+            .prepare("SELECT name, results, created_at FROM benchmarks ORDER BY created_at DESC")
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                let results: String = row.get(1)?;
+                Ok(BenchmarkRun {
+                    name: row.get(0)?,
+                    results: serde_json::from_str(&results).unwrap_or_default(),
+                    created_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| e.to_string())
+    }
+
+    // ========================================================================
+    // FORGE EVENTS
+    // ========================================================================
+
+    /// Record a received webhook event, returning its ID
+    pub fn forge_event_add(
+        &self,
+        source: &str,
+        event: &str,
+        action: Option<&str>,
+        repo: Option<&str>,
+        summary: &serde_json::Value,
+        payload: &serde_json::Value,
+    ) -> Result<i64, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        conn.execute(
+            "INSERT INTO forge_events (source, event, action, repo, summary, payload, received_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params![
+                source,
+                event,
+                action,
+                repo,
+                summary.to_string(),
+                payload.to_string(),
+                Self::now()
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        let id = conn.last_insert_rowid();
+
+        conn.execute(
+            "DELETE FROM forge_events WHERE id <= ?",
+            params![id - FORGE_EVENT_LIMIT],
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(id)
+    }
+
+    /// List forge events after a cursor ID, oldest first
+    pub fn forge_events_since(
+        &self,
+        after_id: i64,
+        source: Option<&str>,
+        event: Option<&str>,
+        limit: usize,
+        with_payload: bool,
+    ) -> Result<Vec<ForgeEvent>, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, source, event, action, repo, summary, payload, received_at FROM forge_events
+                 WHERE id > ?1 AND (?2 IS NULL OR source = ?2) AND (?3 IS NULL OR event = ?3)
+                 ORDER BY id LIMIT ?4",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map(params![after_id, source, event, limit as i64], |row| {
+                let summary: String = row.get(5)?;
+                let payload: String = row.get(6)?;
+                Ok(ForgeEvent {
+                    id: row.get(0)?,
+                    source: row.get(1)?,
+                    event: row.get(2)?,
+                    action: row.get(3)?,
+                    repo: row.get(4)?,
+                    summary: serde_json::from_str(&summary).unwrap_or_default(),
+                    payload: with_payload
+                        .then(|| serde_json::from_str(&payload).unwrap_or_default()),
+                    received_at: row.get(7)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| e.to_string())
+    }
+
+    // ========================================================================
+    // RECENT FILES
+    // ========================================================================
+
+    /// Count a read, write or edit of `path` in `session`
+    pub fn file_touch(&self, session: &str, path: &str, action: FileAction) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+        let count = |a: FileAction| i64::from(action == a);
+
+        conn.execute(
+            "INSERT INTO recent_files
+                (session, path, reads, writes, edits, last_action, first_at, last_at, seq)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7,
+                (SELECT COALESCE(MAX(seq), 0) + 1 FROM recent_files))
+             ON CONFLICT (session, path) DO UPDATE SET
+                reads = reads + excluded.reads,
+                writes = writes + excluded.writes,
+                edits = edits + excluded.edits,
+                last_action = excluded.last_action,
+                last_at = excluded.last_at,
+                seq = excluded.seq",
+            params![
+                session,
+                path,
+                count(FileAction::Read),
+                count(FileAction::Write),
+                count(FileAction::Edit),
+                action.to_string(),
+                Self::now()
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(())
+    }
+
+    /// Files touched in a session (every session when None), most recent first
+    pub fn recent_files(
+        &self,
+        session: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<RecentFile>, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT session, path, reads, writes, edits, last_action, first_at, last_at
+                 FROM recent_files WHERE ?1 IS NULL OR session = ?1
+                 ORDER BY seq DESC LIMIT ?2",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map(params![session, limit as i64], |row| {
+                Ok(RecentFile {
+                    session: row.get(0)?,
+                    path: row.get(1)?,
+                    reads: row.get(2)?,
+                    writes: row.get(3)?,
+                    edits: row.get(4)?,
+                    last_action: row.get(5)?,
+                    first_at: row.get(6)?,
+                    last_at: row.get(7)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| e.to_string())
+    }
+
+    /// Sessions that touched files, most recent first
+    pub fn file_sessions(&self, limit: usize) -> Result<Vec<FileSession>, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT session, COUNT(*), MAX(last_at) FROM recent_files
+                 GROUP BY session ORDER BY MAX(seq) DESC LIMIT ?",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt
+            .query_map(params![limit as i64], |row| {
+                Ok(FileSession {
+                    session: row.get(0)?,
+                    files: row.get(1)?,
+                    last_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| e.to_string())
+    }
+
+    /// Drop the files of all but the `keep` most recent sessions
+    pub fn file_sessions_prune(&self, keep: i64) -> Result<u64, String> {
+        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+
+        let deleted = conn
+            .execute(
+                "DELETE FROM recent_files WHERE session NOT IN (
+                    SELECT session FROM recent_files
+                    GROUP BY session ORDER BY MAX(seq) DESC LIMIT ?
+                )",
+                params![keep],
+            )
+            .map_err(|e| e.to_string())?;
+
+        Ok(deleted as u64)
+    }
 }
 
 impl Default for StateManager {
@@ -588,6 +1159,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_auth_state() {
         let mgr = StateManager::new_in_memory().unwrap();
 
@@ -635,6 +1207,54 @@ mod tests {
         assert_eq!(tasks[0].status, TaskStatus::InProgress);
     }
 
+    #[test]
+    fn test_task_schedules() {
+        let mgr = StateManager::new_in_memory().unwrap();
+
+        let task = mgr.task_create("Prune caches").unwrap();
+        let schedule = TaskSchedule {
+            task_id: task.id,
+            cron: "0 * * * *".to_string(),
+            command: "cargo clean".to_string(),
+            cwd: None,
+            timeout_secs: Some(60),
+            enabled: true,
+            next_run: 100,
+            last_run: None,
+        };
+        mgr.schedule_set(&schedule).unwrap();
+        assert!(mgr
+            .schedule_set(&TaskSchedule {
+                task_id: 999,
+                ..schedule.clone()
+            })
+            .is_err());
+
+        assert!(mgr.schedule_list(Some(99)).unwrap().is_empty());
+        assert_eq!(mgr.schedule_list(Some(100)).unwrap().len(), 1);
+
+        // Only one claimant wins a given occurrence
+        assert!(mgr.schedule_claim(task.id, 100, 200).unwrap());
+        assert!(!mgr.schedule_claim(task.id, 100, 200).unwrap());
+        assert_eq!(mgr.schedule_list(None).unwrap()[0].next_run, 200);
+
+        let run = TaskRun {
+            id: 0,
+            task_id: task.id,
+            started_at: 100,
+            duration_ms: 5,
+            success: true,
+            exit_code: Some(0),
+            output: "done".to_string(),
+        };
+        mgr.task_run_record(&run).unwrap();
+        assert_eq!(mgr.task_runs(Some(task.id), 10).unwrap()[0].output, "done");
+
+        mgr.task_delete(task.id).unwrap();
+        assert!(mgr.schedule_list(None).unwrap().is_empty());
+        assert!(mgr.task_runs(None, 10).unwrap().is_empty());
+    }
+
     #[test]
     fn test_context() {
         let mgr = StateManager::new_in_memory().unwrap();
@@ -654,4 +1274,78 @@ mod tests {
         let value = mgr.context_get("key1", ContextScope::Session).unwrap();
         assert!(value.is_none());
     }
+    #[test]
+    fn test_benchmarks() {
+        let mgr = StateManager::new_in_memory().unwrap();
+
+        let results = serde_json::json!({"results": [{"command": "ls", "mean": 0.01}]});
+        mgr.benchmark_save("baseline", &results).unwrap();
+
+        let run = mgr.benchmark_get("baseline").unwrap().unwrap();
+        assert_eq!(run.results["results"][0]["command"], "ls");
+        assert!(mgr.benchmark_get("missing").unwrap().is_none());
+        assert_eq!(mgr.benchmark_list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_forge_events() {
+        let mgr = StateManager::new_in_memory().unwrap();
+
+        let summary = serde_json::json!({"ref": "refs/heads/main"});
+        let payload = serde_json::json!({"after": "abc123"});
+        let first = mgr
+            .forge_event_add("github", "push", None, Some("o/r"), &summary, &payload)
+            .unwrap();
+        mgr.forge_event_add(
+            "gitlab",
+            "merge_request",
+            Some("open"),
+            Some("g/p"),
+            &summary,
+            &payload,
+        )
+        .unwrap();
+
+        let all = mgr.forge_events_since(0, None, None, 10, false).unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all[0].payload.is_none());
+
+        let after = mgr.forge_events_since(first, None, None, 10, true).unwrap();
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].event, "merge_request");
+        assert_eq!(after[0].payload.as_ref().unwrap()["after"], "abc123");
+
+        let pushes = mgr
+            .forge_events_since(0, Some("github"), Some("push"), 10, false)
+            .unwrap();
+        assert_eq!(pushes.len(), 1);
+    }
+
+    #[test]
+    fn test_recent_files() {
+        let mgr = StateManager::new_in_memory().unwrap();
+
+        mgr.file_touch("s1", "/src/old.rs", FileAction::Edit)
+            .unwrap();
+        mgr.file_touch("s2", "/src/lib.rs", FileAction::Read)
+            .unwrap();
+        mgr.file_touch("s2", "/src/main.rs", FileAction::Read)
+            .unwrap();
+        mgr.file_touch("s2", "/src/lib.rs", FileAction::Edit)
+            .unwrap();
+
+        let files = mgr.recent_files(Some("s2"), 10).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "/src/lib.rs");
+        assert_eq!((files[0].reads, files[0].edits), (1, 1));
+        assert_eq!(files[0].last_action, "edit");
+        assert_eq!(mgr.recent_files(None, 10).unwrap().len(), 3);
+
+        let sessions = mgr.file_sessions(10).unwrap();
+        assert_eq!(sessions[0].session, "s2");
+        assert_eq!(sessions[0].files, 2);
+
+        assert_eq!(mgr.file_sessions_prune(1).unwrap(), 1);
+        assert!(mgr.recent_files(Some("s1"), 10).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,122 @@
+// modern-cli-mcp/src/templates.rs
+//! Overridable summary templates for dual responses.
+//!
+//! The format module's summaries are fixed English text. A template replaces
+//! the summary of one summary type, the data URI's path without its
+//! extension (`eza/listing` for `data://eza/listing.json`). Templates come
+//! from `summary_templates` in the client config, or from the catalog of the
+//! client's locale (see `locale`), and are rendered with minijinja. The
+//! response's JSON fields are top-level variables, `data` is the whole value,
+//! `text` the raw output when it is not JSON, and `summary` the built-in
+//! summary:
+//!
+//! ```json
+//! { "eza/listing": "{{ count }} entries in {{ path }}{% if count > 50 %} (large){% endif %}" }
+//! ```
+//!
+//! Undefined variables, and printing a field that is null, are errors, so a
+//! template that does not fit a response, or does not parse, is skipped and
+//! the built-in summary is kept.
+
+use minijinja::{Environment, Error, ErrorKind, UndefinedBehavior};
+use serde_json::{Map, Value};
+use std::sync::LazyLock;
+
+static ENV: LazyLock<Environment<'static>> = LazyLock::new(|| {
+    let mut env = Environment::new();
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    // A null field is as unfit as a missing one; never print "none"
+    env.set_formatter(|out, state, value| {
+        if value.is_none() {
+            return Err(Error::new(ErrorKind::UndefinedError, "null value"));
+        }
+        minijinja::escape_formatter(out, state, value)
+    });
+    env.add_filter("lines", |text: String| -> Vec<String> {
+        text.lines().map(String::from).collect()
+    });
+    env
+});
+
+/// `eza/listing` for `data://eza/listing.json`
+pub fn summary_type(uri: &str) -> Option<&str> {
+    let path = uri.strip_prefix("data://")?;
+    Some(path.rsplit_once('.').map_or(path, |(stem, _)| stem))
+}
+
+/// Render `template` for a response, or None to keep the built-in summary
+pub fn render(template: &str, data: &str, summary: &str) -> Option<String> {
+    let mut context = Map::new();
+    match serde_json::from_str::<Value>(data) {
+        Ok(value) => {
+            if let Value::Object(fields) = &value {
+                context.extend(fields.clone());
+            }
+            context.insert("data".into(), value);
+        }
+        Err(_) => {
+            context.insert("text".into(), Value::String(data.to_string()));
+        }
+    }
+    context.insert("summary".into(), Value::String(summary.to_string()));
+    match ENV.render_str(template, Value::Object(context)) {
+        Ok(rendered) => Some(rendered.trim().to_string()),
+        Err(e) => {
+            tracing::debug!("Summary template skipped: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let data = r#"{"path": "src", "count": 72, "entries": [{"name": "a"}, {"name": "b"}]}"#;
+        assert_eq!(
+            render(
+                "{{ count }} in {{ path }}{% if count > 50 %} (large){% endif %}: {{ entries|map(attribute='name')|join(', ') }}",
+                data,
+                "",
+            )
+            .unwrap(),
+            "72 in src (large): a, b"
+        );
+        assert_eq!(
+            render(
+                "» {{ summary }} ({{ data.entries|length }})",
+                data,
+                "Listed"
+            )
+            .unwrap(),
+            "» Listed (2)"
+        );
+        assert_eq!(
+            render("{{ text|lines|length }} lines", "a\nb\nc", "").unwrap(),
+            "3 lines"
+        );
+    }
+
+    #[test]
+    fn test_unfit_templates_are_skipped() {
+        let data = r#"{"path": "src"}"#;
+        assert!(render("{{ missing }} entries", data, "").is_none());
+        assert!(render("{{ path.nested.deeper }}", data, "").is_none());
+        assert!(render("{% if %}", data, "").is_none());
+        let nullable = r#"{"user": null, "host": "h"}"#;
+        assert!(render("{{ user }}@{{ host }}", nullable, "").is_none());
+        assert_eq!(
+            render(
+                "{% if user %}{{ user }}@{% endif %}{{ host }}",
+                nullable,
+                ""
+            )
+            .unwrap(),
+            "h"
+        );
+        assert_eq!(summary_type("data://eza/listing.json"), Some("eza/listing"));
+        assert_eq!(summary_type("file:///tmp/x"), None);
+    }
+}
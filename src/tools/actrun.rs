@@ -0,0 +1,133 @@
+// modern-cli-mcp/src/tools/actrun.rs
+//! Reading act's output.
+//!
+//! `act -l` prints a column-aligned table whose cells may contain single
+//! spaces, so columns are cut at the header's offsets. Runs use `--json`,
+//! which logs one object per line; step and job ends carry `stepResult` and
+//! `jobResult`, and the other lines are the step output kept for failures.
+
+use serde_json::{json, Value};
+
+/// `act -l` rows as objects keyed by snake_cased header
+pub fn parse_list(output: &str) -> Vec<Value> {
+    let mut lines = output.lines().filter(|l| !l.trim().is_empty());
+    let Some(header) = lines.find(|l| l.trim_start().starts_with("Stage")) else {
+        return vec![];
+    };
+    let names = [
+        "Stage",
+        "Job ID",
+        "Job name",
+        "Workflow name",
+        "Workflow file",
+        "Events",
+    ];
+    let mut columns: Vec<(usize, String)> = names
+        .iter()
+        .filter_map(|n| {
+            header
+                .find(n)
+                .map(|i| (i, n.to_ascii_lowercase().replace(' ', "_")))
+        })
+        .collect();
+    columns.sort();
+
+    lines
+        .map(|line| {
+            let mut row = serde_json::Map::new();
+            for (i, (start, name)) in columns.iter().enumerate() {
+                let end = columns.get(i + 1).map_or(line.len(), |c| c.0);
+                let cell = line.get(*start..end.min(line.len())).unwrap_or("").trim();
+                row.insert(name.clone(), json!(cell));
+            }
+            Value::Object(row)
+        })
+        .collect()
+}
+
+/// Per-job results from `act --json` output, with the last `tail` output
+/// lines of each failed job
+pub fn parse_run(output: &str, tail: usize) -> Vec<Value> {
+    let mut jobs: Vec<(String, Value, Vec<Value>, Vec<String>)> = vec![];
+    for entry in output
+        .lines()
+        .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+    {
+        let Some(job) = entry["job"].as_str() else {
+            continue;
+        };
+        let index = match jobs.iter().position(|j| j.0 == job) {
+            Some(i) => i,
+            None => {
+                jobs.push((job.to_string(), Value::Null, vec![], vec![]));
+                jobs.len() - 1
+            }
+        };
+        let (_, result, steps, lines) = &mut jobs[index];
+        if let Some(step_result) = entry["stepResult"].as_str() {
+            steps.push(json!({
+                "stage": entry["stage"],
+                "step": entry["step"],
+                "result": step_result,
+            }));
+        } else if let Some(job_result) = entry["jobResult"].as_str() {
+            *result = json!(job_result);
+        } else if let Some(msg) = entry["msg"].as_str() {
+            lines.extend(msg.lines().map(|l| l.trim_end().to_string()));
+        }
+    }
+
+    jobs.into_iter()
+        .map(|(job, result, steps, lines)| {
+            let failed = result == "failure";
+            let failed_steps: Vec<&Value> = steps
+                .iter()
+                .filter(|s| s["result"] == "failure")
+                .map(|s| &s["step"])
+                .collect();
+            let start = lines.len().saturating_sub(tail);
+            json!({
+                "job": job,
+                "result": result,
+                "steps": steps,
+                "failed_steps": failed_steps,
+                "output_tail": if failed { json!(&lines[start..]) } else { Value::Null },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        let output = "Stage  Job ID  Job name    Workflow name  Workflow file  Events\n\
+                      0      lint    Lint code   CI             ci.yml         push,pull_request\n\
+                      1      test    Run tests   CI             ci.yml         push,pull_request\n";
+        let rows = parse_list(output);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["job_id"], "test");
+        assert_eq!(rows[1]["job_name"], "Run tests");
+        assert_eq!(rows[0]["events"], "push,pull_request");
+    }
+
+    #[test]
+    fn test_parse_run() {
+        let output = r#"{"job":"CI/test","level":"info","msg":"⭐ Run Main cargo test","stage":"Main","step":"cargo test"}
+{"job":"CI/test","level":"info","msg":"test it_works ... FAILED\n","raw_output":true}
+{"job":"CI/lint","level":"info","msg":"  ✅  Success - Main clippy","stage":"Main","step":"clippy","stepResult":"success"}
+{"job":"CI/lint","jobResult":"success","level":"info","msg":"🏁  Job succeeded"}
+{"job":"CI/test","level":"info","msg":"  ❌  Failure - Main cargo test","stage":"Main","step":"cargo test","stepResult":"failure"}
+{"job":"CI/test","jobResult":"failure","level":"info","msg":"🏁  Job failed"}
+not json"#;
+        let jobs = parse_run(output, 1);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0]["job"], "CI/test");
+        assert_eq!(jobs[0]["result"], "failure");
+        assert_eq!(jobs[0]["failed_steps"], json!(["cargo test"]));
+        assert_eq!(jobs[0]["output_tail"], json!(["test it_works ... FAILED"]));
+        assert!(jobs[1]["output_tail"].is_null());
+    }
+}
@@ -0,0 +1,181 @@
+// modern-cli-mcp/src/tools/archive.rs
+//! Archive helpers built around libarchive's `bsdtar`.
+//!
+//! libarchive reads tar (any compression), zip, 7z, cpio, iso and more, and
+//! lists them in the same `ls -l` layout. This turns that layout into
+//! entries with a type, size and normalized modification time, and selects
+//! the files of a directory to compress.
+
+use crate::ignore::AgentIgnore;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+/// One archive member
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArchiveEntry {
+    pub path: String,
+    /// file, directory, symlink, hardlink, or other
+    pub kind: &'static str,
+    pub size: u64,
+    /// `YYYY-MM-DDTHH:MM`, or `YYYY-MM-DD` for entries older than six months
+    pub mtime: String,
+    pub mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_target: Option<String>,
+}
+
+static LISTING_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(\S{10})\s+\d+\s+\S+\s+\S+\s+(\d+)\s+([A-Z][a-z]{2})\s+(\d{1,2})\s+(\d{1,2}:\d{2}|\d{4}) (.+)$",
+    )
+    .unwrap()
+});
+
+/// Parse `bsdtar -tvf` output. `today` places dates printed without a year
+/// (the last six months) in the right year.
+pub fn parse_listing(output: &str, today: NaiveDate) -> Vec<ArchiveEntry> {
+    output
+        .lines()
+        .filter_map(|line| LISTING_LINE.captures(line))
+        .map(|caps| {
+            let mode = caps[1].to_string();
+            let kind = match mode.as_bytes()[0] {
+                b'-' => "file",
+                b'd' => "directory",
+                b'l' => "symlink",
+                b'h' => "hardlink",
+                _ => "other",
+            };
+            let (path, link_target) = match (kind, caps[6].split_once(" -> ")) {
+                ("symlink", Some((path, target))) => (path, Some(target.to_string())),
+                _ => match caps[6].split_once(" link to ") {
+                    Some((path, target)) if kind == "hardlink" => (path, Some(target.to_string())),
+                    _ => (&caps[6], None),
+                },
+            };
+            ArchiveEntry {
+                path: path.to_string(),
+                kind,
+                size: caps[2].parse().unwrap_or(0),
+                mtime: normalize_mtime(&caps[3], &caps[4], &caps[5], today),
+                mode,
+                link_target,
+            }
+        })
+        .collect()
+}
+
+fn normalize_mtime(month: &str, day: &str, time_or_year: &str, today: NaiveDate) -> String {
+    let parsed = |year: i32| {
+        NaiveDate::parse_from_str(&format!("{} {} {}", year, month, day), "%Y %b %d").ok()
+    };
+    if let Ok(year) = time_or_year.parse::<i32>() {
+        return parsed(year)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+    }
+    // Recent dates omit the year; a date after today belongs to last year
+    let date = match parsed(today.year()) {
+        Some(d) if d > today => parsed(today.year() - 1),
+        other => other,
+    };
+    date.and_then(|d| {
+        NaiveDateTime::parse_from_str(&format!("{} {}", d, time_or_year), "%Y-%m-%d %H:%M").ok()
+    })
+    .map(|dt| dt.format("%Y-%m-%dT%H:%M").to_string())
+    .unwrap_or_default()
+}
+
+/// Files under `dir` to put in an archive, relative to `dir`, with their
+/// total size. `include` globs (if any) select files; `exclude` globs and
+/// `.agentignore` drop files and directories.
+pub fn collect_files(
+    dir: &Path,
+    include: &[&str],
+    exclude: &[&str],
+    ignore: Arc<AgentIgnore>,
+) -> Result<(Vec<PathBuf>, u64), String> {
+    let mut overrides = OverrideBuilder::new(dir);
+    for glob in include {
+        overrides
+            .add(glob)
+            .map_err(|e| format!("Invalid include glob '{}': {}", glob, e))?;
+    }
+    for glob in exclude {
+        overrides
+            .add(&format!("!{}", glob))
+            .map_err(|e| format!("Invalid exclude glob '{}': {}", glob, e))?;
+    }
+    let overrides = overrides.build().map_err(|e| e.to_string())?;
+
+    let mut files = vec![];
+    let mut total = 0;
+    let walker = WalkBuilder::new(dir)
+        .standard_filters(false)
+        .overrides(overrides)
+        .filter_entry(move |e| e.depth() == 0 || !ignore.is_ignored(e.path()))
+        .build();
+    for entry in walker.filter_map(Result::ok) {
+        if entry.file_type().is_some_and(|t| t.is_dir()) {
+            continue;
+        }
+        total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if let Ok(rel) = entry.path().strip_prefix(dir) {
+            files.push(rel.to_path_buf());
+        }
+    }
+    files.sort();
+    Ok((files, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["src/main.rs", "src/lib.rs", "target/debug/app", "README.md"] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "12345").unwrap();
+        }
+        let ignore = Arc::new(AgentIgnore::default());
+
+        let (files, total) = collect_files(dir.path(), &[], &["target"], ignore.clone()).unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(total, 15);
+
+        let (files, _) = collect_files(dir.path(), &["*.rs"], &["lib.rs"], ignore.clone()).unwrap();
+        assert_eq!(files, [PathBuf::from("src/main.rs")]);
+        assert!(collect_files(dir.path(), &["a{"], &[], ignore).is_err());
+    }
+
+    #[test]
+    fn test_parse_listing() {
+        let output = "\
+drwxr-xr-x  0 0      0           0 Oct 17 09:51 d/
+lrwxrwxrwx  0 0      0           0 Oct 17 09:51 d/link -> a.txt
+-rw-r--r--  0 0      0           4 Dec 30 23:59 d/sub/sp ace.txt
+-rw-r--r--  0 root   root        6 Jan  2  2020 d/a.txt
+";
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let entries = parse_listing(output, today);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].kind, "directory");
+        assert_eq!(entries[0].mtime, "2026-10-17T09:51");
+        assert_eq!(entries[1].path, "d/link");
+        assert_eq!(entries[1].link_target.as_deref(), Some("a.txt"));
+        assert_eq!(entries[2].path, "d/sub/sp ace.txt");
+        assert_eq!(entries[2].mtime, "2025-12-30T23:59");
+        assert_eq!(
+            (entries[3].size, entries[3].mtime.as_str()),
+            (6, "2020-01-02")
+        );
+    }
+}
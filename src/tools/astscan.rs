@@ -0,0 +1,104 @@
+// modern-cli-mcp/src/tools/astscan.rs
+//! ast-grep rule scans.
+//!
+//! `sg scan` takes its rules from exactly one place: a single rule file, inline
+//! YAML, or a project sgconfig.yml (auto-discovered when none is given). Its
+//! `--json=stream` output is one match per line with zero-based positions;
+//! findings are reduced to the rule that fired, where, and the autofix it
+//! proposes.
+
+use serde_json::{json, Value};
+
+/// The `sg scan` flag and value for the requested rule source. Rule sources
+/// are exclusive; passing more than one is an error rather than silently
+/// picking one.
+pub fn rule_source<'a>(
+    rule_file: Option<&'a str>,
+    inline_rules: Option<&'a str>,
+    config: Option<&'a str>,
+) -> Result<Option<(&'static str, &'a str)>, String> {
+    let given: Vec<(&'static str, &str, &'a str)> = [
+        ("--rule", "rule_file", rule_file),
+        ("--inline-rules", "inline_rules", inline_rules),
+        ("--config", "config", config),
+    ]
+    .into_iter()
+    .filter_map(|(flag, name, value)| value.map(|v| (flag, name, v)))
+    .collect();
+    match given.as_slice() {
+        [] => Ok(None),
+        [(flag, _, value)] => Ok(Some((*flag, *value))),
+        _ => Err(format!(
+            "Pass only one of rule_file, inline_rules or config (got {})",
+            given
+                .iter()
+                .map(|(_, name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Convert `sg scan --json=stream` output into compact rule-tagged findings
+pub fn parse_findings(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .map(|m| {
+            let start = &m["range"]["start"];
+            json!({
+                "rule_id": m["ruleId"],
+                "severity": m["severity"],
+                "message": m["message"],
+                "note": m["note"],
+                "file": m["file"],
+                "line": start["line"].as_u64().map(|l| l + 1),
+                "column": start["column"].as_u64().map(|c| c + 1),
+                "text": m["text"],
+                "replacement": m["replacement"]
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_source() {
+        assert_eq!(rule_source(None, None, None), Ok(None));
+        assert_eq!(
+            rule_source(Some("rules/no-unwrap.yml"), None, None),
+            Ok(Some(("--rule", "rules/no-unwrap.yml")))
+        );
+        assert_eq!(
+            rule_source(None, None, Some("sgconfig.yml")),
+            Ok(Some(("--config", "sgconfig.yml")))
+        );
+        let err = rule_source(Some("r.yml"), Some("id: x"), None).unwrap_err();
+        assert!(err.contains("rule_file, inline_rules"), "{}", err);
+        assert!(rule_source(None, Some("id: x"), Some("sgconfig.yml")).is_err());
+    }
+
+    #[test]
+    fn test_parse_findings() {
+        let output = concat!(
+            r#"{"text":"x.unwrap()","range":{"start":{"line":4,"column":8},"end":{"line":4,"column":18}},"file":"src/main.rs","ruleId":"no-unwrap","severity":"warning","message":"Avoid unwrap","note":null,"replacement":"x?"}"#,
+            "\n",
+            "warning: not json\n",
+            r#"{"text":"dbg!(y)","range":{"start":{"line":0,"column":0}},"file":"src/lib.rs","ruleId":"no-dbg","severity":"error","message":"Remove dbg!"}"#,
+            "\n"
+        );
+        let findings = parse_findings(output);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0]["rule_id"], "no-unwrap");
+        assert_eq!(
+            (findings[0]["line"].as_u64(), findings[0]["column"].as_u64()),
+            (Some(5), Some(9))
+        );
+        assert_eq!(findings[0]["replacement"], "x?");
+        assert_eq!(findings[1]["severity"], "error");
+        assert!(findings[1]["replacement"].is_null());
+    }
+}
@@ -0,0 +1,515 @@
+// modern-cli-mcp/src/tools/binary.rs
+//! Looking inside compiled binaries.
+//!
+//! Strings are found by scanning the bytes directly (printable ASCII runs,
+//! optionally UTF-16LE) so no binutils `strings` flavor differences leak in.
+//! ELF headers come from `readelf --wide`, other formats from `objdump -p`,
+//! and binwalk's signature table is read from its text output, which has the
+//! same columns in the Python and Rust releases. Whether an ELF file can run
+//! here is judged from its machine, interpreter, `NEEDED` libraries (found
+//! via RUNPATH/RPATH, the ld.so cache or the usual directories) and the
+//! newest `GLIBC_*` version it requires. Nothing is ever executed.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Library directories searched when the ld.so cache has no entry
+const LIBRARY_DIRS: &[&str] = &[
+    "/lib",
+    "/lib64",
+    "/usr/lib",
+    "/usr/lib64",
+    "/usr/local/lib",
+    "/lib/x86_64-linux-gnu",
+    "/usr/lib/x86_64-linux-gnu",
+    "/lib/aarch64-linux-gnu",
+    "/usr/lib/aarch64-linux-gnu",
+];
+
+fn is_printable(b: u8) -> bool {
+    (0x20..0x7f).contains(&b) || b == b'\t'
+}
+
+/// Printable runs of at least `min_len` characters as `{offset, text,
+/// encoding}`, ASCII first and then UTF-16LE when `utf16` is set
+pub fn strings(data: &[u8], min_len: usize, utf16: bool) -> Vec<Value> {
+    let min_len = min_len.max(1);
+    let mut found = vec![];
+    let mut start = 0;
+    for (i, &b) in data.iter().chain([0u8].iter()).enumerate() {
+        if is_printable(b) {
+            continue;
+        }
+        if i - start >= min_len {
+            let text = String::from_utf8_lossy(&data[start..i]);
+            found.push(json!({ "offset": start, "text": text, "encoding": "ascii" }));
+        }
+        start = i + 1;
+    }
+
+    if utf16 {
+        for parity in 0..2 {
+            let mut run = String::new();
+            let mut run_start = parity;
+            let units = data[parity..].chunks(2);
+            for (n, unit) in units.chain([&[0u8, 1][..]]).enumerate() {
+                let offset = parity + n * 2;
+                match unit {
+                    [lo, 0] if is_printable(*lo) => {
+                        if run.is_empty() {
+                            run_start = offset;
+                        }
+                        run.push(*lo as char);
+                    }
+                    _ => {
+                        if run.len() >= min_len {
+                            found.push(json!({
+                                "offset": run_start,
+                                "text": run,
+                                "encoding": "utf16le",
+                            }));
+                        }
+                        run.clear();
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Text inside the first `[...]` of a readelf value
+fn bracketed(s: &str) -> Option<&str> {
+    let start = s.find('[')? + 1;
+    let end = s[start..].find(']')? + start;
+    Some(&s[start..end])
+}
+
+/// `2.34` sorts above `2.4`
+fn version_key(v: &str) -> Vec<u64> {
+    v.split('.').filter_map(|p| p.parse().ok()).collect()
+}
+
+/// Headers, segments of interest, dynamic entries and required symbol
+/// versions from `readelf --file-header --program-headers --dynamic
+/// --version-info --wide`
+pub fn parse_readelf(output: &str) -> Value {
+    let mut header = BTreeMap::new();
+    let mut segments: Vec<String> = vec![];
+    let mut interpreter = None;
+    let mut executable_stack = None;
+    let mut needed = vec![];
+    let (mut soname, mut rpath, mut runpath) = (None, None, None);
+    let mut flags: Vec<String> = vec![];
+    let mut version_needs: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut section = "";
+    let mut current_file = String::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if line.starts_with("ELF Header:") {
+            section = "header";
+            continue;
+        } else if line.starts_with("Program Headers:") {
+            section = "program";
+            continue;
+        } else if line.starts_with("Dynamic section") {
+            section = "dynamic";
+            continue;
+        } else if line.starts_with("Version needs section") {
+            section = "verneed";
+            continue;
+        } else if line.starts_with(' ') || trimmed.is_empty() {
+            // Indented lines belong to the current section
+        } else {
+            section = "";
+        }
+
+        match section {
+            "header" => {
+                if let Some((key, value)) = trimmed.split_once(':') {
+                    header.insert(key.trim().to_string(), value.trim().to_string());
+                }
+            }
+            "program" => {
+                if let Some(interp) = trimmed.strip_prefix("[Requesting program interpreter: ") {
+                    interpreter = interp.strip_suffix(']').map(String::from);
+                    continue;
+                }
+                let Some(kind) = trimmed.split_whitespace().next() else {
+                    continue;
+                };
+                if kind.chars().all(|c| c.is_ascii_uppercase() || c == '_') && kind != "Type" {
+                    if kind == "GNU_STACK" {
+                        // The Flg column sits before the alignment; E means executable
+                        let cols: Vec<&str> = trimmed.split_whitespace().collect();
+                        let flg = cols
+                            .len()
+                            .checked_sub(1)
+                            .and_then(|end| cols.get(6..end))
+                            .unwrap_or(&[]);
+                        executable_stack = Some(flg.iter().any(|f| f.contains('E')));
+                    }
+                    if !segments.iter().any(|s| s == kind) {
+                        segments.push(kind.to_string());
+                    }
+                }
+            }
+            "dynamic" => {
+                let Some(tag) = bracketed_tag(trimmed) else {
+                    continue;
+                };
+                let value = trimmed.split_once(')').map_or("", |(_, v)| v.trim());
+                match tag {
+                    "NEEDED" => needed.extend(bracketed(value).map(String::from)),
+                    "SONAME" => soname = bracketed(value).map(String::from),
+                    "RPATH" => rpath = bracketed(value).map(String::from),
+                    "RUNPATH" => runpath = bracketed(value).map(String::from),
+                    "FLAGS" | "FLAGS_1" => flags.extend(
+                        value
+                            .trim_start_matches("Flags:")
+                            .split_whitespace()
+                            .map(String::from),
+                    ),
+                    "BIND_NOW" => flags.push("BIND_NOW".into()),
+                    _ => {}
+                }
+            }
+            "verneed" => {
+                if let Some((_, rest)) = trimmed.split_once("File: ") {
+                    current_file = rest.split_whitespace().next().unwrap_or("").to_string();
+                } else if let Some((_, rest)) = trimmed.split_once("Name: ") {
+                    let name = rest.split_whitespace().next().unwrap_or("").to_string();
+                    version_needs
+                        .entry(current_file.clone())
+                        .or_default()
+                        .push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    flags.sort();
+    flags.dedup();
+    for versions in version_needs.values_mut() {
+        versions.sort_by_key(|v| {
+            let (prefix, number) = v.rsplit_once('_').unwrap_or((v, ""));
+            (prefix.to_string(), version_key(number))
+        });
+    }
+    let glibc_required = version_needs
+        .values()
+        .flatten()
+        .filter_map(|v| v.strip_prefix("GLIBC_"))
+        .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+        .max_by_key(|v| version_key(v))
+        .map(String::from);
+    let get = |key: &str| header.get(key).cloned();
+    let kind = get("Type");
+    let linking = if interpreter.is_some() || !needed.is_empty() {
+        "dynamic"
+    } else {
+        "static"
+    };
+
+    json!({
+        "format": "elf",
+        "class": get("Class"),
+        "endianness": get("Data").map(|d| if d.contains("big") { "big" } else { "little" }),
+        "os_abi": get("OS/ABI"),
+        "type": kind.as_deref().and_then(|t| t.split_whitespace().next()),
+        "machine": get("Machine"),
+        "entry": get("Entry point address"),
+        "linking": linking,
+        "pie": kind.as_deref().is_some_and(|t| t.starts_with("DYN")) && interpreter.is_some(),
+        "interpreter": interpreter,
+        "needed": needed,
+        "soname": soname,
+        "rpath": rpath,
+        "runpath": runpath,
+        "flags": flags,
+        "relro": segments.iter().any(|s| s == "GNU_RELRO"),
+        "executable_stack": executable_stack,
+        "segments": segments,
+        "version_needs": version_needs,
+        "glibc_required": glibc_required,
+    })
+}
+
+/// `NEEDED` out of ` 0x0000000000000001 (NEEDED)  Shared library: [libc.so.6]`
+fn bracketed_tag(line: &str) -> Option<&str> {
+    let start = line.find('(')? + 1;
+    let end = line[start..].find(')')? + start;
+    Some(&line[start..end])
+}
+
+/// File format, architecture and imported DLLs from `objdump -f -p`, for
+/// PE and other non-ELF files
+pub fn parse_objdump(output: &str) -> Value {
+    let mut format = None;
+    let mut architecture = None;
+    let mut needed = vec![];
+    for line in output.lines().map(str::trim) {
+        if let Some((_, f)) = line.split_once("file format ") {
+            format.get_or_insert_with(|| f.trim().to_string());
+        } else if let Some(arch) = line.strip_prefix("architecture: ") {
+            architecture = Some(arch.split(',').next().unwrap_or(arch).trim().to_string());
+        } else if let Some(dll) = line.strip_prefix("DLL Name: ") {
+            needed.push(dll.trim().to_string());
+        }
+    }
+    json!({
+        "format": format,
+        "machine": architecture,
+        "needed": needed,
+    })
+}
+
+/// `{offset, hex_offset, description}` rows of a binwalk signature scan
+pub fn parse_binwalk(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut cols = line.split_whitespace();
+            let offset: u64 = cols.next()?.parse().ok()?;
+            let hex = cols.next().filter(|h| h.starts_with("0x"))?;
+            let (_, description) = line.split_once(hex)?;
+            Some(json!({
+                "offset": offset,
+                "hex_offset": hex,
+                "description": description.trim(),
+            }))
+        })
+        .collect()
+}
+
+/// readelf's name for the machine this server runs on
+pub fn host_machine() -> Option<&'static str> {
+    match std::env::consts::ARCH {
+        "x86_64" => Some("Advanced Micro Devices X86-64"),
+        "x86" => Some("Intel 80386"),
+        "aarch64" => Some("AArch64"),
+        "arm" => Some("ARM"),
+        "riscv64" => Some("RISC-V"),
+        "powerpc64" => Some("PowerPC64"),
+        "s390x" => Some("IBM S/390"),
+        _ => None,
+    }
+}
+
+/// Where each `NEEDED` library resolves and what would stop the binary from
+/// running on this host. `ld_cache` is `ldconfig -p` output and
+/// `host_glibc` the `getconf GNU_LIBC_VERSION` number.
+pub fn runnability(
+    headers: &Value,
+    binary: &Path,
+    ld_cache: &str,
+    host_glibc: Option<&str>,
+    exists: impl Fn(&Path) -> bool,
+) -> Value {
+    let mut problems = vec![];
+    if let (Some(machine), Some(host)) = (headers["machine"].as_str(), host_machine()) {
+        if machine != host {
+            problems.push(format!("built for {}, this host is {}", machine, host));
+        }
+    }
+    if let Some(interp) = headers["interpreter"].as_str() {
+        if !exists(Path::new(interp)) {
+            problems.push(format!("interpreter {} does not exist here", interp));
+        }
+    }
+
+    let origin = binary
+        .parent()
+        .unwrap_or(Path::new("."))
+        .display()
+        .to_string();
+    // RUNPATH wins over RPATH, which is ignored when both are set
+    let path_list = headers["runpath"].as_str().or(headers["rpath"].as_str());
+    let search_dirs: Vec<PathBuf> = path_list
+        .into_iter()
+        .flat_map(|p| p.split(':'))
+        .map(|d| PathBuf::from(d.replace("${ORIGIN}", &origin).replace("$ORIGIN", &origin)))
+        .collect();
+    let cache: BTreeMap<&str, &str> = ld_cache
+        .lines()
+        .filter_map(|l| {
+            let (name, path) = l.trim().split_once(" => ")?;
+            Some((name.split_whitespace().next()?, path.trim()))
+        })
+        .collect();
+
+    let mut libraries = vec![];
+    for lib in headers["needed"].as_array().into_iter().flatten() {
+        let Some(name) = lib.as_str() else {
+            continue;
+        };
+        let found = search_dirs
+            .iter()
+            .map(|d| d.join(name))
+            .find(|p| exists(p))
+            .map(|p| p.display().to_string())
+            .or_else(|| cache.get(name).map(|p| p.to_string()))
+            .or_else(|| {
+                LIBRARY_DIRS
+                    .iter()
+                    .map(|d| Path::new(d).join(name))
+                    .find(|p| exists(p))
+                    .map(|p| p.display().to_string())
+            });
+        if found.is_none() && headers["format"] == "elf" {
+            problems.push(format!("library {} not found", name));
+        }
+        libraries.push(json!({ "name": name, "path": found }));
+    }
+
+    if let (Some(required), Some(host)) = (headers["glibc_required"].as_str(), host_glibc) {
+        if version_key(required) > version_key(host) {
+            problems.push(format!("needs glibc {}, this host has {}", required, host));
+        }
+    }
+    json!({
+        "runnable": problems.is_empty(),
+        "host_machine": host_machine(),
+        "host_glibc": host_glibc,
+        "libraries": libraries,
+        "problems": problems,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings() {
+        let data =
+            b"\x7fELF\x00\x01hello world\x00ab\x00/lib/ld.so\xffw\x00i\x00d\x00e\x00\x00\x00";
+        let ascii = strings(data, 4, false);
+        let texts: Vec<&str> = ascii.iter().map(|s| s["text"].as_str().unwrap()).collect();
+        assert_eq!(texts, ["hello world", "/lib/ld.so"]);
+        assert_eq!(ascii[0]["offset"], 6);
+
+        let all = strings(data, 4, true);
+        let wide = all.iter().find(|s| s["encoding"] == "utf16le").unwrap();
+        assert_eq!(wide["text"], "wide");
+        assert_eq!(wide["offset"], 32);
+    }
+
+    const READELF: &str = "ELF Header:
+  Class:                             ELF64
+  Data:                              2's complement, little endian
+  OS/ABI:                            UNIX - System V
+  Type:                              DYN (Position-Independent Executable file)
+  Machine:                           Advanced Micro Devices X86-64
+  Entry point address:               0x61d0
+
+Program Headers:
+  Type           Offset   VirtAddr           PhysAddr           FileSiz  MemSiz   Flg Align
+  INTERP         0x000318 0x0000000000000318 0x0000000000000318 0x00001c 0x00001c R   0x1
+      [Requesting program interpreter: /lib64/ld-linux-x86-64.so.2]
+  LOAD           0x004000 0x0000000000004000 0x0000000000004000 0x015759 0x015759 R E 0x1000
+  GNU_STACK      0x000000 0x0000000000000000 0x0000000000000000 0x000000 0x000000 RW  0x10
+  GNU_RELRO      0x0232b0 0x00000000000232b0 0x00000000000232b0 0x000d50 0x000d50 R   0x1
+
+ Section to Segment mapping:
+  Segment Sections...
+   00     .interp
+
+Dynamic section at offset 0x23d98 contains 27 entries:
+  Tag        Type                         Name/Value
+ 0x0000000000000001 (NEEDED)             Shared library: [libselinux.so.1]
+ 0x0000000000000001 (NEEDED)             Shared library: [libc.so.6]
+ 0x000000000000001d (RUNPATH)            Library runpath: [$ORIGIN/../lib]
+ 0x000000006ffffffb (FLAGS_1)            Flags: NOW PIE
+
+Version needs section '.gnu.version_r' contains 2 entries:
+ Addr: 0x0000000000001718  Offset: 0x00001718  Link: 7 (.dynstr)
+  000000: Version: 1  File: libselinux.so.1  Cnt: 1
+  0x0010:   Name: LIBSELINUX_1.0  Flags: none  Version: 4
+  0x0020: Version: 1  File: libc.so.6  Cnt: 3
+  0x0030:   Name: GLIBC_2.4  Flags: none  Version: 12
+  0x0040:   Name: GLIBC_2.34  Flags: none  Version: 11
+  0x0050:   Name: GLIBC_2.2.5  Flags: none  Version: 3
+";
+
+    #[test]
+    fn test_parse_readelf() {
+        let h = parse_readelf(READELF);
+        assert_eq!(h["class"], "ELF64");
+        assert_eq!(h["type"], "DYN");
+        assert_eq!(h["pie"], true);
+        assert_eq!(h["interpreter"], "/lib64/ld-linux-x86-64.so.2");
+        assert_eq!(h["needed"], json!(["libselinux.so.1", "libc.so.6"]));
+        assert_eq!(h["runpath"], "$ORIGIN/../lib");
+        assert_eq!(h["flags"], json!(["NOW", "PIE"]));
+        assert_eq!(h["executable_stack"], false);
+        assert_eq!(h["relro"], true);
+        assert_eq!(
+            h["segments"],
+            json!(["INTERP", "LOAD", "GNU_STACK", "GNU_RELRO"])
+        );
+        assert_eq!(
+            h["version_needs"]["libc.so.6"],
+            json!(["GLIBC_2.2.5", "GLIBC_2.4", "GLIBC_2.34"])
+        );
+        assert_eq!(h["glibc_required"], "2.34");
+    }
+
+    #[test]
+    fn test_runnability() {
+        let mut headers = parse_readelf(READELF);
+        headers["machine"] = json!(host_machine());
+        let cache = "2 libs found in cache `/etc/ld.so.cache'\n\
+                     \tlibc.so.6 (libc6,x86-64) => /lib/x86_64-linux-gnu/libc.so.6\n";
+        let exists = |p: &Path| {
+            p == Path::new("/lib64/ld-linux-x86-64.so.2")
+                || p == Path::new("/opt/app/bin/../lib/libselinux.so.1")
+        };
+        let result = runnability(
+            &headers,
+            Path::new("/opt/app/bin/tool"),
+            cache,
+            Some("2.31"),
+            exists,
+        );
+        assert_eq!(
+            result["libraries"][0]["path"],
+            "/opt/app/bin/../lib/libselinux.so.1"
+        );
+        assert_eq!(
+            result["libraries"][1]["path"],
+            "/lib/x86_64-linux-gnu/libc.so.6"
+        );
+        assert_eq!(result["runnable"], false);
+        assert_eq!(
+            result["problems"],
+            json!(["needs glibc 2.34, this host has 2.31"])
+        );
+    }
+
+    #[test]
+    fn test_parse_binwalk_and_objdump() {
+        let output = "\nDECIMAL       HEXADECIMAL     DESCRIPTION\n\
+                      --------------------------------------------------------------------------------\n\
+                      0             0x0             uImage header, header size: 64 bytes\n\
+                      64            0x40            LZMA compressed data, properties: 0x5D\n";
+        let rows = parse_binwalk(output);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["offset"], 64);
+        assert_eq!(
+            rows[1]["description"],
+            "LZMA compressed data, properties: 0x5D"
+        );
+
+        let pe = parse_objdump(
+            "\napp.exe:     file format pei-x86-64\narchitecture: i386:x86-64, flags 0x0000012f:\n\
+             \tDLL Name: KERNEL32.dll\n\tDLL Name: msvcrt.dll\n",
+        );
+        assert_eq!(pe["format"], "pei-x86-64");
+        assert_eq!(pe["machine"], "i386:x86-64");
+        assert_eq!(pe["needed"], json!(["KERNEL32.dll", "msvcrt.dll"]));
+    }
+}
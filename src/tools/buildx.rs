@@ -0,0 +1,123 @@
+// modern-cli-mcp/src/tools/buildx.rs
+//! Parsing for buildx bake plans and build cache reports.
+//!
+//! `buildx bake --print` emits the resolved bake file as JSON (groups and
+//! targets after variables, inheritance and `--set` overrides), which is
+//! reduced to what each target would build. `buildx du` and `buildx prune`
+//! only print tables, so their rows and the `Shared:`/`Total:` trailer are
+//! split on tabs or runs of spaces.
+
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::sync::LazyLock;
+
+static COLUMNS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\t+|\s{2,}").unwrap());
+
+/// Targets and groups from `buildx bake --print`
+pub fn bake_plan(print: &Value) -> Value {
+    let targets: Vec<Value> = print["target"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, t)| {
+            json!({
+                "name": name,
+                "context": t["context"],
+                "dockerfile": t["dockerfile"],
+                "target": t["target"],
+                "tags": t["tags"].as_array().cloned().unwrap_or_default(),
+                "platforms": t["platforms"].as_array().cloned().unwrap_or_default(),
+                "cache_from": t["cache-from"].as_array().cloned().unwrap_or_default(),
+                "cache_to": t["cache-to"].as_array().cloned().unwrap_or_default(),
+                "output": t["output"].as_array().cloned().unwrap_or_default(),
+            })
+        })
+        .collect();
+    let groups: Map<String, Value> = print["group"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, g)| (name.clone(), g["targets"].clone()))
+        .collect();
+    json!({ "groups": groups, "targets": targets })
+}
+
+/// `Shared:`, `Private:`, `Reclaimable:` and `Total:` trailer lines
+fn totals(output: &str) -> Map<String, Value> {
+    output
+        .lines()
+        .filter_map(|l| l.split_once(':'))
+        .filter(|(key, _)| matches!(key.trim(), "Shared" | "Private" | "Reclaimable" | "Total"))
+        .map(|(key, value)| (key.trim().to_ascii_lowercase(), json!(value.trim())))
+        .collect()
+}
+
+/// Cache records and totals from `buildx du` (or the table `buildx prune`
+/// prints of what it removed)
+pub fn parse_du(output: &str) -> Value {
+    let records: Vec<Value> = output
+        .lines()
+        .skip_while(|l| !l.starts_with("ID"))
+        .skip(1)
+        .take_while(|l| !l.trim().is_empty() && !l.contains(':'))
+        .filter_map(|l| {
+            let cols: Vec<&str> = COLUMNS.split(l.trim()).collect();
+            let (id, reclaimable, size) = (cols.first()?, cols.get(1)?, cols.get(2)?);
+            Some(json!({
+                "id": id.trim_end_matches('*'),
+                "reclaimable": *reclaimable == "true",
+                "size": size,
+                "last_accessed": cols.get(3),
+            }))
+        })
+        .collect();
+    let mut result = totals(output);
+    result.insert("count".into(), json!(records.len()));
+    result.insert("records".into(), json!(records));
+    Value::Object(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bake_plan() {
+        let print = serde_json::json!({
+            "group": {"default": {"targets": ["api", "web"]}},
+            "target": {
+                "api": {"context": ".", "dockerfile": "api/Dockerfile",
+                        "tags": ["ghcr.io/o/api:dev"], "platforms": ["linux/amd64", "linux/arm64"],
+                        "cache-from": ["type=gha"], "cache-to": ["type=gha,mode=max"]},
+                "web": {"context": "web", "dockerfile": "Dockerfile"}
+            }
+        });
+        let plan = bake_plan(&print);
+        assert_eq!(plan["groups"]["default"], json!(["api", "web"]));
+        assert_eq!(plan["targets"][0]["name"], "api");
+        assert_eq!(plan["targets"][0]["cache_to"], json!(["type=gha,mode=max"]));
+        assert_eq!(plan["targets"][1]["platforms"], json!([]));
+    }
+
+    #[test]
+    fn test_parse_du() {
+        let output = "ID                                              RECLAIMABLE     SIZE            LAST ACCESSED\n\
+            v2ioiq8ys1uzcaq8n0n9dq2ts*                      true            1.012GB         2 days ago\n\
+            qk0sdb4ze6yd1vkbfbq0pdzgq                       false           87.4MB\n\
+            Shared:         87.4MB\n\
+            Private:        1.012GB\n\
+            Reclaimable:    1.012GB\n\
+            Total:          1.099GB\n";
+        let du = parse_du(output);
+        assert_eq!(du["count"], 2);
+        assert_eq!(du["records"][0]["id"], "v2ioiq8ys1uzcaq8n0n9dq2ts");
+        assert_eq!(du["records"][0]["last_accessed"], "2 days ago");
+        assert_eq!(du["records"][1]["reclaimable"], false);
+        assert_eq!(du["total"], "1.099GB");
+        assert_eq!(du["reclaimable"], "1.012GB");
+
+        let pruned = parse_du("ID\tRECLAIMABLE\tSIZE\tLAST ACCESSED\nTotal:\t0B\n");
+        assert_eq!(pruned["count"], 0);
+        assert_eq!(pruned["total"], "0B");
+    }
+}
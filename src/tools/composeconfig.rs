@@ -0,0 +1,123 @@
+// modern-cli-mcp/src/tools/composeconfig.rs
+//! Rendered compose configuration.
+//!
+//! `docker compose config --format json` and `podman-compose config` (YAML)
+//! both print the effective model: files merged, variables interpolated and
+//! services outside the active profiles dropped. Problems that do not stop
+//! rendering, such as unset variables silently becoming blank strings, only
+//! show up as warnings on stderr, so they are collected separately from the
+//! errors that make the file invalid.
+
+use serde_json::{json, Value};
+
+/// The rendered model from either runtime's `config` output
+pub fn parse_config(output: &str) -> Result<Value, String> {
+    let trimmed = output.trim();
+    if trimmed.starts_with('{') {
+        return serde_json::from_str(trimmed).map_err(|e| format!("Invalid config JSON: {}", e));
+    }
+    serde_yaml::from_str::<Value>(trimmed).map_err(|e| format!("Invalid config YAML: {}", e))
+}
+
+/// Warning lines (unset variables, obsolete keys) from compose's stderr
+pub fn warnings(stderr: &str) -> Vec<String> {
+    stderr
+        .lines()
+        .map(str::trim)
+        .filter(|l| {
+            let lower = l.to_ascii_lowercase();
+            lower.contains("warn") || lower.contains("is not set") || lower.contains("obsolete")
+        })
+        .map(|l| {
+            l.trim_start_matches("WARN[0000]")
+                .trim_start_matches("WARNING:")
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Per-service overview: image or build context, ports, dependencies and
+/// the profiles that enable it
+pub fn services(config: &Value) -> Vec<Value> {
+    let Some(services) = config["services"].as_object() else {
+        return vec![];
+    };
+    services
+        .iter()
+        .map(|(name, svc)| {
+            let depends_on: Vec<&str> = match &svc["depends_on"] {
+                Value::Object(map) => map.keys().map(String::as_str).collect(),
+                Value::Array(list) => list.iter().filter_map(|d| d.as_str()).collect(),
+                _ => vec![],
+            };
+            let ports: Vec<String> = svc["ports"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|p| match p {
+                    Value::String(s) => s.clone(),
+                    Value::Object(_) => format!(
+                        "{}:{}/{}",
+                        p["published"]
+                            .as_str()
+                            .map(String::from)
+                            .unwrap_or_else(|| p["published"].to_string()),
+                        p["target"],
+                        p["protocol"].as_str().unwrap_or("tcp")
+                    ),
+                    other => other.to_string(),
+                })
+                .collect();
+            json!({
+                "name": name,
+                "image": svc["image"],
+                "build": svc["build"]["context"].as_str().or(svc["build"].as_str()),
+                "ports": ports,
+                "depends_on": depends_on,
+                "profiles": svc["profiles"],
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_docker_json() {
+        let output = r#"{"name":"app","services":{
+            "db":{"image":"postgres:16","profiles":["full"]},
+            "web":{"build":{"context":"/src/app"},"depends_on":{"db":{"condition":"service_healthy"}},
+                   "ports":[{"mode":"ingress","target":80,"published":"8080","protocol":"tcp"}]}}}"#;
+        let config = parse_config(output).unwrap();
+        let services = services(&config);
+        assert_eq!(services.len(), 2);
+        assert_eq!(services[0]["profiles"], json!(["full"]));
+        assert_eq!(services[1]["build"], "/src/app");
+        assert_eq!(services[1]["depends_on"], json!(["db"]));
+        assert_eq!(services[1]["ports"], json!(["8080:80/tcp"]));
+    }
+
+    #[test]
+    fn test_parse_podman_yaml() {
+        let output = "services:\n  web:\n    image: nginx\n    ports:\n    - 8080:80\n    depends_on:\n    - db\n";
+        let config = parse_config(output).unwrap();
+        let services = services(&config);
+        assert_eq!(services[0]["ports"], json!(["8080:80"]));
+        assert_eq!(services[0]["depends_on"], json!(["db"]));
+        assert!(parse_config("services: [unclosed").is_err());
+    }
+
+    #[test]
+    fn test_warnings() {
+        let stderr =
+            "WARN[0000] The \"API_KEY\" variable is not set. Defaulting to a blank string.\n\
+                      WARN[0000] /src/compose.yml: the attribute `version` is obsolete\n\
+                      Pulling web\n";
+        let found = warnings(stderr);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].starts_with("The \"API_KEY\" variable"));
+    }
+}
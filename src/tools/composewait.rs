@@ -0,0 +1,131 @@
+// modern-cli-mcp/src/tools/composewait.rs
+//! Compose service readiness.
+//!
+//! `compose up -d` returns once containers are created, not once they can
+//! serve. Waiting inspects every container of the stack: running services
+//! with a healthcheck are ready when healthy, one-shot services (migrations,
+//! init jobs) when they exit cleanly, and anything that exits with an error
+//! fails the wait early.
+
+use serde_json::{json, Value};
+
+/// Extract per-service state, health and published ports from `inspect` output
+pub fn parse_inspect(output: &str) -> Vec<Value> {
+    let containers: Vec<Value> = serde_json::from_str(output).unwrap_or_default();
+    containers
+        .iter()
+        .map(|c| {
+            let state = &c["State"];
+            let status = state["Status"].as_str().unwrap_or("unknown");
+            let exit_code = state["ExitCode"].as_i64();
+            // One-shot services (migrations, init jobs) count as ready once they exit cleanly
+            let state_name = match (status, exit_code) {
+                ("exited", Some(0)) => "completed",
+                ("exited" | "dead", _) => "failed",
+                (s, _) => s,
+            };
+            // podman reports health under Healthcheck on older versions
+            let health = state["Health"]["Status"]
+                .as_str()
+                .or_else(|| state["Healthcheck"]["Status"].as_str())
+                .filter(|h| !h.is_empty());
+
+            let mut ports = vec![];
+            if let Some(map) = c["NetworkSettings"]["Ports"].as_object() {
+                for (container_port, bindings) in map {
+                    if !container_port.ends_with("/tcp") {
+                        continue;
+                    }
+                    for b in bindings.as_array().into_iter().flatten() {
+                        let Some(port) = b["HostPort"].as_str().and_then(|p| p.parse::<u16>().ok())
+                        else {
+                            continue;
+                        };
+                        let host = match b["HostIp"].as_str() {
+                            Some("") | Some("0.0.0.0") | Some("::") | None => "127.0.0.1",
+                            Some(ip) => ip,
+                        };
+                        ports.push(json!({
+                            "container_port": container_port,
+                            "host": host,
+                            "port": port,
+                        }));
+                    }
+                }
+            }
+
+            let labels = &c["Config"]["Labels"];
+            let name = c["Name"]
+                .as_str()
+                .unwrap_or_default()
+                .trim_start_matches('/');
+            json!({
+                "service": labels["com.docker.compose.service"].as_str().unwrap_or(name),
+                "container": name,
+                "state": state_name,
+                "health": health,
+                "exit_code": exit_code,
+                "ports": ports,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(state: Value) -> Value {
+        json!({
+            "Name": "/app-web-1",
+            "State": state,
+            "Config": {"Labels": {"com.docker.compose.service": "web"}},
+            "NetworkSettings": {"Ports": {
+                "80/tcp": [{"HostIp": "0.0.0.0", "HostPort": "8080"}],
+                "53/udp": [{"HostIp": "", "HostPort": "5353"}]
+            }}
+        })
+    }
+
+    fn parse(states: Vec<Value>) -> Vec<Value> {
+        let containers: Vec<Value> = states.into_iter().map(container).collect();
+        parse_inspect(&Value::Array(containers).to_string())
+    }
+
+    #[test]
+    fn test_parse_inspect_states() {
+        let services = parse(vec![
+            json!({"Status": "running", "ExitCode": 0, "Health": {"Status": "healthy"}}),
+            json!({"Status": "exited", "ExitCode": 0}),
+            json!({"Status": "exited", "ExitCode": 137}),
+        ]);
+        assert_eq!(services[0]["service"], "web");
+        assert_eq!(services[0]["container"], "app-web-1");
+        assert_eq!(
+            (
+                services[0]["state"].as_str(),
+                services[0]["health"].as_str()
+            ),
+            (Some("running"), Some("healthy"))
+        );
+        assert_eq!(services[1]["state"], "completed");
+        assert_eq!(services[2]["state"], "failed");
+        assert_eq!(services[2]["exit_code"], 137);
+        // Only published TCP ports are probed
+        assert_eq!(
+            services[0]["ports"],
+            json!([{"container_port": "80/tcp", "host": "127.0.0.1", "port": 8080}])
+        );
+    }
+
+    #[test]
+    fn test_parse_inspect_podman_healthcheck() {
+        let services = parse(vec![
+            json!({"Status": "running", "Healthcheck": {"Status": "starting"}}),
+            json!({"Status": "running", "Health": {"Status": ""}}),
+        ]);
+        assert_eq!(services[0]["health"], "starting");
+        assert!(services[1]["health"].is_null());
+        assert!(parse_inspect("not json").is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+// modern-cli-mcp/src/tools/containerexec.rs
+//! `podman exec` / `docker exec` invocations.
+//!
+//! The command is split like a shell would split it but never run through
+//! one. The container name goes into argv right before that command, so a
+//! name starting with `-` would be read as an exec option (`--privileged`)
+//! and is refused.
+
+/// One exec as requested
+#[derive(Debug, Default)]
+pub struct Exec<'a> {
+    pub container: &'a str,
+    /// Command line, split with shell quoting rules
+    pub command: &'a str,
+    pub user: Option<&'a str>,
+    pub workdir: Option<&'a str>,
+    /// `KEY=VALUE` entries
+    pub env: &'a [String],
+}
+
+impl Exec<'_> {
+    /// Arguments after the runtime binary, starting with `exec`
+    pub fn args(&self) -> Result<Vec<String>, String> {
+        let container = self.container.trim();
+        if container.is_empty() {
+            return Err("container must not be empty".into());
+        }
+        if container.starts_with('-') {
+            return Err(format!(
+                "Invalid container name: {} (must not start with '-')",
+                container
+            ));
+        }
+        let argv = match shellwords::split(self.command) {
+            Ok(argv) if !argv.is_empty() => argv,
+            Ok(_) => return Err("command must not be empty".into()),
+            Err(e) => return Err(format!("Invalid command line: {}", e)),
+        };
+
+        let mut args: Vec<String> = vec!["exec".into()];
+        if let Some(user) = self.user {
+            args.extend(["--user".into(), user.to_string()]);
+        }
+        if let Some(workdir) = self.workdir {
+            args.extend(["--workdir".into(), workdir.to_string()]);
+        }
+        for var in self.env {
+            if var.split_once('=').is_none_or(|(key, _)| key.is_empty()) {
+                return Err(format!("env entries must be KEY=VALUE: {}", var));
+            }
+            args.extend(["--env".into(), var.clone()]);
+        }
+        args.push(container.to_string());
+        args.extend(argv);
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_args() {
+        let env = ["RUST_LOG=debug".to_string(), "EMPTY=".to_string()];
+        let args = Exec {
+            container: "web",
+            command: "sh -c 'echo \"$HOME\"'",
+            user: Some("1000:1000"),
+            workdir: Some("/srv/app"),
+            env: &env,
+        }
+        .args()
+        .unwrap();
+        assert_eq!(
+            args,
+            [
+                "exec",
+                "--user",
+                "1000:1000",
+                "--workdir",
+                "/srv/app",
+                "--env",
+                "RUST_LOG=debug",
+                "--env",
+                "EMPTY=",
+                "web",
+                "sh",
+                "-c",
+                "echo \"$HOME\"",
+            ]
+        );
+
+        let args = Exec {
+            container: "db",
+            command: "pg_isready",
+            ..Default::default()
+        }
+        .args()
+        .unwrap();
+        assert_eq!(args, ["exec", "db", "pg_isready"]);
+    }
+
+    #[test]
+    fn test_exec_rejects_bad_input() {
+        let err = |exec: Exec| exec.args().unwrap_err();
+        assert!(err(Exec {
+            container: "--privileged",
+            command: "id",
+            ..Default::default()
+        })
+        .contains("must not start with '-'"));
+        assert!(err(Exec {
+            container: " ",
+            command: "id",
+            ..Default::default()
+        })
+        .contains("container must not be empty"));
+        assert!(err(Exec {
+            container: "web",
+            command: "  ",
+            ..Default::default()
+        })
+        .contains("command must not be empty"));
+        assert!(err(Exec {
+            container: "web",
+            command: "echo 'unterminated",
+            ..Default::default()
+        })
+        .contains("Invalid command line"));
+        for bad in ["NOVALUE", "=value"] {
+            let env = [bad.to_string()];
+            assert!(err(Exec {
+                container: "web",
+                command: "id",
+                env: &env,
+                ..Default::default()
+            })
+            .contains("KEY=VALUE"));
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// modern-cli-mcp/src/tools/dirdiff.rs
+//! Comparing two directory trees file by file.
+//!
+//! Both trees are walked without following symlinks; files are matched by
+//! relative path and compared by size, then by content when sizes agree.
+//! Paths matched by `.agentignore` are left out of both sides.
+
+use crate::ignore::AgentIgnore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Upper bound on files walked per tree
+pub const MAX_FILES: usize = 20_000;
+
+/// A file present on one side only
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Entry {
+    pub path: String,
+    pub size: u64,
+}
+
+/// A file present on both sides with different content
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Modified {
+    pub path: String,
+    pub size_a: u64,
+    pub size_b: u64,
+    pub size_delta: i64,
+}
+
+/// Differences between two trees
+#[derive(Debug, Default, Serialize)]
+pub struct DirDiff {
+    pub added: Vec<Entry>,
+    pub removed: Vec<Entry>,
+    pub modified: Vec<Modified>,
+    pub unchanged: usize,
+    pub bytes_a: u64,
+    pub bytes_b: u64,
+    /// A tree had more than MAX_FILES files and was cut short
+    pub truncated: bool,
+}
+
+/// Relative path to size for every file (or symlink) under `root`
+fn walk(root: &Path, ignore: &AgentIgnore) -> (BTreeMap<String, u64>, bool) {
+    let mut files = BTreeMap::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if ignore.is_ignored(&path) {
+                continue;
+            }
+            let Ok(meta) = std::fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            if files.len() >= MAX_FILES {
+                return (files, true);
+            }
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string();
+            files.insert(rel, meta.len());
+        }
+    }
+    (files, false)
+}
+
+/// Whether two files (or symlinks) have the same content
+fn same_content(a: &Path, b: &Path) -> bool {
+    let link_a = std::fs::read_link(a).ok();
+    let link_b = std::fs::read_link(b).ok();
+    if link_a.is_some() || link_b.is_some() {
+        return link_a == link_b;
+    }
+    match (std::fs::read(a), std::fs::read(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Compare the trees under `a` and `b`
+pub fn compare(a: &Path, b: &Path, ignore: &AgentIgnore) -> DirDiff {
+    let (files_a, truncated_a) = walk(a, ignore);
+    let (files_b, truncated_b) = walk(b, ignore);
+    let mut diff = DirDiff {
+        bytes_a: files_a.values().sum(),
+        bytes_b: files_b.values().sum(),
+        truncated: truncated_a || truncated_b,
+        ..Default::default()
+    };
+
+    for (path, &size_a) in &files_a {
+        match files_b.get(path) {
+            None => diff.removed.push(Entry {
+                path: path.clone(),
+                size: size_a,
+            }),
+            Some(&size_b) => {
+                if size_a == size_b && same_content(&a.join(path), &b.join(path)) {
+                    diff.unchanged += 1;
+                } else {
+                    diff.modified.push(Modified {
+                        path: path.clone(),
+                        size_a,
+                        size_b,
+                        size_delta: size_b as i64 - size_a as i64,
+                    });
+                }
+            }
+        }
+    }
+    diff.added = files_b
+        .iter()
+        .filter(|(path, _)| !files_a.contains_key(*path))
+        .map(|(path, &size)| Entry {
+            path: path.clone(),
+            size,
+        })
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_trees() {
+        let a = tempfile::tempdir().unwrap();
+        let b = tempfile::tempdir().unwrap();
+        for (root, files) in [
+            (
+                a.path(),
+                &[
+                    ("same.txt", "x"),
+                    ("sub/edit.txt", "abc"),
+                    ("gone.txt", "bye"),
+                ][..],
+            ),
+            (
+                b.path(),
+                &[
+                    ("same.txt", "x"),
+                    ("sub/edit.txt", "abd!"),
+                    ("new.txt", "hi"),
+                ][..],
+            ),
+        ] {
+            std::fs::create_dir_all(root.join("sub")).unwrap();
+            for (name, content) in files {
+                std::fs::write(root.join(name), content).unwrap();
+            }
+        }
+
+        let diff = compare(a.path(), b.path(), &AgentIgnore::default());
+        assert_eq!(diff.added[0].path, "new.txt");
+        assert_eq!(diff.removed[0].path, "gone.txt");
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].size_delta, 1);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!((diff.bytes_a, diff.bytes_b), (7, 7));
+        assert!(!diff.truncated);
+    }
+}
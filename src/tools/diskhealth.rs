@@ -0,0 +1,250 @@
+// modern-cli-mcp/src/tools/diskhealth.rs
+//! Disk and filesystem health.
+//!
+//! `smartctl -j -a` reports are reduced to the handful of values that predict
+//! failure (overall verdict, reallocated/pending/uncorrectable sectors, NVMe
+//! media errors and wear), and each block-device mount is checked for being
+//! read-only, for recorded ext4 errors, and for nearly full space or inodes
+//! in duf's usage data.
+
+use serde_json::{json, Value};
+
+/// ATA attributes whose raw value should stay at zero on a healthy disk
+const ATA_CRITICAL: &[(u64, &str)] = &[
+    (5, "reallocated sectors"),
+    (187, "reported uncorrectable errors"),
+    (188, "command timeouts"),
+    (197, "pending sectors"),
+    (198, "offline uncorrectable sectors"),
+    (199, "UDMA CRC errors (check the cable)"),
+];
+
+/// Filesystems that are read-only by design
+const READ_ONLY_TYPES: &[&str] = &["squashfs", "iso9660", "erofs", "udf", "cramfs"];
+
+/// Space or inode use at which a mount is flagged
+pub const FULL_PERCENT: f64 = 90.0;
+
+/// Key values and warnings from one `smartctl -j -a` report
+pub fn summarize_smart(report: &Value) -> Value {
+    let mut warnings: Vec<String> = vec![];
+    let passed = report["smart_status"]["passed"].as_bool();
+    if passed == Some(false) {
+        warnings.push("SMART overall health self-assessment FAILED".into());
+    }
+
+    let mut attributes = serde_json::Map::new();
+    for attr in report["ata_smart_attributes"]["table"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        let id = attr["id"].as_u64().unwrap_or(0);
+        let raw = attr["raw"]["value"].as_u64().unwrap_or(0);
+        let name = attr["name"].as_str().unwrap_or("");
+        if let Some((_, what)) = ATA_CRITICAL.iter().find(|(i, _)| *i == id) {
+            attributes.insert(name.to_string(), json!(raw));
+            if raw > 0 {
+                warnings.push(format!("{} {}", raw, what));
+            }
+        }
+        if let Some(when) = attr["when_failed"].as_str().filter(|w| !w.is_empty()) {
+            warnings.push(format!("attribute {} failed ({})", name, when));
+        }
+    }
+
+    let nvme = &report["nvme_smart_health_information_log"];
+    if nvme.is_object() {
+        for key in [
+            "critical_warning",
+            "percentage_used",
+            "available_spare",
+            "media_errors",
+            "num_err_log_entries",
+            "unsafe_shutdowns",
+        ] {
+            if !nvme[key].is_null() {
+                attributes.insert(key.to_string(), nvme[key].clone());
+            }
+        }
+        let get = |key: &str| nvme[key].as_u64().unwrap_or(0);
+        if get("critical_warning") != 0 {
+            warnings.push(format!(
+                "NVMe critical warning 0x{:x}",
+                get("critical_warning")
+            ));
+        }
+        if get("media_errors") > 0 {
+            warnings.push(format!("{} media errors", get("media_errors")));
+        }
+        if get("percentage_used") >= 90 {
+            warnings.push(format!(
+                "{}% of rated endurance used",
+                get("percentage_used")
+            ));
+        }
+        if nvme["available_spare"].is_u64()
+            && get("available_spare") <= get("available_spare_threshold")
+        {
+            warnings.push("available spare at or below threshold".into());
+        }
+    }
+
+    let temperature = report["temperature"]["current"]
+        .as_u64()
+        .or(nvme["temperature"].as_u64());
+    json!({
+        "device": report["device"]["name"],
+        "model": report["model_name"],
+        "serial": report["serial_number"],
+        "protocol": report["device"]["protocol"],
+        "passed": passed,
+        "temperature_c": temperature,
+        "power_on_hours": report["power_on_time"]["hours"],
+        "attributes": attributes,
+        "warnings": warnings,
+    })
+}
+
+/// duf's record for a mount point, if any
+fn usage_for<'a>(usage: &'a [Value], target: &str) -> Option<&'a Value> {
+    usage
+        .iter()
+        .find(|u| u["mount_point"].as_str() == Some(target))
+}
+
+fn percent(used: &Value, total: &Value) -> Option<f64> {
+    let (used, total) = (used.as_f64()?, total.as_f64()?);
+    (total > 0.0).then(|| (used / total * 1000.0).round() / 10.0)
+}
+
+/// Health of every block-device mount in `mounts` (`/proc/self/mounts`
+/// format). `errors` looks up the ext4 error count for a device name.
+pub fn mount_health(
+    mounts: &str,
+    usage: &[Value],
+    errors: impl Fn(&str) -> Option<u64>,
+) -> Vec<Value> {
+    let mut seen = std::collections::HashSet::new();
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (source, target, fstype, options) = (
+                *fields.first()?,
+                *fields.get(1)?,
+                *fields.get(2)?,
+                *fields.get(3)?,
+            );
+            if !source.starts_with("/dev/") || !seen.insert(target.to_string()) {
+                return None;
+            }
+            // Spaces in mount points are octal-escaped
+            let target = target.replace("\\040", " ");
+            let options: Vec<&str> = options.split(',').collect();
+            let read_only = options.contains(&"ro");
+            let mut warnings: Vec<String> = vec![];
+            if read_only && !READ_ONLY_TYPES.contains(&fstype) {
+                warnings.push(
+                    "mounted read-only; if unexpected, the kernel may have remounted it after errors"
+                        .to_string(),
+                );
+            }
+            let device = source.rsplit('/').next().unwrap_or(source);
+            let error_count = if fstype == "ext4" {
+                errors(device)
+            } else {
+                None
+            };
+            if let Some(n) = error_count.filter(|n| *n > 0) {
+                warnings.push(format!("{} filesystem errors recorded", n));
+            }
+            let usage = usage_for(usage, &target);
+            let use_percent = usage.and_then(|u| percent(&u["used"], &u["total"]));
+            let inode_percent = usage.and_then(|u| percent(&u["inodes_used"], &u["inodes"]));
+            for (what, value) in [("space", use_percent), ("inodes", inode_percent)] {
+                if let Some(p) = value.filter(|p| *p >= FULL_PERCENT) {
+                    warnings.push(format!("{} {}% used", what, p));
+                }
+            }
+            Some(json!({
+                "target": target,
+                "source": source,
+                "fstype": fstype,
+                "read_only": read_only,
+                "options": options,
+                "fs_errors": error_count,
+                "use_percent": use_percent,
+                "inode_percent": inode_percent,
+                "warnings": warnings,
+            }))
+        })
+        .collect()
+}
+
+/// ext4's running error count for a device, from sysfs
+pub fn ext4_errors(device: &str) -> Option<u64> {
+    std::fs::read_to_string(format!("/sys/fs/ext4/{}/errors_count", device))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_smart() {
+        let ata = json!({
+            "device": {"name": "/dev/sda", "protocol": "ATA"},
+            "model_name": "WDC WD40EFRX",
+            "smart_status": {"passed": true},
+            "temperature": {"current": 38},
+            "ata_smart_attributes": {"table": [
+                {"id": 5, "name": "Reallocated_Sector_Ct", "when_failed": "", "raw": {"value": 8}},
+                {"id": 9, "name": "Power_On_Hours", "when_failed": "", "raw": {"value": 30000}},
+                {"id": 197, "name": "Current_Pending_Sector", "when_failed": "", "raw": {"value": 0}}
+            ]}
+        });
+        let summary = summarize_smart(&ata);
+        assert_eq!(summary["passed"], true);
+        assert_eq!(summary["temperature_c"], 38);
+        assert_eq!(summary["attributes"]["Reallocated_Sector_Ct"], 8);
+        assert_eq!(summary["warnings"], json!(["8 reallocated sectors"]));
+
+        let nvme = json!({
+            "device": {"name": "/dev/nvme0", "protocol": "NVMe"},
+            "smart_status": {"passed": false},
+            "nvme_smart_health_information_log": {
+                "critical_warning": 0, "temperature": 41, "available_spare": 5,
+                "available_spare_threshold": 10, "percentage_used": 3, "media_errors": 2
+            }
+        });
+        let warnings = summarize_smart(&nvme)["warnings"].clone();
+        assert_eq!(warnings.as_array().unwrap().len(), 3);
+        assert_eq!(summarize_smart(&nvme)["temperature_c"], 41);
+    }
+
+    #[test]
+    fn test_mount_health() {
+        let mounts = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+proc /proc proc rw,nosuid 0 0
+/dev/sdb1 /mnt/my\\040data ext4 ro,relatime 0 0
+/dev/sda1 / ext4 rw,relatime 0 0
+";
+        let usage = [json!({
+            "mount_point": "/", "total": 1000, "used": 950, "inodes": 100, "inodes_used": 10
+        })];
+        let health = mount_health(mounts, &usage, |dev| (dev == "sdb1").then_some(3));
+        assert_eq!(health.len(), 2);
+        assert_eq!(health[0]["use_percent"], 95.0);
+        assert_eq!(health[0]["warnings"], json!(["space 95% used"]));
+        assert_eq!(health[1]["target"], "/mnt/my data");
+        assert_eq!(health[1]["read_only"], true);
+        assert_eq!(health[1]["fs_errors"], 3);
+        assert_eq!(health[1]["warnings"].as_array().unwrap().len(), 2);
+    }
+}
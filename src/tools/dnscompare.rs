@@ -0,0 +1,178 @@
+// modern-cli-mcp/src/tools/dnscompare.rs
+//! Comparing DNS answers across resolvers.
+//!
+//! After a DNS change, resolvers disagree until cached records expire. Each
+//! resolver's answer set is normalized and compared against the
+//! authoritative answer (or, without one, the most common answer), and the
+//! TTLs show how long a stale resolver may keep its old records.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+/// One record as answered by a resolver
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
+}
+
+/// What one resolver answered
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolverAnswer {
+    /// system, a resolver address, or authoritative
+    pub resolver: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    pub records: Vec<Record>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Lowercase and drop the trailing root dot, so `A.example.` == `a.example`
+fn normalize(value: &str) -> String {
+    value.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Answers of `record_type` from `doggo --json` output. CNAMEs followed
+/// on the way to the answer are skipped unless CNAME was asked for.
+pub fn parse_doggo_answers(output: &str, record_type: &str) -> Result<Vec<Record>, String> {
+    let parsed: Value =
+        serde_json::from_str(output).map_err(|e| format!("Unexpected doggo output: {}", e))?;
+    let responses = match &parsed {
+        Value::Array(items) => items.clone(),
+        other => other["responses"].as_array().cloned().unwrap_or_default(),
+    };
+    let mut records: Vec<Record> = responses
+        .iter()
+        .flat_map(|r| r["answers"].as_array().cloned().unwrap_or_default())
+        .filter(|a| {
+            a["type"]
+                .as_str()
+                .is_none_or(|t| t.eq_ignore_ascii_case(record_type))
+        })
+        .filter_map(|a| {
+            let value = a["address"].as_str().or(a["data"].as_str())?;
+            let ttl = match &a["ttl"] {
+                Value::Number(n) => n.as_u64(),
+                Value::String(s) => s.trim_end_matches('s').parse().ok(),
+                _ => None,
+            };
+            Some(Record {
+                value: normalize(value),
+                ttl,
+            })
+        })
+        .collect();
+    records.sort_by(|a, b| a.value.cmp(&b.value));
+    records.dedup_by(|a, b| a.value == b.value);
+    Ok(records)
+}
+
+fn value_set(answer: &ResolverAnswer) -> BTreeSet<&str> {
+    answer.records.iter().map(|r| r.value.as_str()).collect()
+}
+
+/// Agreement report: the reference answer set, and for each resolver that
+/// differs, what it is missing and what it has extra
+pub fn compare(answers: &[ResolverAnswer]) -> Value {
+    let ok: Vec<&ResolverAnswer> = answers.iter().filter(|a| a.error.is_none()).collect();
+
+    // Reference: authoritative if it answered, else the most common set
+    let (reference, reference_from) = match ok.iter().find(|a| a.resolver == "authoritative") {
+        Some(auth) => (value_set(auth), "authoritative"),
+        None => {
+            let sets: Vec<BTreeSet<&str>> = ok.iter().map(|a| value_set(a)).collect();
+            let common = sets
+                .iter()
+                .max_by_key(|s| sets.iter().filter(|t| t == s).count())
+                .cloned()
+                .unwrap_or_default();
+            (common, "consensus")
+        }
+    };
+
+    let differences: Vec<Value> = ok
+        .iter()
+        .filter(|a| value_set(a) != reference)
+        .map(|a| {
+            let set = value_set(a);
+            json!({
+                "resolver": a.resolver,
+                "missing": reference.difference(&set).collect::<Vec<_>>(),
+                "extra": set.difference(&reference).collect::<Vec<_>>(),
+                // Worst case until this resolver's cached answer expires
+                "expires_within": a.records.iter().filter_map(|r| r.ttl).max(),
+            })
+        })
+        .collect();
+
+    json!({
+        "consistent": differences.is_empty() && ok.len() == answers.len(),
+        "reference": reference_from,
+        "expected": reference,
+        "differences": differences,
+        "failed": answers.iter().filter(|a| a.error.is_some()).map(|a| &a.resolver).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(resolver: &str, values: &[(&str, u64)]) -> ResolverAnswer {
+        ResolverAnswer {
+            resolver: resolver.into(),
+            server: None,
+            records: values
+                .iter()
+                .map(|(v, ttl)| Record {
+                    value: v.to_string(),
+                    ttl: Some(*ttl),
+                })
+                .collect(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_doggo_answers() {
+        let output = r#"{"responses":[{"answers":[
+            {"name":"www.example.com.","type":"CNAME","ttl":"300s","address":"example.com."},
+            {"name":"example.com.","type":"A","ttl":"3600s","address":"93.184.216.34"},
+            {"name":"example.com.","type":"A","ttl":"3600s","address":"93.184.216.34"}
+        ]}]}"#;
+        let records = parse_doggo_answers(output, "A").unwrap();
+        assert_eq!(
+            records,
+            [Record {
+                value: "93.184.216.34".into(),
+                ttl: Some(3600)
+            }]
+        );
+        assert!(parse_doggo_answers("not json", "A").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let answers = [
+            answer("system", &[("1.1.1.1", 300)]),
+            answer("8.8.8.8", &[("2.2.2.2", 120)]),
+            answer("authoritative", &[("2.2.2.2", 3600)]),
+        ];
+        let report = compare(&answers);
+        assert_eq!(report["consistent"], false);
+        assert_eq!(report["reference"], "authoritative");
+        assert_eq!(report["differences"][0]["resolver"], "system");
+        assert_eq!(report["differences"][0]["missing"], json!(["2.2.2.2"]));
+        assert_eq!(report["differences"][0]["expires_within"], 300);
+
+        let report = compare(&answers[..2]);
+        assert_eq!(report["reference"], "consensus");
+        assert_eq!(
+            compare(&[answers[1].clone(), answers[2].clone()])["consistent"],
+            true
+        );
+    }
+}
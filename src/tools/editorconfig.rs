@@ -0,0 +1,247 @@
+// modern-cli-mcp/src/tools/editorconfig.rs
+//! Applying `.editorconfig` whitespace rules.
+//!
+//! Properties for a file come from every `.editorconfig` between it and the
+//! nearest `root = true`, farthest first so closer files and later sections
+//! win. Section globs without a `/` match the file name at any depth, as the
+//! EditorConfig spec requires. Only whitespace is rewritten: line endings,
+//! trailing whitespace, leading indentation and the final newline; `charset`
+//! is reported but never converted.
+
+use globset::GlobBuilder;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Properties that apply to `path`, keys and values lowercased
+pub fn properties_for(path: &Path) -> BTreeMap<String, String> {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut configs = vec![];
+    for dir in absolute.ancestors().skip(1) {
+        let Ok(text) = std::fs::read_to_string(dir.join(".editorconfig")) else {
+            continue;
+        };
+        let (is_root, sections) = parse(&text);
+        configs.push((dir.to_path_buf(), sections));
+        if is_root {
+            break;
+        }
+    }
+
+    let mut props = BTreeMap::new();
+    for (dir, sections) in configs.iter().rev() {
+        let Ok(rel) = absolute.strip_prefix(dir) else {
+            continue;
+        };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        for (pattern, section) in sections {
+            if matches(pattern, &rel) {
+                props.extend(section.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+        }
+    }
+    props
+}
+
+type Section = (String, Vec<(String, String)>);
+
+/// `root` flag and `[glob]` sections of one `.editorconfig`
+fn parse(text: &str) -> (bool, Vec<Section>) {
+    let mut root = false;
+    let mut sections: Vec<Section> = vec![];
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push((glob.to_string(), vec![]));
+        } else if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim().to_ascii_lowercase();
+            match sections.last_mut() {
+                Some((_, props)) => props.push((key, value)),
+                None if key == "root" => root = value == "true",
+                None => {}
+            }
+        }
+    }
+    (root, sections)
+}
+
+/// EditorConfig glob match against a `/`-separated relative path
+fn matches(pattern: &str, rel: &str) -> bool {
+    let pattern = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if pattern.contains('/') => pattern.to_string(),
+        None => format!("**/{}", pattern),
+    };
+    GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .build()
+        .map(|g| g.compile_matcher().is_match(rel))
+        .unwrap_or(false)
+}
+
+/// Width of leading whitespace, with tabs advancing to the next tab stop
+fn indent_width(indent: &str, tab_width: usize) -> usize {
+    indent.chars().fold(0, |col, c| match c {
+        '\t' => col + tab_width - col % tab_width,
+        _ => col + 1,
+    })
+}
+
+/// `text` rewritten to `props`, and what changed
+pub fn normalize(text: &str, props: &BTreeMap<String, String>) -> (String, Value) {
+    let get = |key: &str| props.get(key).map(String::as_str);
+    let indent_size: Option<usize> = get("indent_size").and_then(|s| s.parse().ok());
+    let tab_width = get("tab_width")
+        .and_then(|s| s.parse().ok())
+        .or(indent_size)
+        .unwrap_or(4)
+        .max(1);
+    let indent_size = indent_size.unwrap_or(tab_width).max(1);
+
+    let had_final_newline = text.ends_with('\n') || text.ends_with('\r');
+    let eol = match get("end_of_line") {
+        Some("crlf") => "\r\n",
+        Some("cr") => "\r",
+        Some("lf") => "\n",
+        // Keep the file's own line ending
+        _ if text.contains("\r\n") => "\r\n",
+        _ if !text.contains('\n') && text.contains('\r') => "\r",
+        _ => "\n",
+    };
+    let line_endings_changed = get("end_of_line").is_some() && {
+        let lf = text.matches('\n').count();
+        let crlf = text.matches("\r\n").count();
+        let cr = text.matches('\r').count();
+        match eol {
+            "\r\n" => lf != crlf || cr != crlf,
+            "\r" => lf > 0,
+            _ => cr > 0,
+        }
+    };
+
+    let body = text.replace("\r\n", "\n").replace('\r', "\n");
+    let body = body.strip_suffix('\n').unwrap_or(&body);
+    let mut trimmed = 0;
+    let mut reindented = 0;
+    let lines: Vec<String> = body
+        .split('\n')
+        .map(|line| {
+            let mut line = line.to_string();
+            if get("trim_trailing_whitespace") == Some("true") {
+                let kept = line.trim_end_matches([' ', '\t']).len();
+                if kept != line.len() {
+                    line.truncate(kept);
+                    trimmed += 1;
+                }
+            }
+            let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+            let (indent, rest) = line.split_at(indent_len);
+            let width = indent_width(indent, tab_width);
+            let new_indent = match get("indent_style") {
+                Some("space") if indent.contains('\t') => " ".repeat(width),
+                Some("tab") if indent.contains(' ') => format!(
+                    "{}{}",
+                    "\t".repeat(width / tab_width),
+                    " ".repeat(width % tab_width)
+                ),
+                _ => return line,
+            };
+            // Leave alignment spaces after tabs alone when they don't add up to a level
+            if get("indent_style") == Some("tab") && width < indent_size {
+                return line;
+            }
+            reindented += 1;
+            format!("{}{}", new_indent, rest)
+        })
+        .collect();
+
+    let mut out = lines.join(eol);
+    let final_newline = match get("insert_final_newline") {
+        Some("true") => !text.is_empty(),
+        Some("false") => false,
+        _ => had_final_newline,
+    };
+    if final_newline {
+        out.push_str(eol);
+    }
+
+    let changes = json!({
+        "line_endings": line_endings_changed,
+        "trailing_whitespace_lines": trimmed,
+        "reindented_lines": reindented,
+        "final_newline": match (had_final_newline, final_newline) {
+            (false, true) => Some("added"),
+            (true, false) => Some("removed"),
+            _ => None,
+        },
+    });
+    (out, changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn props(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize() {
+        let p = props(&[
+            ("indent_style", "space"),
+            ("indent_size", "4"),
+            ("end_of_line", "lf"),
+            ("trim_trailing_whitespace", "true"),
+            ("insert_final_newline", "true"),
+        ]);
+        let (out, changes) = normalize("fn main() {  \r\n\tlet x = 1;\r\n}", &p);
+        assert_eq!(out, "fn main() {\n    let x = 1;\n}\n");
+        assert_eq!(changes["line_endings"], true);
+        assert_eq!(changes["trailing_whitespace_lines"], 1);
+        assert_eq!(changes["reindented_lines"], 1);
+        assert_eq!(changes["final_newline"], "added");
+
+        let p = props(&[("indent_style", "tab"), ("indent_size", "2")]);
+        let (out, _) = normalize("a:\n  b:\n    c: 1\n", &p);
+        assert_eq!(out, "a:\n\tb:\n\t\tc: 1\n");
+
+        let (out, changes) = normalize("unchanged\r\n", &BTreeMap::new());
+        assert_eq!(out, "unchanged\r\n");
+        assert_eq!(changes["line_endings"], false);
+    }
+
+    #[test]
+    fn test_properties_for() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".editorconfig"),
+            "root = true\n\n[*]\nindent_style = space\nindent_size = 4\n\n[*.{yml,yaml}]\nindent_size = 2\n\n[Makefile]\nindent_style = tab\n",
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(
+            dir.path().join("sub/.editorconfig"),
+            "[*.yml]\nINDENT_SIZE = 3\n",
+        )
+        .unwrap();
+
+        let p = properties_for(&dir.path().join("sub/ci.yml"));
+        assert_eq!(p["indent_size"], "3");
+        assert_eq!(p["indent_style"], "space");
+        assert_eq!(
+            properties_for(&dir.path().join("a.yaml"))["indent_size"],
+            "2"
+        );
+        assert_eq!(
+            properties_for(&dir.path().join("sub/Makefile"))["indent_style"],
+            "tab"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+// modern-cli-mcp/src/tools/everywhere.rs
+//! One query across code, saved state and the forge.
+//!
+//! Code hits come from ripgrep's JSON stream, context entries and tasks are
+//! matched case-insensitively in the state database, and issues, pull and
+//! merge requests from gh and glab are reduced to one shape so results from
+//! either forge read the same. Which forge to ask is taken from the `origin`
+//! remote unless the caller names a repo or project.
+
+use crate::state::{ContextEntry, Task};
+use serde_json::{json, Value};
+
+/// Context values are cut to this many characters around the match
+const EXCERPT_CHARS: usize = 160;
+
+/// `github` or `gitlab` for a remote URL
+pub fn forge_for_remote(url: &str) -> Option<&'static str> {
+    let url = url.to_ascii_lowercase();
+    if url.contains("github") {
+        Some("github")
+    } else if url.contains("gitlab") {
+        Some("gitlab")
+    } else {
+        None
+    }
+}
+
+/// Matches from `rg --json`: total match count, distinct files and the first
+/// `limit` matches
+pub fn code_matches(output: &str, limit: usize) -> Value {
+    let mut total = 0;
+    let mut files: Vec<String> = vec![];
+    let mut results = vec![];
+    for entry in output
+        .lines()
+        .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+        .filter(|e| e["type"] == "match")
+    {
+        total += 1;
+        let data = &entry["data"];
+        let path = data["path"]["text"].as_str().unwrap_or("").to_string();
+        if !files.contains(&path) {
+            files.push(path.clone());
+        }
+        if results.len() < limit {
+            results.push(json!({
+                "path": path,
+                "line": data["line_number"],
+                "text": data["lines"]["text"].as_str().unwrap_or("").trim(),
+            }));
+        }
+    }
+    json!({ "count": total, "files": files.len(), "results": results })
+}
+
+/// Up to `EXCERPT_CHARS` characters of `text` centered on `query`
+fn excerpt(text: &str, query: &str) -> String {
+    let lower = text.to_lowercase();
+    let at = lower.find(&query.to_lowercase()).unwrap_or(0);
+    let chars: Vec<char> = text.chars().collect();
+    let center = text[..at.min(text.len())].chars().count();
+    let start = center.saturating_sub(EXCERPT_CHARS / 2);
+    let end = (start + EXCERPT_CHARS).min(chars.len());
+    let mut out: String = chars[start..end].iter().collect();
+    if start > 0 {
+        out.insert(0, '…');
+    }
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+/// Context entries whose key or value contains `query`
+pub fn context_matches(entries: &[ContextEntry], query: &str) -> Vec<Value> {
+    let needle = query.to_lowercase();
+    entries
+        .iter()
+        .filter(|e| {
+            e.key.to_lowercase().contains(&needle) || e.value.to_lowercase().contains(&needle)
+        })
+        .map(|e| {
+            json!({
+                "key": e.key,
+                "scope": e.scope.to_string(),
+                "excerpt": excerpt(&e.value, query),
+            })
+        })
+        .collect()
+}
+
+/// Tasks whose content contains `query`
+pub fn task_matches(tasks: &[Task], query: &str) -> Vec<Value> {
+    let needle = query.to_lowercase();
+    tasks
+        .iter()
+        .filter(|t| t.content.to_lowercase().contains(&needle))
+        .map(|t| json!({ "id": t.id, "content": t.content, "status": t.status.to_string() }))
+        .collect()
+}
+
+/// gh (`number`, `url`, `updatedAt`) or glab (`iid`, `web_url`,
+/// `updated_at`) list JSON as `{kind, number, title, state, url, updated}`
+pub fn forge_items(output: &str, kind: &str) -> Vec<Value> {
+    let items: Vec<Value> = serde_json::from_str(output.trim()).unwrap_or_default();
+    items
+        .iter()
+        .map(|i| {
+            json!({
+                "kind": kind,
+                "number": if i["number"].is_null() { &i["iid"] } else { &i["number"] },
+                "title": i["title"],
+                "state": i["state"].as_str().map(str::to_ascii_lowercase),
+                "url": if i["url"].is_null() { &i["web_url"] } else { &i["url"] },
+                "updated": if i["updatedAt"].is_null() { &i["updated_at"] } else { &i["updatedAt"] },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{ContextScope, TaskStatus};
+
+    #[test]
+    fn test_code_matches() {
+        let output = r#"{"type":"begin","data":{"path":{"text":"src/a.rs"}}}
+{"type":"match","data":{"path":{"text":"src/a.rs"},"lines":{"text":"  // retry budget\n"},"line_number":4}}
+{"type":"match","data":{"path":{"text":"src/a.rs"},"lines":{"text":"let budget = 3;\n"},"line_number":9}}
+{"type":"match","data":{"path":{"text":"src/b.rs"},"lines":{"text":"budget()\n"},"line_number":1}}"#;
+        let result = code_matches(output, 2);
+        assert_eq!(result["count"], 3);
+        assert_eq!(result["files"], 2);
+        assert_eq!(result["results"][0]["text"], "// retry budget");
+        assert_eq!(result["results"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_state_matches() {
+        let entries = vec![
+            ContextEntry {
+                key: "deploy_notes".into(),
+                value: format!("{} Retry budget is 3 per job.", "x".repeat(200)),
+                scope: ContextScope::Project,
+            },
+            ContextEntry {
+                key: "other".into(),
+                value: "nothing here".into(),
+                scope: ContextScope::Session,
+            },
+        ];
+        let found = context_matches(&entries, "retry BUDGET");
+        assert_eq!(found.len(), 1);
+        let excerpt = found[0]["excerpt"].as_str().unwrap();
+        assert!(excerpt.starts_with('…') && excerpt.contains("Retry budget"));
+
+        let tasks = vec![Task {
+            id: 7,
+            content: "Lower the retry budget".into(),
+            status: TaskStatus::Pending,
+            created_at: 0,
+            updated_at: 0,
+        }];
+        assert_eq!(task_matches(&tasks, "budget")[0]["id"], 7);
+        assert!(task_matches(&tasks, "deploy").is_empty());
+    }
+
+    #[test]
+    fn test_forge_items() {
+        let gh = r#"[{"number":12,"title":"Retry budget","state":"OPEN","url":"https://github.com/o/r/issues/12","updatedAt":"2024-05-01T00:00:00Z"}]"#;
+        let glab = r#"[{"iid":3,"title":"Budget","state":"merged","web_url":"https://gitlab.com/g/p/-/merge_requests/3","updated_at":"2024-05-02T00:00:00Z"}]"#;
+        let issue = &forge_items(gh, "issue")[0];
+        assert_eq!(issue["number"], 12);
+        assert_eq!(issue["state"], "open");
+        let mr = &forge_items(glab, "merge_request")[0];
+        assert_eq!(mr["number"], 3);
+        assert_eq!(mr["url"], "https://gitlab.com/g/p/-/merge_requests/3");
+        assert_eq!(
+            forge_for_remote("git@gitlab.example.com:g/p.git"),
+            Some("gitlab")
+        );
+        assert!(forge_items("not json", "issue").is_empty());
+    }
+}
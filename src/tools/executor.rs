@@ -1,4 +1,8 @@
 // modern-cli-mcp/src/tools/executor.rs
+use super::interactive::{self, Interactivity};
+use super::proctree::{self, TreeGuard};
+use crate::workdir;
+use base64::Engine;
 use serde::Serialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -16,6 +20,63 @@ pub struct ExecOptions<'a> {
     pub timeout_secs: Option<u64>,
     pub env: Option<&'a HashMap<String, String>>,
     pub clear_env: bool,
+    /// Run under a pseudo-terminal (via util-linux `script`)
+    pub pty: bool,
+}
+
+/// Where a command's stdin comes from
+#[derive(Debug, Clone, Copy)]
+pub enum StdinSource<'a> {
+    /// Inline data, streamed in chunks
+    Data(&'a str),
+    /// File handed to the child directly, never loaded into memory
+    File(&'a std::path::Path),
+}
+
+/// Chunk size for streaming inline stdin data
+const STDIN_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Lines in a file, counted like `str::lines` one buffer at a time, so an
+/// `input_path` streamed as stdin is never loaded whole
+pub fn count_file_lines(path: &std::path::Path) -> std::io::Result<usize> {
+    use std::io::BufRead;
+    let file = std::fs::File::open(workdir::resolve(path))?;
+    let mut reader = std::io::BufReader::with_capacity(STDIN_CHUNK_SIZE, file);
+    let mut count = 0;
+    let mut last = None;
+    loop {
+        let buf = reader.fill_buf()?;
+        let Some(&end) = buf.last() else {
+            break;
+        };
+        count += buf.iter().filter(|&&b| b == b'\n').count();
+        last = Some(end);
+        let len = buf.len();
+        reader.consume(len);
+    }
+    // An unterminated last line still counts
+    Ok(count + usize::from(last.is_some_and(|b| b != b'\n')))
+}
+
+/// Environment that keeps common CLIs from prompting or paging
+const NON_INTERACTIVE_ENV: &[(&str, &str)] = &[
+    ("GIT_TERMINAL_PROMPT", "0"),
+    ("GH_PROMPT_DISABLED", "1"),
+    ("GIT_PAGER", "cat"),
+    ("PAGER", "cat"),
+];
+
+/// Fail fast on invocations that would wait for terminal input.
+/// Returns whether the command should run under a pty.
+fn check_interactive(cmd: &str, args: &[&str]) -> Result<bool, String> {
+    match interactive::classify(cmd, args) {
+        Interactivity::Batch => Ok(false),
+        Interactivity::NeedsPty => Ok(true),
+        Interactivity::Interactive(guidance) => Err(format!(
+            "Command '{}' requires an interactive terminal and would hang: {}",
+            cmd, guidance
+        )),
+    }
 }
 
 impl CommandExecutor {
@@ -51,17 +112,36 @@ impl CommandExecutor {
         args: &[&str],
         opts: ExecOptions<'_>,
     ) -> Result<CommandOutput, String> {
+        let use_pty = check_interactive(cmd, args)? || opts.pty;
+
         let cmd_path =
             which::which(cmd).map_err(|_| format!("Command '{}' not found in PATH", cmd))?;
 
-        let mut command = Command::new(&cmd_path);
+        let mut command = if use_pty {
+            let script = which::which("script").map_err(|_| {
+                format!(
+                    "Command '{}' needs a terminal but 'script' (util-linux) is not in PATH",
+                    cmd
+                )
+            })?;
+            let cmd_str = cmd_path.to_string_lossy();
+            let mut words: Vec<&str> = vec![&cmd_str];
+            words.extend_from_slice(args);
+            let mut command = Command::new(script);
+            command.args(["-qefc", &shellwords::join(&words), "/dev/null"]);
+            command
+        } else {
+            let mut command = Command::new(&cmd_path);
+            command.args(args);
+            command
+        };
         command
-            .args(args)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
+        proctree::isolate(&mut command);
 
-        if let Some(dir) = opts.working_dir {
+        if let Some(dir) = workdir::dir_for(opts.working_dir) {
             command.current_dir(dir);
         }
 
@@ -69,22 +149,26 @@ impl CommandExecutor {
             command.env_clear();
         }
 
+        command.envs(NON_INTERACTIVE_ENV.iter().copied());
+
         if let Some(env) = opts.env {
             for (k, v) in env {
                 command.env(k, v);
             }
         }
 
-        let output_future = command.output();
+        let child = command
+            .spawn()
+            .map_err(|e| format!("Failed to execute {}: {}", cmd, e))?;
+        let guard = TreeGuard::new(child.id());
+        let output_future = child.wait_with_output();
 
         let output = if let Some(timeout_secs) = opts.timeout_secs {
             match tokio::time::timeout(Duration::from_secs(timeout_secs), output_future).await {
                 Ok(result) => result.map_err(|e| format!("Failed to execute {}: {}", cmd, e))?,
                 Err(_) => {
-                    return Err(format!(
-                        "Command '{}' timed out after {} seconds",
-                        cmd, timeout_secs
-                    ))
+                    let killed = guard.kill();
+                    return Err(proctree::timeout_message(cmd, timeout_secs, &killed));
                 }
             }
         } else {
@@ -92,9 +176,13 @@ impl CommandExecutor {
                 .await
                 .map_err(|e| format!("Failed to execute {}: {}", cmd, e))?
         };
+        guard.disarm();
 
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let mut stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if use_pty {
+            stdout = stdout.replace("\r\n", "\n");
+        }
 
         Ok(CommandOutput {
             success: output.status.success(),
@@ -104,36 +192,156 @@ impl CommandExecutor {
         })
     }
 
+    /// Run a command that streams until stopped (`stern`, `kubectl logs -f`)
+    /// and collect its output for at most `secs` seconds. Returns what was
+    /// read and whether the window closed before the command exited; the
+    /// process tree is killed in that case, which is not an error.
+    pub async fn run_for(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        secs: u64,
+    ) -> Result<(CommandOutput, bool), String> {
+        use tokio::io::AsyncReadExt;
+
+        check_interactive(cmd, args)?;
+        let cmd_path =
+            which::which(cmd).map_err(|_| format!("Command '{}' not found in PATH", cmd))?;
+
+        let mut command = Command::new(&cmd_path);
+        command
+            .args(args)
+            .envs(NON_INTERACTIVE_ENV.iter().copied())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = workdir::current() {
+            command.current_dir(dir);
+        }
+        proctree::isolate(&mut command);
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("Failed to spawn {}: {}", cmd, e))?;
+        let guard = TreeGuard::new(child.id());
+        let (Some(mut out), Some(mut err)) = (child.stdout.take(), child.stderr.take()) else {
+            return Err(format!("Failed to open pipes for {}", cmd));
+        };
+
+        // Reads are cancel-safe, so everything received before the window
+        // closes stays in the buffers
+        let mut stdout = vec![];
+        let mut stderr = vec![];
+        let collect = async {
+            let (mut out_buf, mut err_buf) = ([0u8; 8192], [0u8; 8192]);
+            let (mut out_open, mut err_open) = (true, true);
+            while out_open || err_open {
+                tokio::select! {
+                    n = out.read(&mut out_buf), if out_open => match n {
+                        Ok(0) | Err(_) => out_open = false,
+                        Ok(n) => stdout.extend_from_slice(&out_buf[..n]),
+                    },
+                    n = err.read(&mut err_buf), if err_open => match n {
+                        Ok(0) | Err(_) => err_open = false,
+                        Ok(n) => stderr.extend_from_slice(&err_buf[..n]),
+                    },
+                }
+            }
+            child.wait().await
+        };
+        let status = match tokio::time::timeout(Duration::from_secs(secs), collect).await {
+            Ok(status) => {
+                guard.disarm();
+                Some(status.map_err(|e| format!("Failed to wait for {}: {}", cmd, e))?)
+            }
+            Err(_) => {
+                guard.kill();
+                None
+            }
+        };
+
+        Ok((
+            CommandOutput {
+                success: status.is_none_or(|s| s.success()),
+                exit_code: status.and_then(|s| s.code()),
+                stdout: String::from_utf8_lossy(&stdout).to_string(),
+                stderr: String::from_utf8_lossy(&stderr).to_string(),
+            },
+            status.is_none(),
+        ))
+    }
+
     pub async fn run_with_stdin(
         &self,
         cmd: &str,
         args: &[&str],
         stdin_data: &str,
+    ) -> Result<CommandOutput, String> {
+        self.run_with_stdin_source(cmd, args, StdinSource::Data(stdin_data))
+            .await
+    }
+
+    /// Run a command fed from inline data or a file.
+    ///
+    /// Inline data is written in chunks while stdout/stderr are drained
+    /// concurrently, so large inputs cannot deadlock on a full pipe.
+    pub async fn run_with_stdin_source(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        source: StdinSource<'_>,
     ) -> Result<CommandOutput, String> {
         use tokio::io::AsyncWriteExt;
 
+        // Input comes from stdin_data, so only editor/UI programs are a problem here
+        check_interactive(cmd, args)?;
+
         let cmd_path =
             which::which(cmd).map_err(|_| format!("Command '{}' not found in PATH", cmd))?;
 
-        let mut child = Command::new(&cmd_path)
+        let stdin = match source {
+            StdinSource::Data(_) => Stdio::piped(),
+            StdinSource::File(path) => std::fs::File::open(workdir::resolve(path))
+                .map(Stdio::from)
+                .map_err(|e| format!("Failed to open input file {}: {}", path.display(), e))?,
+        };
+
+        let mut command = Command::new(&cmd_path);
+        command
             .args(args)
-            .stdin(Stdio::piped())
+            .envs(NON_INTERACTIVE_ENV.iter().copied())
+            .stdin(stdin)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = workdir::current() {
+            command.current_dir(dir);
+        }
+        proctree::isolate(&mut command);
+        let mut child = command
             .spawn()
             .map_err(|e| format!("Failed to spawn {}: {}", cmd, e))?;
+        let guard = TreeGuard::new(child.id());
 
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(stdin_data.as_bytes())
-                .await
-                .map_err(|e| format!("Failed to write stdin: {}", e))?;
-        }
+        let pipe = child.stdin.take();
+        let write = async move {
+            if let (Some(mut pipe), StdinSource::Data(data)) = (pipe, source) {
+                for chunk in data.as_bytes().chunks(STDIN_CHUNK_SIZE) {
+                    pipe.write_all(chunk).await?;
+                }
+                pipe.shutdown().await?;
+            }
+            Ok::<(), std::io::Error>(())
+        };
 
-        let output = child
-            .wait_with_output()
-            .await
-            .map_err(|e| format!("Failed to wait for {}: {}", cmd, e))?;
+        let (written, output) = tokio::join!(write, child.wait_with_output());
+        let output = output.map_err(|e| format!("Failed to wait for {}: {}", cmd, e))?;
+        guard.disarm();
+
+        // A child that exits before reading all input (e.g. `head`) is not an error
+        if let Err(e) = written {
+            if e.kind() != std::io::ErrorKind::BrokenPipe {
+                return Err(format!("Failed to write stdin: {}", e));
+            }
+        }
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -145,6 +353,86 @@ impl CommandExecutor {
             stderr,
         })
     }
+
+    /// Run a command fed from inline data, handing each stderr line to
+    /// `on_line` as it arrives. For tools that report progress per item on
+    /// stderr (`tar -v`); the lines are still returned in `stderr`.
+    pub async fn run_with_stderr_lines(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        stdin_data: &str,
+        mut on_line: impl FnMut(&str) + Send,
+    ) -> Result<CommandOutput, String> {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        check_interactive(cmd, args)?;
+        let cmd_path =
+            which::which(cmd).map_err(|_| format!("Command '{}' not found in PATH", cmd))?;
+
+        let mut command = Command::new(&cmd_path);
+        command
+            .args(args)
+            .envs(NON_INTERACTIVE_ENV.iter().copied())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = workdir::current() {
+            command.current_dir(dir);
+        }
+        proctree::isolate(&mut command);
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("Failed to spawn {}: {}", cmd, e))?;
+        let guard = TreeGuard::new(child.id());
+
+        let (Some(mut pipe), Some(mut out), Some(err)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            return Err(format!("Failed to open pipes for {}", cmd));
+        };
+        let write = async move {
+            for chunk in stdin_data.as_bytes().chunks(STDIN_CHUNK_SIZE) {
+                pipe.write_all(chunk).await?;
+            }
+            pipe.shutdown().await
+        };
+        let read_out = async move {
+            let mut buf = vec![];
+            out.read_to_end(&mut buf).await.map(|_| buf)
+        };
+        let read_err = async {
+            let mut stderr = String::new();
+            let mut lines = BufReader::new(err).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                on_line(&line);
+                stderr.push_str(&line);
+                stderr.push('\n');
+            }
+            stderr
+        };
+
+        let (written, stdout, stderr) = tokio::join!(write, read_out, read_err);
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| format!("Failed to wait for {}: {}", cmd, e))?;
+        guard.disarm();
+
+        if let Err(e) = written {
+            if e.kind() != std::io::ErrorKind::BrokenPipe {
+                return Err(format!("Failed to write stdin: {}", e));
+            }
+        }
+        let stdout = stdout.map_err(|e| format!("Failed to read {} output: {}", cmd, e))?;
+
+        Ok(CommandOutput {
+            success: status.success(),
+            exit_code: status.code(),
+            stdout: String::from_utf8_lossy(&stdout).to_string(),
+            stderr,
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -228,19 +516,26 @@ pub struct FileEntry {
     pub git_status: Option<String>,
 }
 
-/// Search match for JSON output (reserved for future enhanced parsing)
-#[allow(dead_code)]
+/// Single ripgrep match line with surrounding context
 #[derive(Debug, Serialize)]
 pub struct SearchMatch {
-    pub path: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub line_number: Option<u32>,
+    pub line: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub column: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub line_content: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub match_text: Option<String>,
+    pub column: Option<u64>,
+    pub match_text: String,
+    pub line_text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+}
+
+/// Matches grouped by file
+#[derive(Debug, Serialize)]
+pub struct SearchFileMatches {
+    pub file: String,
+    pub count: usize,
+    pub matches: Vec<SearchMatch>,
 }
 
 /// Diff entry for JSON output
@@ -345,6 +640,105 @@ pub fn parse_fd_to_json(output: &str) -> String {
 
 /// Parse unified diff output to JSON
 pub fn parse_diff_to_json(output: &str, file_a: &str, file_b: &str) -> String {
+    let result = DiffResult {
+        file_a: file_a.to_string(),
+        file_b: file_b.to_string(),
+        hunks: parse_hunks(output),
+    };
+
+    serde_json::to_string_pretty(&result).unwrap_or_else(|_| {
+        json!({
+            "file_a": file_a,
+            "file_b": file_b,
+            "raw": output
+        })
+        .to_string()
+    })
+}
+
+/// Parse a multi-file git patch (as from `git diff` or `gh pr diff`) to JSON.
+///
+/// Each file section is split on its `diff --git` header and its hunks are
+/// parsed with the same parser as single-file diffs.
+pub fn parse_patch_to_json(patch: &str) -> Value {
+    let mut sections: Vec<Vec<&str>> = vec![];
+    for line in patch.lines() {
+        if line.starts_with("diff --git ") || sections.is_empty() {
+            sections.push(vec![]);
+        }
+        if let Some(section) = sections.last_mut() {
+            section.push(line);
+        }
+    }
+
+    let mut total_add = 0usize;
+    let mut total_del = 0usize;
+    let files: Vec<Value> = sections
+        .iter()
+        .filter(|s| s.first().is_some_and(|l| l.starts_with("diff --git ")))
+        .map(|section| {
+            // "diff --git a/old b/new" (quoted paths are left as-is)
+            let header = section[0].trim_start_matches("diff --git ");
+            let (mut old_path, mut new_path) = match header.split_once(" b/") {
+                Some((a, b)) => (a.trim_start_matches("a/").to_string(), b.to_string()),
+                None => (header.to_string(), header.to_string()),
+            };
+            let mut status = "modified";
+            let mut binary = false;
+            let mut body_start = section.len();
+            for (i, line) in section.iter().enumerate().skip(1) {
+                if line.starts_with("@@") {
+                    body_start = i;
+                    break;
+                }
+                if line.starts_with("new file mode") {
+                    status = "added";
+                } else if line.starts_with("deleted file mode") {
+                    status = "deleted";
+                } else if let Some(p) = line.strip_prefix("rename from ") {
+                    status = "renamed";
+                    old_path = p.to_string();
+                } else if let Some(p) = line.strip_prefix("rename to ") {
+                    new_path = p.to_string();
+                } else if line.starts_with("Binary files") || line.starts_with("GIT binary patch") {
+                    binary = true;
+                }
+            }
+
+            let body = section[body_start..].join("\n");
+            let hunks = parse_hunks(&body);
+            let count = |kind: &str| {
+                hunks
+                    .iter()
+                    .flat_map(|h| &h.lines)
+                    .filter(|l| l.change_type == kind)
+                    .count()
+            };
+            let (additions, deletions) = (count("add"), count("remove"));
+            total_add += additions;
+            total_del += deletions;
+
+            json!({
+                "path": new_path,
+                "old_path": (old_path != new_path).then_some(old_path),
+                "status": status,
+                "binary": binary,
+                "additions": additions,
+                "deletions": deletions,
+                "hunks": hunks,
+            })
+        })
+        .collect();
+
+    json!({
+        "files_changed": files.len(),
+        "additions": total_add,
+        "deletions": total_del,
+        "files": files,
+    })
+}
+
+fn parse_hunks(output: &str) -> Vec<DiffHunk> {
     let mut hunks = Vec::new();
     let mut current_hunk: Option<DiffHunk> = None;
     let mut old_line = 0u32;
@@ -410,20 +804,7 @@ pub fn parse_diff_to_json(output: &str, file_a: &str, file_b: &str) -> String {
         hunks.push(hunk);
     }
 
-    let result = DiffResult {
-        file_a: file_a.to_string(),
-        file_b: file_b.to_string(),
-        hunks,
-    };
-
-    serde_json::to_string_pretty(&result).unwrap_or_else(|_| {
-        json!({
-            "file_a": file_a,
-            "file_b": file_b,
-            "raw": output
-        })
-        .to_string()
-    })
+    hunks
 }
 
 fn parse_hunk_range(s: &str) -> (u32, u32) {
@@ -483,16 +864,16 @@ fn categorize_file_type(description: &str) -> &'static str {
     }
 }
 
-/// Parse fzf filter output to JSON
-pub fn parse_fzf_to_json(output: &str, query: &str) -> String {
-    let matches: Vec<Value> = output
-        .lines()
-        .filter(|line| !line.is_empty())
+/// Convert scored fuzzy matches (best first) to JSON
+pub fn parse_fzf_to_json(matches: &[(&str, u32)], query: &str) -> String {
+    let matches: Vec<Value> = matches
+        .iter()
         .enumerate()
-        .map(|(i, line)| {
+        .map(|(i, (item, score))| {
             json!({
                 "rank": i + 1,
-                "match": line.trim()
+                "match": item.trim(),
+                "score": score
             })
         })
         .collect();
@@ -505,6 +886,111 @@ pub fn parse_fzf_to_json(output: &str, query: &str) -> String {
     .to_string()
 }
 
+/// Text of an rg `{"text": ...}` field, or of `{"bytes": <base64>}` (what
+/// rg sends for non-UTF-8 data) decoded lossily
+fn rg_text(v: &Value) -> String {
+    let text = match (v.get("text").and_then(|t| t.as_str()), v.get("bytes")) {
+        (Some(text), _) => text.to_string(),
+        (None, Some(bytes)) => bytes
+            .as_str()
+            .and_then(|b| base64::engine::general_purpose::STANDARD.decode(b).ok())
+            .map(|b| String::from_utf8_lossy(&b).into_owned())
+            .unwrap_or_default(),
+        (None, None) => String::new(),
+    };
+    text.trim_end_matches(['\n', '\r']).to_string()
+}
+
+/// Parse ripgrep --json event stream into compact per-file match groups.
+/// `context` is the -C value used, so context lines can be split into
+/// before/after arrays. `max_results` caps the total number of matches.
+pub fn parse_rg_json_to_matches(
+    output: &str,
+    pattern: &str,
+    context: usize,
+    max_results: Option<usize>,
+) -> String {
+    let mut files: Vec<SearchFileMatches> = Vec::new();
+    let mut pending: Vec<(u64, String)> = Vec::new();
+    let mut total = 0usize;
+    let mut truncated = false;
+
+    for line in output.lines() {
+        let Ok(event) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let data = &event["data"];
+        match event["type"].as_str() {
+            Some("begin") => {
+                pending.clear();
+                files.push(SearchFileMatches {
+                    file: rg_text(&data["path"]),
+                    count: 0,
+                    matches: Vec::new(),
+                });
+            }
+            Some("context") => {
+                let line_no = data["line_number"].as_u64().unwrap_or(0);
+                let text = rg_text(&data["lines"]);
+                if let Some(last) = files.last_mut().and_then(|f| f.matches.last_mut()) {
+                    if line_no > last.line && last.after.len() < context {
+                        last.after.push(text.clone());
+                    }
+                }
+                pending.push((line_no, text));
+                if pending.len() > context {
+                    pending.remove(0);
+                }
+            }
+            Some("match") => {
+                if max_results.is_some_and(|max| total >= max) {
+                    truncated = true;
+                    continue;
+                }
+                let line_no = data["line_number"].as_u64().unwrap_or(0);
+                let first = data["submatches"].get(0);
+                let before = pending
+                    .drain(..)
+                    .filter(|(n, _)| *n < line_no && *n + context as u64 >= line_no)
+                    .map(|(_, t)| t)
+                    .collect();
+                let entry = SearchMatch {
+                    line: line_no,
+                    column: first.and_then(|m| m["start"].as_u64()).map(|c| c + 1),
+                    match_text: first.map(|m| rg_text(&m["match"])).unwrap_or_default(),
+                    line_text: rg_text(&data["lines"]),
+                    before,
+                    after: Vec::new(),
+                };
+                if files.is_empty() {
+                    files.push(SearchFileMatches {
+                        file: rg_text(&data["path"]),
+                        count: 0,
+                        matches: Vec::new(),
+                    });
+                }
+                if let Some(file) = files.last_mut() {
+                    file.count += 1;
+                    file.matches.push(entry);
+                }
+                total += 1;
+            }
+            _ => {}
+        }
+    }
+
+    files.retain(|f| f.count > 0);
+
+    json!({
+        "pattern": pattern,
+        "total_matches": total,
+        "files_matched": files.len(),
+        "truncated": truncated,
+        "files": files
+    })
+    .to_string()
+}
+
 /// Parse dust output to JSON
 pub fn parse_dust_to_json(output: &str, path: &str) -> String {
     let entries: Vec<Value> = output
@@ -535,6 +1021,243 @@ pub fn parse_dust_to_json(output: &str, path: &str) -> String {
     .to_string()
 }
 
+/// Parse TAP (Test Anything Protocol) output into per-test results.
+///
+/// Understands bats' `--timing` suffix (`ok 1 name in 12ms`), `# skip` and
+/// `# TODO` directives, and attaches the `#`-prefixed diagnostics that follow
+/// a `not ok` line as that test's failure output.
+pub fn parse_tap_to_json(output: &str) -> Value {
+    let mut tests: Vec<Value> = vec![];
+    let mut planned: Option<u64> = None;
+    let mut failure: Vec<String> = vec![];
+
+    let flush = |tests: &mut Vec<Value>, failure: &mut Vec<String>| {
+        if let Some(last) = tests.last_mut() {
+            if last["status"] == "failed" && !failure.is_empty() {
+                last["output"] = failure.join("\n").into();
+            }
+        }
+        failure.clear();
+    };
+
+    for line in output.lines() {
+        if let Some(plan) = line.strip_prefix("1..") {
+            planned = plan.trim().parse().ok();
+            continue;
+        }
+        let (ok, rest) = if let Some(rest) = line.strip_prefix("not ok ") {
+            (false, rest)
+        } else if let Some(rest) = line.strip_prefix("ok ") {
+            (true, rest)
+        } else {
+            // Diagnostics belong to the preceding failed test
+            if tests.last().is_some_and(|t| t["status"] == "failed") {
+                let diag = line.strip_prefix("# ").or_else(|| line.strip_prefix('#'));
+                failure.push(diag.unwrap_or(line).to_string());
+            }
+            continue;
+        };
+        flush(&mut tests, &mut failure);
+
+        let (number, desc) = rest.split_once(' ').unwrap_or((rest, ""));
+        let (desc, directive) = match desc.split_once(" # ") {
+            Some((d, dir)) => (d, Some(dir.trim())),
+            None => (desc, None),
+        };
+        let (name, duration_ms) = match desc.rsplit_once(" in ") {
+            Some((n, t)) if t.ends_with("ms") && t[..t.len() - 2].parse::<u64>().is_ok() => {
+                (n, t[..t.len() - 2].parse::<u64>().ok())
+            }
+            _ => (desc, None),
+        };
+
+        let lower = directive.map(str::to_lowercase);
+        let status = match lower.as_deref() {
+            Some(d) if d.starts_with("skip") => "skipped",
+            Some(d) if d.starts_with("todo") => "todo",
+            _ if ok => "passed",
+            _ => "failed",
+        };
+        let reason = directive
+            .and_then(|d| d.split_once(' ').map(|(_, r)| r.trim().to_string()))
+            .filter(|r| !r.is_empty());
+
+        tests.push(json!({
+            "number": number.parse::<u64>().ok(),
+            "name": name.trim(),
+            "status": status,
+            "duration_ms": duration_ms,
+            "reason": reason,
+        }));
+    }
+    flush(&mut tests, &mut failure);
+
+    let count = |status: &str| tests.iter().filter(|t| t["status"] == status).count();
+    let failures: Vec<&Value> = tests
+        .iter()
+        .filter(|t| t["status"] == "failed")
+        .map(|t| &t["name"])
+        .collect();
+    let timed: Vec<u64> = tests
+        .iter()
+        .filter_map(|t| t["duration_ms"].as_u64())
+        .collect();
+
+    json!({
+        "summary": {
+            "planned": planned,
+            "total": tests.len(),
+            "passed": count("passed"),
+            "failed": count("failed"),
+            "skipped": count("skipped"),
+            "todo": count("todo"),
+            "duration_ms": (!timed.is_empty()).then(|| timed.iter().sum::<u64>()),
+        },
+        "failures": failures,
+        "tests": tests,
+    })
+}
+
+/// Normalize `gh pr checks --json name,state,bucket,link,workflow,...` output.
+///
+/// `bucket` is gh's own pass/fail/pending/skipping/cancel classification, so
+/// conclusions stay consistent across check runs and commit statuses.
+pub fn parse_gh_checks(output: &str) -> Option<Value> {
+    let raw: Vec<Value> = serde_json::from_str(output).ok()?;
+    let checks: Vec<Value> = raw
+        .iter()
+        .map(|c| {
+            let bucket = c["bucket"].as_str().unwrap_or("");
+            let (status, conclusion) = match bucket {
+                "pass" => ("completed", Some("success")),
+                "fail" => ("completed", Some("failure")),
+                "skipping" => ("completed", Some("skipped")),
+                "cancel" => ("completed", Some("cancelled")),
+                _ => match c["state"].as_str() {
+                    Some("QUEUED" | "PENDING" | "WAITING" | "REQUESTED") => ("queued", None),
+                    _ => ("in_progress", None),
+                },
+            };
+            json!({
+                "check": c["name"],
+                "workflow": c["workflow"],
+                "status": status,
+                "conclusion": conclusion,
+                "url": c["link"],
+                "started_at": c["startedAt"],
+                "completed_at": c["completedAt"],
+            })
+        })
+        .collect();
+
+    let count = |conclusion: Option<&str>| {
+        checks
+            .iter()
+            .filter(|c| c["conclusion"].as_str() == conclusion)
+            .count()
+    };
+    Some(json!({
+        "total": checks.len(),
+        "passed": count(Some("success")),
+        "failed": count(Some("failure")) + count(Some("cancelled")),
+        "skipped": count(Some("skipped")),
+        "pending": count(None),
+        "checks": checks,
+    }))
+}
+
+/// Normalize hyperfine's `--export-json` output.
+///
+/// Times are in seconds; `relative` is each command's mean over the fastest mean.
+pub fn parse_hyperfine_json(output: &str) -> Option<Value> {
+    let export: Value = serde_json::from_str(output).ok()?;
+    let results = export["results"].as_array()?;
+    let fastest = results
+        .iter()
+        .filter_map(|r| r["mean"].as_f64())
+        .fold(f64::INFINITY, f64::min);
+
+    let results: Vec<Value> = results
+        .iter()
+        .map(|r| {
+            let mean = r["mean"].as_f64().unwrap_or(0.0);
+            json!({
+                "command": r["command"],
+                "mean": mean,
+                "stddev": r["stddev"],
+                "median": r["median"],
+                "min": r["min"],
+                "max": r["max"],
+                "user": r["user"],
+                "system": r["system"],
+                "runs": r["times"].as_array().map(|t| t.len()),
+                "relative": if fastest > 0.0 { mean / fastest } else { 1.0 },
+            })
+        })
+        .collect();
+
+    Some(json!({ "results": results }))
+}
+
+/// Compare two normalized hyperfine runs command by command.
+///
+/// Commands are matched by command line only; a command missing from either
+/// run is listed as unmatched rather than compared with whatever ran in its
+/// place. A mean slower by more than `threshold_pct` is a regression; faster
+/// by more is an improvement.
+pub fn compare_benchmarks(current: &Value, previous: &Value, threshold_pct: f64) -> Value {
+    let empty = vec![];
+    let prev_results = previous["results"].as_array().unwrap_or(&empty);
+    let cur_results = current["results"].as_array().unwrap_or(&empty);
+    let unmatched: Vec<Value> = cur_results
+        .iter()
+        .filter(|c| !prev_results.iter().any(|p| p["command"] == c["command"]))
+        .map(|c| json!({"command": c["command"], "missing_from": "baseline"}))
+        .chain(
+            prev_results
+                .iter()
+                .filter(|p| !cur_results.iter().any(|c| c["command"] == p["command"]))
+                .map(|p| json!({"command": p["command"], "missing_from": "current"})),
+        )
+        .collect();
+    let comparisons: Vec<Value> = cur_results
+        .iter()
+        .filter_map(|cur| {
+            let prev = prev_results
+                .iter()
+                .find(|p| p["command"] == cur["command"])?;
+            let cur_mean = cur["mean"].as_f64()?;
+            let prev_mean = prev["mean"].as_f64().filter(|m| *m > 0.0)?;
+            let change_pct = (cur_mean - prev_mean) / prev_mean * 100.0;
+            let status = if change_pct > threshold_pct {
+                "regression"
+            } else if change_pct < -threshold_pct {
+                "improvement"
+            } else {
+                "unchanged"
+            };
+            Some(json!({
+                "command": cur["command"],
+                "previous_mean": prev_mean,
+                "current_mean": cur_mean,
+                "change_pct": (change_pct * 100.0).round() / 100.0,
+                "status": status,
+            }))
+        })
+        .collect();
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c["status"] == "regression")
+        .count();
+    json!({
+        "threshold_pct": threshold_pct,
+        "regressions": regressions,
+        "comparisons": comparisons,
+        "unmatched": unmatched,
+    })
+}
+
 /// Wrap any output as JSON with optional metadata (reserved for future use)
 #[allow(dead_code)]
 pub fn wrap_as_json(output: &str, tool: &str, args: &[&str]) -> String {
@@ -546,3 +1269,161 @@ pub fn wrap_as_json(output: &str, tool: &str, args: &[&str]) -> String {
     })
     .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_benchmarks_by_command() {
+        let run = |results: Value| json!({ "results": results });
+        let previous = run(json!([
+            {"command": "old-tool", "mean": 1.0},
+            {"command": "tool --fast", "mean": 2.0}
+        ]));
+        let current = run(json!([
+            {"command": "new-tool", "mean": 0.5},
+            {"command": "tool --fast", "mean": 2.5}
+        ]));
+        let cmp = compare_benchmarks(&current, &previous, 5.0);
+        assert_eq!(cmp["comparisons"].as_array().unwrap().len(), 1);
+        assert_eq!(cmp["comparisons"][0]["command"], "tool --fast");
+        assert_eq!(cmp["comparisons"][0]["status"], "regression");
+        assert_eq!(cmp["regressions"], 1);
+        // Renamed commands are not compared by position
+        assert_eq!(
+            cmp["unmatched"],
+            json!([
+                {"command": "new-tool", "missing_from": "baseline"},
+                {"command": "old-tool", "missing_from": "current"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_count_file_lines() {
+        let tmp = tempfile::tempdir().unwrap();
+        for text in ["", "one", "one\n", "one\ntwo", "one\n\nthree\n"] {
+            let path = tmp.path().join("input.txt");
+            std::fs::write(&path, text).unwrap();
+            assert_eq!(count_file_lines(&path).unwrap(), text.lines().count());
+        }
+        // Larger than one read buffer
+        let long = "line\n".repeat(STDIN_CHUNK_SIZE);
+        let path = tmp.path().join("long.txt");
+        std::fs::write(&path, &long).unwrap();
+        assert_eq!(count_file_lines(&path).unwrap(), STDIN_CHUNK_SIZE);
+        assert!(count_file_lines(&tmp.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_parse_rg_json_to_matches() {
+        let events = [
+            r#"{"type":"begin","data":{"path":{"text":"src/a.rs"}}}"#,
+            r#"{"type":"context","data":{"path":{"text":"src/a.rs"},"lines":{"text":"fn a() {\n"},"line_number":1}}"#,
+            r#"{"type":"match","data":{"path":{"text":"src/a.rs"},"lines":{"text":"    foo();\n"},"line_number":2,"submatches":[{"match":{"text":"foo"},"start":4,"end":7}]}}"#,
+            r#"{"type":"context","data":{"path":{"text":"src/a.rs"},"lines":{"text":"}\n"},"line_number":3}}"#,
+            r#"{"type":"end","data":{"path":{"text":"src/a.rs"}}}"#,
+            r#"{"type":"begin","data":{"path":{"bytes":"YmluL/8uZGF0"}}}"#,
+            r#"{"type":"match","data":{"path":{"bytes":"YmluL/8uZGF0"},"lines":{"bytes":"Zm9vIP8K"},"line_number":5,"submatches":[{"match":{"text":"foo"},"start":0,"end":3}]}}"#,
+            r#"{"type":"end","data":{"path":{"bytes":"YmluL/8uZGF0"}}}"#,
+            r#"{"type":"summary","data":{"stats":{"matches":2}}}"#,
+        ]
+        .join("\n");
+
+        let result: Value =
+            serde_json::from_str(&parse_rg_json_to_matches(&events, "foo", 1, None)).unwrap();
+        assert_eq!(result["total_matches"], 2);
+        assert_eq!(result["files_matched"], 2);
+        assert_eq!(result["truncated"], false);
+        let first = &result["files"][0]["matches"][0];
+        assert_eq!(result["files"][0]["file"], "src/a.rs");
+        assert_eq!(first["line"], 2);
+        assert_eq!(first["column"], 5);
+        assert_eq!(first["match_text"], "foo");
+        assert_eq!(first["line_text"], "    foo();");
+        assert_eq!(first["before"], json!(["fn a() {"]));
+        assert_eq!(first["after"], json!(["}"]));
+
+        // Non-UTF-8 path and line come as base64 `bytes`
+        let binary = &result["files"][1];
+        assert_eq!(binary["file"], "bin/\u{FFFD}.dat");
+        assert_eq!(binary["matches"][0]["line_text"], "foo \u{FFFD}");
+        assert!(binary["matches"][0].get("before").is_none());
+
+        let capped: Value =
+            serde_json::from_str(&parse_rg_json_to_matches(&events, "foo", 1, Some(1))).unwrap();
+        assert_eq!(capped["total_matches"], 1);
+        assert_eq!(capped["files_matched"], 1);
+        assert_eq!(capped["truncated"], true);
+    }
+
+    #[test]
+    fn test_parse_tap_to_json() {
+        let tap = "1..3
+ok 1 addition works in 12ms
+not ok 2 subtraction fails in 8ms
+# (in test file test.bats, line 7)
+#   `[ \"$result\" -eq 1 ]' failed
+ok 3 slow path in 0ms # skip needs network
+";
+        let result = parse_tap_to_json(tap);
+        assert_eq!(result["summary"]["planned"], 3);
+        assert_eq!(result["summary"]["passed"], 1);
+        assert_eq!(result["summary"]["failed"], 1);
+        assert_eq!(result["summary"]["skipped"], 1);
+        assert_eq!(result["failures"][0], "subtraction fails");
+
+        let failed = &result["tests"][1];
+        assert_eq!(failed["duration_ms"], 8);
+        assert!(failed["output"].as_str().unwrap().contains("line 7"));
+        assert_eq!(result["tests"][2]["reason"], "needs network");
+    }
+
+    #[test]
+    fn test_parse_patch_to_json() {
+        let patch = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,3 @@
+ fn a() {}
+-fn b() {}
++fn b() -> u8 { 0 }
++fn c() {}
+diff --git a/old.txt b/new.txt
+similarity index 100%
+rename from old.txt
+rename to new.txt
+diff --git a/logo.png b/logo.png
+new file mode 100644
+Binary files /dev/null and b/logo.png differ
+";
+        let result = parse_patch_to_json(patch);
+        assert_eq!(result["files_changed"], 3);
+        assert_eq!(result["additions"], 2);
+        assert_eq!(result["deletions"], 1);
+        assert_eq!(result["files"][0]["hunks"][0]["new_count"], 3);
+        assert_eq!(result["files"][1]["status"], "renamed");
+        assert_eq!(result["files"][1]["old_path"], "old.txt");
+        assert_eq!(result["files"][2]["status"], "added");
+        assert_eq!(result["files"][2]["binary"], true);
+    }
+
+    #[test]
+    fn test_parse_gh_checks() {
+        let output = r#"[
+            {"name":"build","state":"SUCCESS","bucket":"pass","link":"https://ci/1","workflow":"CI"},
+            {"name":"lint","state":"FAILURE","bucket":"fail","link":"https://ci/2","workflow":"CI"},
+            {"name":"deploy","state":"IN_PROGRESS","bucket":"pending","link":"","workflow":"CD"}
+        ]"#;
+        let result = parse_gh_checks(output).unwrap();
+        assert_eq!(result["passed"], 1);
+        assert_eq!(result["failed"], 1);
+        assert_eq!(result["pending"], 1);
+        assert_eq!(result["checks"][0]["conclusion"], "success");
+        assert_eq!(result["checks"][2]["status"], "in_progress");
+        assert!(result["checks"][2]["conclusion"].is_null());
+        assert!(parse_gh_checks("no checks reported").is_none());
+    }
+}
@@ -0,0 +1,85 @@
+// modern-cli-mcp/src/tools/fuzzy.rs
+//! Built-in fzf-compatible filtering.
+//!
+//! Queries use fzf's extended search syntax: whitespace-separated terms that
+//! must all match, `'term` for an exact substring, `^prefix`, `suffix$` and
+//! `!term` to exclude. In exact mode (`fzf --exact`) plain terms are
+//! substrings instead, and `'term` makes that one term fuzzy again.
+
+use nucleo_matcher::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher};
+
+/// Non-blank lines of `input` matching `query`, best score first
+pub fn filter<'a>(
+    input: &'a str,
+    query: &str,
+    exact: bool,
+    ignore_case: bool,
+) -> Vec<(&'a str, u32)> {
+    let case = if ignore_case {
+        CaseMatching::Ignore
+    } else {
+        CaseMatching::Smart
+    };
+    let mut pattern = Pattern::parse(query, case, Normalization::Smart);
+    if exact {
+        // Negated terms are already exact; only plain and quoted terms swap
+        for atom in pattern.atoms.iter_mut().filter(|a| !a.negative) {
+            atom.kind = match atom.kind {
+                AtomKind::Fuzzy => AtomKind::Substring,
+                AtomKind::Substring => AtomKind::Fuzzy,
+                kind => kind,
+            };
+        }
+    }
+
+    let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
+    let items = input.lines().filter(|line| !line.trim().is_empty());
+    pattern.match_list(items, &mut matcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: &str = "src/main.rs\nsrc/tools/mod.rs\n\ntests/main_test.rs\nREADME.md\n";
+
+    fn matched(query: &str, exact: bool) -> Vec<&'static str> {
+        let mut items: Vec<&str> = filter(ITEMS, query, exact, false)
+            .into_iter()
+            .map(|(item, _)| item)
+            .collect();
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn test_fuzzy() {
+        assert_eq!(
+            matched("mnrs", false),
+            ["src/main.rs", "tests/main_test.rs"]
+        );
+        // Best match first, with a score
+        let ranked = filter(ITEMS, "main.rs", false, false);
+        assert_eq!(ranked[0].0, "src/main.rs");
+        assert!(ranked[0].1 > 0);
+        assert_eq!(matched("'mod ^src", false), ["src/tools/mod.rs"]);
+        assert_eq!(matched("main !test", false), ["src/main.rs"]);
+        assert_eq!(matched(".md$", false), ["README.md"]);
+        // Smart case unless told otherwise
+        assert!(matched("Readme", false).is_empty());
+        assert_eq!(filter(ITEMS, "Readme", false, true).len(), 1);
+    }
+
+    #[test]
+    fn test_exact() {
+        assert!(matched("mnrs", true).is_empty());
+        assert_eq!(matched("main", true), ["src/main.rs", "tests/main_test.rs"]);
+        assert_eq!(matched("^tests", true), ["tests/main_test.rs"]);
+        assert_eq!(matched("main.rs$", true), ["src/main.rs"]);
+        assert_eq!(matched("^src/main.rs$", true), ["src/main.rs"]);
+        assert_eq!(matched("rs !tools !^tests", true), ["src/main.rs"]);
+        // A quoted term is fuzzy again in exact mode
+        assert_eq!(matched("'mnrs ^src", true), ["src/main.rs"]);
+    }
+}
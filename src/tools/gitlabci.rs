@@ -0,0 +1,306 @@
+// modern-cli-mcp/src/tools/gitlabci.rs
+//! Reading `.gitlab-ci.yml` without a GitLab server.
+//!
+//! Top-level keys are jobs unless they are global keywords; keys starting
+//! with a dot are templates. `extends` is resolved the way GitLab does it,
+//! deep-merging maps and replacing everything else, and YAML merge keys
+//! (`<<: *anchor`) are applied first. `include`d files are listed but not
+//! fetched, so checks that depend on them are left to the lint API.
+
+use serde_json::{json, Map, Value};
+
+/// Top-level keys that configure the pipeline rather than define a job
+const GLOBAL_KEYWORDS: &[&str] = &[
+    "after_script",
+    "before_script",
+    "cache",
+    "default",
+    "image",
+    "include",
+    "services",
+    "stages",
+    "variables",
+    "workflow",
+];
+
+/// Stages a pipeline has when it doesn't declare `stages`
+const DEFAULT_STAGES: &[&str] = &[".pre", "build", "test", "deploy", ".post"];
+
+/// Nested `extends` deeper than this are reported as a loop
+const MAX_EXTENDS_DEPTH: usize = 11;
+
+/// Maps are merged key by key; any other value replaces the base
+fn deep_merge(base: &mut Value, over: &Value) {
+    match (base, over) {
+        (Value::Object(base), Value::Object(over)) => {
+            for (key, value) in over {
+                match base.get_mut(key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, over) => *base = over.clone(),
+    }
+}
+
+/// String-or-list keywords such as `extends` as a list
+fn names(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|i| i.as_str().map(String::from))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// `job` with its `extends` chain merged in
+fn resolve(
+    name: &str,
+    entries: &Map<String, Value>,
+    depth: usize,
+    problems: &mut Vec<String>,
+) -> Value {
+    let Some(job) = entries.get(name) else {
+        return Value::Null;
+    };
+    let parents = names(&job["extends"]);
+    if parents.is_empty() {
+        return job.clone();
+    }
+    if depth >= MAX_EXTENDS_DEPTH {
+        problems.push(format!("extends nests too deeply (loop?) at {}", name));
+        return job.clone();
+    }
+    let mut merged = Value::Object(Map::new());
+    for parent in &parents {
+        if !entries.contains_key(parent) {
+            problems.push(format!("extends unknown job or template {}", parent));
+            continue;
+        }
+        let resolved = resolve(parent, entries, depth + 1, problems);
+        deep_merge(&mut merged, &resolved);
+    }
+    deep_merge(&mut merged, job);
+    merged
+}
+
+/// Parse the last YAML document (a `spec:` header may come first), with
+/// merge keys applied; returns the top-level keys in file order
+fn parse(content: &str) -> Result<Vec<(String, Value)>, String> {
+    use serde::Deserialize;
+    let mut doc = None;
+    for document in serde_yaml::Deserializer::from_str(content) {
+        doc = Some(serde_yaml::Value::deserialize(document).map_err(|e| e.to_string())?);
+    }
+    let mut doc = doc.ok_or("empty CI configuration")?;
+    doc.apply_merge().map_err(|e| e.to_string())?;
+    let serde_yaml::Value::Mapping(mapping) = doc else {
+        return Err("CI configuration is not a mapping".into());
+    };
+    mapping
+        .into_iter()
+        .map(|(key, value)| {
+            let key = key
+                .as_str()
+                .ok_or("top-level keys must be strings")?
+                .to_string();
+            let value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Stages, jobs (with `extends` resolved), templates, includes and the
+/// configuration problems that can be found locally
+pub fn jobs(content: &str) -> Result<Value, String> {
+    let entries = parse(content)?;
+    let by_name: Map<String, Value> = entries.iter().cloned().collect();
+
+    let stages: Vec<String> = match by_name.get("stages") {
+        Some(declared) => {
+            let mut stages = names(declared);
+            // .pre and .post always exist, around the declared stages
+            stages.insert(0, ".pre".into());
+            stages.push(".post".into());
+            stages
+        }
+        None => DEFAULT_STAGES.iter().map(|s| s.to_string()).collect(),
+    };
+    let job_names: Vec<&String> = entries
+        .iter()
+        .map(|(k, _)| k)
+        .filter(|k| !k.starts_with('.') && !GLOBAL_KEYWORDS.contains(&k.as_str()))
+        .collect();
+
+    let mut problems = vec![];
+    let mut jobs = vec![];
+    for name in &job_names {
+        if !by_name[name.as_str()].is_object() {
+            problems.push(json!({ "job": name, "message": "job must be a mapping" }));
+            continue;
+        }
+        let mut job_problems = vec![];
+        let job = resolve(name, &by_name, 0, &mut job_problems);
+        let stage = job["stage"].as_str().unwrap_or("test");
+        if !stages.iter().any(|s| s == stage) {
+            job_problems.push(format!("stage {} is not in stages", stage));
+        }
+        if job["script"].is_null() && job["trigger"].is_null() && job["run"].is_null() {
+            job_problems.push("no script, trigger or run".into());
+        }
+        let needs: Vec<String> = job["needs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|n| match n {
+                Value::String(s) => Some(s.clone()),
+                // needs from other pipelines or optional needs may be absent here
+                other if other["pipeline"].is_null() && other["project"].is_null() => other["job"]
+                    .as_str()
+                    .filter(|_| other["optional"] != true)
+                    .map(String::from),
+                _ => None,
+            })
+            .collect();
+        for need in &needs {
+            if !job_names.contains(&need) {
+                job_problems.push(format!("needs unknown job {}", need));
+            }
+        }
+        for message in job_problems {
+            problems.push(json!({ "job": name, "message": message }));
+        }
+
+        let script_lines = match &job["script"] {
+            Value::Array(lines) => lines.len(),
+            Value::String(_) => 1,
+            _ => 0,
+        };
+        jobs.push(json!({
+            "name": name,
+            "stage": stage,
+            "extends": names(&by_name[name.as_str()]["extends"]),
+            "needs": job["needs"],
+            "rules": job["rules"],
+            "only": job["only"],
+            "except": job["except"],
+            "when": job["when"].as_str().unwrap_or("on_success"),
+            "allow_failure": job["allow_failure"],
+            "image": job["image"],
+            "tags": job["tags"],
+            "environment": job["environment"],
+            "trigger": job["trigger"],
+            "script_lines": script_lines,
+        }));
+    }
+
+    let by_stage: Vec<Value> = stages
+        .iter()
+        .map(|stage| {
+            let in_stage: Vec<&Value> = jobs
+                .iter()
+                .filter(|j| j["stage"] == stage.as_str())
+                .map(|j| &j["name"])
+                .collect();
+            json!({ "stage": stage, "jobs": in_stage })
+        })
+        .collect();
+    let templates: Vec<&String> = entries
+        .iter()
+        .map(|(k, _)| k)
+        .filter(|k| k.starts_with('.'))
+        .collect();
+    let includes = match by_name.get("include") {
+        Some(Value::Array(items)) => items.clone(),
+        Some(item) => vec![item.clone()],
+        None => vec![],
+    };
+
+    Ok(json!({
+        "valid": problems.is_empty(),
+        "stages": stages,
+        "by_stage": by_stage,
+        "jobs": jobs,
+        "templates": templates,
+        "includes": includes,
+        "problems": problems,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs() {
+        let content = r#"
+stages: [build, test]
+
+.rust: &rust
+  image: rust:1.80
+  variables:
+    CARGO_HOME: .cargo
+
+.tests:
+  extends: .rust
+  stage: test
+  rules:
+    - if: $CI_PIPELINE_SOURCE == "merge_request_event"
+
+build:
+  <<: *rust
+  stage: build
+  script:
+    - cargo build
+
+test:
+  extends: .tests
+  needs: [build, {job: docs, optional: true}]
+  script: cargo test
+
+deploy:
+  stage: deploy
+  needs: [package]
+"#;
+        let result = jobs(content).unwrap();
+        assert_eq!(result["stages"], json!([".pre", "build", "test", ".post"]));
+        assert_eq!(result["templates"], json!([".rust", ".tests"]));
+        let jobs = result["jobs"].as_array().unwrap();
+        assert_eq!(jobs[0]["image"], "rust:1.80");
+        assert_eq!(jobs[1]["stage"], "test");
+        assert_eq!(jobs[1]["image"], "rust:1.80");
+        assert!(jobs[1]["rules"].is_array());
+        assert_eq!(result["by_stage"][2]["jobs"], json!(["test"]));
+
+        assert_eq!(result["valid"], false);
+        let messages: Vec<&str> = result["problems"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["message"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "stage deploy is not in stages",
+                "no script, trigger or run",
+                "needs unknown job package"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jobs_errors() {
+        assert!(jobs("- not\n- a mapping\n").is_err());
+        let result = jobs("lint:\n  extends: .missing\n  script: [true]\n").unwrap();
+        assert_eq!(
+            result["problems"][0]["message"],
+            "extends unknown job or template .missing"
+        );
+    }
+}
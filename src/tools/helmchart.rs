@@ -0,0 +1,108 @@
+// modern-cli-mcp/src/tools/helmchart.rs
+//! Helm chart authoring helpers.
+//!
+//! `helm lint` prints one `[LEVEL] path: message` line per finding and a
+//! closing `N chart(s) linted, M chart(s) failed` tally; both are turned into
+//! JSON. Scaffolded charts are listed file by file so the next edit can go
+//! straight to a template.
+
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Findings and the tally from `helm lint` output (stdout and stderr)
+pub fn parse_lint(output: &str) -> Value {
+    let mut messages = vec![];
+    let mut linted = None;
+    let mut failed = None;
+    for line in output.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix('[') {
+            let Some((level, text)) = rest.split_once("] ") else {
+                continue;
+            };
+            let (path, message) = match text.split_once(": ") {
+                Some((path, message)) if !path.contains(' ') => (Some(path), message),
+                _ => (None, text),
+            };
+            messages.push(json!({
+                "severity": level.to_ascii_lowercase(),
+                "path": path,
+                "message": message,
+            }));
+        } else if line.contains("chart(s) linted") {
+            let counts: Vec<u64> = line
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|n| n.parse().ok())
+                .collect();
+            linted = counts.first().copied();
+            failed = counts.get(1).copied();
+        }
+    }
+    let count = |level: &str| messages.iter().filter(|m| m["severity"] == level).count();
+    json!({
+        "charts_linted": linted,
+        "charts_failed": failed,
+        "errors": count("error"),
+        "warnings": count("warning"),
+        "info": count("info"),
+        "messages": messages,
+    })
+}
+
+/// Files under a chart directory, relative and sorted
+pub fn chart_files(dir: &Path) -> Vec<String> {
+    fn walk(root: &Path, dir: &Path, out: &mut Vec<String>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(root, &path, out);
+            } else if let Ok(rel) = path.strip_prefix(root) {
+                out.push(rel.display().to_string());
+            }
+        }
+    }
+    let mut files = vec![];
+    walk(dir, dir, &mut files);
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lint() {
+        let output = "==> Linting ./web\n\
+            [INFO] Chart.yaml: icon is recommended\n\
+            [WARNING] templates/deployment.yaml: object name does not conform to Kubernetes naming requirements\n\
+            [ERROR] templates/: template: web/templates/svc.yaml:3: unexpected \"}\" in operand\n\
+            [ERROR] chart metadata is missing these dependencies: redis\n\
+            \n\
+            Error: 1 chart(s) linted, 1 chart(s) failed\n";
+        let result = parse_lint(output);
+        assert_eq!(result["charts_linted"], 1);
+        assert_eq!(result["charts_failed"], 1);
+        assert_eq!(
+            (result["errors"].as_u64(), result["info"].as_u64()),
+            (Some(2), Some(1))
+        );
+        assert_eq!(result["messages"][1]["path"], "templates/deployment.yaml");
+        assert_eq!(result["messages"][2]["path"], "templates/");
+        assert!(result["messages"][3]["path"].is_null());
+    }
+
+    #[test]
+    fn test_chart_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("templates")).unwrap();
+        std::fs::write(dir.path().join("Chart.yaml"), "name: web").unwrap();
+        std::fs::write(dir.path().join("templates/service.yaml"), "").unwrap();
+        assert_eq!(
+            chart_files(dir.path()),
+            ["Chart.yaml", "templates/service.yaml"]
+        );
+    }
+}
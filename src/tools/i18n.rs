@@ -0,0 +1,418 @@
+// modern-cli-mcp/src/tools/i18n.rs
+//! Translatable strings in source and the catalogs that translate them.
+//!
+//! Keys are extracted from calls such as `t("nav.home")`, `$t('title')`,
+//! `_("Save")` or `t!("greeting")` whose first argument is a string literal;
+//! computed keys can't be seen and are not counted. Catalogs are gettext
+//! `.po` files or nested JSON/YAML flattened to dotted keys, with a
+//! Rails-style `en:` root dropped and i18next plural suffixes folded into the
+//! base key. A catalog's locale comes from `LC_MESSAGES`'s parent, a
+//! locale-named parent directory, or the file name.
+
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Translation functions matched when none are given
+pub const DEFAULT_FUNCTIONS: &[&str] = &[
+    "t",
+    "tc",
+    "T",
+    "_",
+    "gettext",
+    "ngettext",
+    "i18n",
+    "translate",
+    "tr",
+];
+
+/// Extensions searched for translation calls
+pub const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "js", "jsx", "mjs", "ts", "tsx", "vue", "svelte", "py", "rb", "erb", "go", "php", "java",
+    "kt", "swift", "dart", "html",
+];
+
+/// Directory names that hold catalogs but are not locales themselves
+const CATALOG_DIRS: &[&str] = &[
+    "i18n",
+    "l10n",
+    "lang",
+    "langs",
+    "locale",
+    "locales",
+    "messages",
+    "po",
+    "translations",
+];
+
+/// Keys listed per category; longer lists are only counted
+const MAX_LISTED: usize = 50;
+
+static LOCALE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z]{2,3}(?:[-_][A-Za-z0-9]{2,8})*$").unwrap());
+
+static PLURAL_SUFFIX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"_(?:zero|one|two|few|many|other)$").unwrap());
+
+/// Regex matching a call to one of `functions` with a string literal first
+/// argument; the key is in group 1, 2 or 3 depending on the quote
+pub fn call_pattern(functions: &[&str]) -> Regex {
+    let names: Vec<String> = functions.iter().map(|f| regex::escape(f)).collect();
+    Regex::new(&format!(
+        r#"(?:^|[^\w])(?:{})!?\(\s*(?:"((?:[^"\\]|\\.)+)"|'((?:[^'\\]|\\.)+)'|`([^`$\\]+)`)"#,
+        names.join("|")
+    ))
+    .unwrap()
+}
+
+/// `(line, key)` for every translation call in `text`
+pub fn extract(text: &str, pattern: &Regex) -> Vec<(usize, String)> {
+    text.lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            pattern.captures_iter(line).filter_map(move |c| {
+                let key = c.get(1).or(c.get(2)).or(c.get(3))?;
+                Some((i + 1, key.as_str().to_string()))
+            })
+        })
+        .collect()
+}
+
+/// Locale of a catalog file, if its path names one
+pub fn catalog_locale(path: &Path) -> Option<String> {
+    let is_locale = |s: &str| LOCALE.is_match(s) && !CATALOG_DIRS.contains(&s);
+    let component = |p: Option<&Path>| {
+        p.and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .map(String::from)
+    };
+    let parent = component(path.parent());
+    if parent.as_deref() == Some("LC_MESSAGES") {
+        return component(path.parent().and_then(Path::parent)).filter(|l| is_locale(l));
+    }
+    if let Some(parent) = parent.filter(|p| is_locale(p)) {
+        return Some(parent);
+    }
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| is_locale(s))
+        .map(String::from)
+}
+
+/// Undo C-style escapes in a `.po` string
+fn unescape_po(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// `msgid` to translated for a gettext catalog; fuzzy and empty entries
+/// count as untranslated, and obsolete `#~` entries are skipped
+fn parse_po(text: &str) -> BTreeMap<String, bool> {
+    let mut entries = BTreeMap::new();
+    let mut fuzzy = false;
+    let mut msgid: Option<String> = None;
+    let mut msgstrs: Vec<String> = vec![];
+    // Which string continuation lines append to
+    let mut field = "";
+
+    let mut finish = |msgid: &mut Option<String>, msgstrs: &mut Vec<String>, fuzzy: &mut bool| {
+        if let Some(id) = msgid.take().filter(|id| !id.is_empty()) {
+            let translated = !*fuzzy && msgstrs.iter().any(|s| !s.is_empty());
+            entries.insert(id, translated);
+        }
+        msgstrs.clear();
+        *fuzzy = false;
+    };
+
+    for line in text.lines().map(str::trim) {
+        let quoted = |s: &str| {
+            unescape_po(
+                s.trim()
+                    .strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                    .unwrap_or(""),
+            )
+        };
+        if line.is_empty() {
+            finish(&mut msgid, &mut msgstrs, &mut fuzzy);
+        } else if line.starts_with("#,") {
+            fuzzy |= line.contains("fuzzy");
+        } else if line.starts_with('#') {
+            continue;
+        } else if line.starts_with("msgid_plural") {
+            field = "plural";
+        } else if let Some(rest) = line.strip_prefix("msgid ") {
+            if msgid.is_some() {
+                finish(&mut msgid, &mut msgstrs, &mut fuzzy);
+            }
+            msgid = Some(quoted(rest));
+            field = "msgid";
+        } else if let Some(rest) = line.strip_prefix("msgstr") {
+            let value = rest.split_once(' ').map_or("", |(_, v)| v);
+            msgstrs.push(quoted(value));
+            field = "msgstr";
+        } else if line.starts_with('"') {
+            match field {
+                "msgid" => {
+                    if let Some(id) = msgid.as_mut() {
+                        id.push_str(&quoted(line));
+                    }
+                }
+                "msgstr" => {
+                    if let Some(s) = msgstrs.last_mut() {
+                        s.push_str(&quoted(line));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    finish(&mut msgid, &mut msgstrs, &mut fuzzy);
+    entries
+}
+
+/// Dotted key to translated for a nested JSON or YAML catalog
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, bool>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&key, value, out);
+            }
+        }
+        leaf => {
+            let translated = match leaf {
+                Value::String(s) => !s.trim().is_empty(),
+                Value::Null => false,
+                _ => true,
+            };
+            let key = PLURAL_SUFFIX.replace(prefix, "").into_owned();
+            let entry = out.entry(key).or_insert(false);
+            *entry |= translated;
+        }
+    }
+}
+
+/// Whether `path` looks like a translation catalog: a `.po`, JSON or YAML
+/// file with a locale in its path, under a catalog directory unless
+/// `in_catalog_dir` says it already is
+pub fn is_catalog(path: &Path, in_catalog_dir: bool) -> bool {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if !["po", "json", "yaml", "yml"].contains(&ext) || catalog_locale(path).is_none() {
+        return false;
+    }
+    in_catalog_dir
+        || path.components().any(|c| {
+            let name = c.as_os_str().to_str().unwrap_or("");
+            CATALOG_DIRS.contains(&name) || name == "LC_MESSAGES"
+        })
+}
+
+/// Keys of one catalog file and whether each has a translation
+pub fn parse_catalog(path: &Path, text: &str) -> Result<BTreeMap<String, bool>, String> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let value: Value = match ext {
+        "po" => return Ok(parse_po(text)),
+        "json" => serde_json::from_str(text).map_err(|e| e.to_string())?,
+        "yaml" | "yml" => serde_yaml::from_str(text).map_err(|e| e.to_string())?,
+        other => return Err(format!("unsupported catalog format: {}", other)),
+    };
+    // Rails nests every key under the locale
+    let value = match (&value, catalog_locale(path)) {
+        (Value::Object(map), Some(locale)) if map.len() == 1 && map.contains_key(&locale) => {
+            map[&locale].clone()
+        }
+        _ => value,
+    };
+    let mut keys = BTreeMap::new();
+    flatten("", &value, &mut keys);
+    Ok(keys)
+}
+
+/// Per-locale coverage of the keys used in source. Without any extracted
+/// keys, catalogs are compared against the union of all their keys instead
+/// and unused keys are not reported.
+pub fn report(
+    used: &BTreeMap<String, Vec<String>>,
+    catalogs: &BTreeMap<String, BTreeMap<String, bool>>,
+) -> Value {
+    let expected: BTreeSet<&String> = if used.is_empty() {
+        catalogs.values().flat_map(|c| c.keys()).collect()
+    } else {
+        used.keys().collect()
+    };
+    let listed = |keys: Vec<&String>| -> Value {
+        json!({
+            "count": keys.len(),
+            "keys": keys.iter().take(MAX_LISTED).collect::<Vec<_>>(),
+        })
+    };
+
+    let locales: Vec<Value> = catalogs
+        .iter()
+        .map(|(locale, keys)| {
+            let missing: Vec<&String> = expected
+                .iter()
+                .filter(|k| !keys.contains_key(k.as_str()))
+                .copied()
+                .collect();
+            let untranslated: Vec<&String> = expected
+                .iter()
+                .filter(|k| keys.get(k.as_str()) == Some(&false))
+                .copied()
+                .collect();
+            let translated = expected.len() - missing.len() - untranslated.len();
+            let coverage = if expected.is_empty() {
+                100.0
+            } else {
+                (translated as f64 * 1000.0 / expected.len() as f64).round() / 10.0
+            };
+            let unused = (!used.is_empty())
+                .then(|| listed(keys.keys().filter(|k| !used.contains_key(*k)).collect()));
+            json!({
+                "locale": locale,
+                "keys": keys.len(),
+                "translated": translated,
+                "coverage": coverage,
+                "missing": listed(missing),
+                "untranslated": listed(untranslated),
+                "unused": unused,
+            })
+        })
+        .collect();
+
+    let in_no_catalog: Vec<&String> = used
+        .keys()
+        .filter(|k| !catalogs.values().any(|c| c.contains_key(k.as_str())))
+        .collect();
+    let used_keys: Vec<Value> = used
+        .iter()
+        .take(MAX_LISTED)
+        .map(|(key, locations)| json!({ "key": key, "locations": locations }))
+        .collect();
+    json!({
+        "keys_used": used.len(),
+        "used": used_keys,
+        "keys_expected": expected.len(),
+        "locales": locales,
+        "in_no_catalog": listed(in_no_catalog),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let pattern = call_pattern(DEFAULT_FUNCTIONS);
+        let text = "const title = t('nav.home');\n\
+                    <p>{{ $t(\"footer.copyright\") }}</p> i18n.t(`nav.about`)\n\
+                    print(_(\"Save changes\")); let s = format(name); rust_i18n::t!(\"greeting\");\n\
+                    t(key); trait_(\"nope\")\n";
+        let keys: Vec<(usize, String)> = extract(text, &pattern);
+        assert_eq!(
+            keys,
+            [
+                (1, "nav.home".to_string()),
+                (2, "footer.copyright".to_string()),
+                (2, "nav.about".to_string()),
+                (3, "Save changes".to_string()),
+                (3, "greeting".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_catalog_locale() {
+        let locale = |p: &str| catalog_locale(Path::new(p));
+        assert_eq!(locale("locales/fr.json").as_deref(), Some("fr"));
+        assert_eq!(
+            locale("public/locales/pt-BR/common.json").as_deref(),
+            Some("pt-BR")
+        );
+        assert_eq!(
+            locale("locale/de/LC_MESSAGES/app.po").as_deref(),
+            Some("de")
+        );
+        assert_eq!(locale("po/es.po").as_deref(), Some("es"));
+        assert_eq!(locale("config/locales/en.yml").as_deref(), Some("en"));
+        assert_eq!(locale("i18n/messages.json"), None);
+        assert!(is_catalog(Path::new("src/locales/en.json"), false));
+        assert!(!is_catalog(Path::new("src/en.json"), false));
+        assert!(!is_catalog(Path::new("locales/README.md"), true));
+    }
+
+    #[test]
+    fn test_parse_catalog() {
+        let po = "msgid \"\"\nmsgstr \"Language: fr\\n\"\n\n\
+                  msgid \"Save\"\nmsgstr \"Enregistrer\"\n\n\
+                  #, fuzzy\nmsgid \"Cancel\"\nmsgstr \"Annuler\"\n\n\
+                  msgid \"\"\n\"Long \"\n\"text\"\nmsgstr \"\"\n\n\
+                  #~ msgid \"Old\"\n#~ msgstr \"Vieux\"\n";
+        let keys = parse_catalog(Path::new("fr.po"), po).unwrap();
+        assert_eq!(keys.len(), 3);
+        assert!(keys["Save"]);
+        assert!(!keys["Cancel"]);
+        assert!(!keys["Long text"]);
+
+        let yaml = "en:\n  nav:\n    home: Home\n    about: ''\n  items_one: one item\n  items_other: '{{count}} items'\n";
+        let keys = parse_catalog(Path::new("config/locales/en.yml"), yaml).unwrap();
+        assert_eq!(
+            keys.keys().collect::<Vec<_>>(),
+            ["items", "nav.about", "nav.home"]
+        );
+        assert!(!keys["nav.about"]);
+        assert!(parse_catalog(Path::new("en.json"), "{").is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let used: BTreeMap<String, Vec<String>> = [("nav.home", "a.ts:1"), ("nav.about", "a.ts:2")]
+            .iter()
+            .map(|(k, l)| (k.to_string(), vec![l.to_string()]))
+            .collect();
+        let catalog = |pairs: &[(&str, bool)]| -> BTreeMap<String, bool> {
+            pairs.iter().map(|(k, t)| (k.to_string(), *t)).collect()
+        };
+        let catalogs = BTreeMap::from([
+            (
+                "en".to_string(),
+                catalog(&[("nav.home", true), ("nav.about", true), ("old", true)]),
+            ),
+            ("fr".to_string(), catalog(&[("nav.home", false)])),
+        ]);
+        let result = report(&used, &catalogs);
+        let en = &result["locales"][0];
+        assert_eq!(en["coverage"], 100.0);
+        assert_eq!(en["unused"]["keys"], json!(["old"]));
+        let fr = &result["locales"][1];
+        assert_eq!(fr["coverage"], 0.0);
+        assert_eq!(fr["missing"]["keys"], json!(["nav.about"]));
+        assert_eq!(fr["untranslated"]["keys"], json!(["nav.home"]));
+        assert_eq!(result["in_no_catalog"]["count"], 0);
+
+        let result = report(&BTreeMap::new(), &catalogs);
+        assert_eq!(result["keys_expected"], 3);
+        assert!(result["locales"][1]["unused"].is_null());
+    }
+}
@@ -0,0 +1,216 @@
+// modern-cli-mcp/src/tools/imagereport.rs
+//! One report for a container image.
+//!
+//! The registry manifest gives the compressed layer sizes and the image
+//! config gives the history that created them (empty history entries such as
+//! `ENV` or `CMD` have no layer). syft's catalog supplies the installed
+//! packages and their licenses. The base image comes from the OCI
+//! `org.opencontainers.image.base.*` annotations when the build recorded
+//! them, and is otherwise guessed from the detected distribution.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// syft package types installed by an OS package manager
+const OS_PACKAGE_TYPES: &[&str] = &["apk", "deb", "rpm", "alpm", "portage"];
+
+/// Layers listed in the summary, largest first
+pub const TOP_LAYERS: usize = 5;
+
+/// Layers from a single-platform manifest, each paired with the config
+/// history entry that produced it
+pub fn layers(manifest: &Value, config: &Value) -> Vec<Value> {
+    let mut history = config["history"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|h| h["empty_layer"].as_bool() != Some(true));
+    manifest["layers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, layer)| {
+            let created_by = history
+                .next()
+                .and_then(|h| h["created_by"].as_str())
+                .map(clean_created_by);
+            json!({
+                "index": index,
+                "digest": layer["digest"],
+                "size": layer["size"].as_u64().unwrap_or(0),
+                "media_type": layer["mediaType"],
+                "created_by": created_by,
+            })
+        })
+        .collect()
+}
+
+/// Drop the `/bin/sh -c #(nop) ` prefix Docker puts on history entries
+fn clean_created_by(created_by: &str) -> String {
+    let s = created_by.trim();
+    let s = s.strip_prefix("/bin/sh -c ").unwrap_or(s);
+    let s = s.strip_prefix("#(nop) ").unwrap_or(s);
+    s.trim().to_string()
+}
+
+/// The base image: recorded annotations or labels first, then a
+/// `distro:version` guess from syft's distribution
+pub fn base_image(manifest: &Value, config: &Value, distro: &Value) -> Value {
+    let annotation = |key: &str| {
+        manifest["annotations"][key]
+            .as_str()
+            .or(config["config"]["Labels"][key].as_str())
+            .map(String::from)
+    };
+    if let Some(name) = annotation("org.opencontainers.image.base.name") {
+        return json!({
+            "name": name,
+            "digest": annotation("org.opencontainers.image.base.digest"),
+            "source": "annotation",
+        });
+    }
+    let id = distro["id"].as_str().unwrap_or("");
+    if id.is_empty() {
+        return Value::Null;
+    }
+    let version = distro["versionID"].as_str().unwrap_or("");
+    // Alpine images are tagged by minor version, 3.19 rather than 3.19.1
+    let tag = match id {
+        "alpine" => version.splitn(3, '.').take(2).collect::<Vec<_>>().join("."),
+        _ => version.to_string(),
+    };
+    json!({
+        "name": if tag.is_empty() { id.to_string() } else { format!("{}:{}", id, tag) },
+        "distro": distro["prettyName"].as_str().or(distro["name"].as_str()),
+        "source": "distro",
+    })
+}
+
+/// License names of one syft artifact; syft has emitted both plain strings
+/// and `{value, spdxExpression}` objects
+fn licenses(artifact: &Value) -> Vec<String> {
+    artifact["licenses"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|l| match l {
+            Value::String(s) => Some(s.clone()),
+            other => other["spdxExpression"]
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .or(other["value"].as_str())
+                .map(String::from),
+        })
+        .collect()
+}
+
+/// Packages, counts by type and license from `syft -o syft-json` output
+pub fn packages(syft: &Value) -> Value {
+    let mut by_type: BTreeMap<String, usize> = BTreeMap::new();
+    let mut by_license: BTreeMap<String, usize> = BTreeMap::new();
+    let mut unlicensed = 0;
+    let mut os_packages = 0;
+    let list: Vec<Value> = syft["artifacts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|artifact| {
+            let kind = artifact["type"].as_str().unwrap_or("unknown");
+            *by_type.entry(kind.to_string()).or_default() += 1;
+            if OS_PACKAGE_TYPES.contains(&kind) {
+                os_packages += 1;
+            }
+            let names = licenses(artifact);
+            if names.is_empty() {
+                unlicensed += 1;
+            }
+            for name in &names {
+                *by_license.entry(name.clone()).or_default() += 1;
+            }
+            json!({
+                "name": artifact["name"],
+                "version": artifact["version"],
+                "type": kind,
+                "licenses": names,
+            })
+        })
+        .collect();
+    let mut licenses: Vec<(String, usize)> = by_license.into_iter().collect();
+    licenses.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    json!({
+        "total": list.len(),
+        "os_packages": os_packages,
+        "by_type": by_type,
+        "licenses": licenses
+            .iter()
+            .map(|(name, count)| json!({ "license": name, "count": count }))
+            .collect::<Vec<_>>(),
+        "without_license": unlicensed,
+        "packages": list,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layers_and_base() {
+        let manifest = json!({
+            "layers": [
+                {"mediaType": "application/vnd.oci.image.layer.v1.tar+gzip", "digest": "sha256:a", "size": 3000},
+                {"mediaType": "application/vnd.oci.image.layer.v1.tar+gzip", "digest": "sha256:b", "size": 500},
+            ]
+        });
+        let config = json!({
+            "config": {"Labels": {}},
+            "history": [
+                {"created_by": "/bin/sh -c #(nop) ADD file:abc in / "},
+                {"created_by": "/bin/sh -c #(nop)  CMD [\"/bin/sh\"]", "empty_layer": true},
+                {"created_by": "RUN /bin/sh -c apk add curl # buildkit"},
+            ]
+        });
+        let found = layers(&manifest, &config);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0]["created_by"], "ADD file:abc in /");
+        assert_eq!(
+            found[1]["created_by"],
+            "RUN /bin/sh -c apk add curl # buildkit"
+        );
+
+        let distro =
+            json!({"id": "alpine", "versionID": "3.19.1", "prettyName": "Alpine Linux v3.19"});
+        assert_eq!(
+            base_image(&manifest, &config, &distro)["name"],
+            "alpine:3.19"
+        );
+        let annotated = json!({"annotations": {
+            "org.opencontainers.image.base.name": "docker.io/library/debian:12-slim",
+        }});
+        let base = base_image(&annotated, &config, &distro);
+        assert_eq!(
+            (base["name"].as_str(), base["source"].as_str()),
+            (Some("docker.io/library/debian:12-slim"), Some("annotation"))
+        );
+        assert!(base_image(&manifest, &config, &Value::Null).is_null());
+    }
+
+    #[test]
+    fn test_packages() {
+        let syft = json!({"artifacts": [
+            {"name": "musl", "version": "1.2.4", "type": "apk", "licenses": [{"value": "MIT", "spdxExpression": "MIT"}]},
+            {"name": "busybox", "version": "1.36", "type": "apk", "licenses": ["GPL-2.0-only"]},
+            {"name": "requests", "version": "2.31", "type": "python", "licenses": [{"value": "Apache 2.0", "spdxExpression": ""}]},
+            {"name": "left-pad", "version": "1.3.0", "type": "npm", "licenses": []},
+            {"name": "zlib", "version": "1.3", "type": "apk", "licenses": ["Zlib", "MIT"]},
+        ]});
+        let report = packages(&syft);
+        assert_eq!(report["total"], 5);
+        assert_eq!(report["os_packages"], 3);
+        assert_eq!(report["by_type"]["apk"], 3);
+        assert_eq!(report["without_license"], 1);
+        assert_eq!(report["licenses"][0], json!({"license": "MIT", "count": 2}));
+        assert_eq!(report["packages"][2]["licenses"], json!(["Apache 2.0"]));
+    }
+}
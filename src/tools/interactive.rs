@@ -0,0 +1,262 @@
+// modern-cli-mcp/src/tools/interactive.rs
+//! Detection of invocations that expect a terminal.
+//!
+//! MCP tools run with stdin closed and no controlling TTY, so anything that
+//! opens a UI, an editor, or a login prompt would otherwise hang until the
+//! timeout. Invocations that merely *request* a TTY (e.g. `podman run -t`)
+//! can still run non-interactively under a pseudo-terminal.
+
+use std::path::Path;
+
+/// How a command invocation interacts with the terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Interactivity {
+    /// Safe to run with piped stdio
+    Batch,
+    /// Needs a TTY for output but reads no input; run under a pty
+    NeedsPty,
+    /// Waits for user input; fail fast with the given guidance
+    Interactive(String),
+}
+
+/// Classify a command invocation before it is spawned
+pub fn classify(cmd: &str, args: &[&str]) -> Interactivity {
+    let name = Path::new(cmd)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(cmd);
+    let has = |flags: &[&str]| args.iter().any(|a| flags.contains(a));
+
+    match name {
+        "vi" | "vim" | "nvim" | "nano" | "emacs" | "less" | "more" | "top" | "htop" | "btop"
+        | "btm" | "watch" | "tmux" | "screen" => Interactivity::Interactive(format!(
+            "'{}' is a full-screen terminal program. Use File - Read/Edit or a \
+             non-interactive equivalent instead",
+            name
+        )),
+        "fzf" | "sk" if !args.iter().any(|a| *a == "-f" || a.starts_with("--filter")) => {
+            Interactivity::Interactive(
+                "fzf needs --filter QUERY when run without a terminal. \
+                 Use the Search - Fuzzy tool instead"
+                    .into(),
+            )
+        }
+        "navi"
+            if !has(&["--best-match"])
+                && !args
+                    .first()
+                    .is_some_and(|a| matches!(*a, "fn" | "info" | "widget")) =>
+        {
+            Interactivity::Interactive(
+                "navi opens a selection UI. Pass --best-match with --query".into(),
+            )
+        }
+        "dive" if !has(&["--ci", "--json"]) => Interactivity::Interactive(
+            "dive opens a layer browser. Pass --ci (or --json) for non-interactive analysis".into(),
+        ),
+        "gh" if args.starts_with(&["auth", "login"]) && !has(&["--with-token"]) => {
+            Interactivity::Interactive(
+                "gh auth login starts a browser/device flow. \
+                 Use GitHub - Auth Login with a token instead"
+                    .into(),
+            )
+        }
+        "glab" if args.starts_with(&["auth", "login"]) && !has(&["--stdin", "--token", "-t"]) => {
+            Interactivity::Interactive(
+                "glab auth login prompts for input. \
+                 Use GitLab - Auth Login with a token instead"
+                    .into(),
+            )
+        }
+        "git" => classify_git(args),
+        "podman" | "docker" | "kubectl" => {
+            let sub = args.iter().find(|a| !a.starts_with('-')).copied();
+            let tty = args
+                .iter()
+                .any(|a| matches!(*a, "-t" | "--tty" | "-it" | "-ti" | "--tty=true"));
+            if tty && matches!(sub, Some("run" | "exec" | "attach")) {
+                Interactivity::NeedsPty
+            } else {
+                Interactivity::Batch
+            }
+        }
+        "bash" | "sh" | "zsh" | "fish" | "nu" | "dash" if args.first() == Some(&"-c") => args
+            .get(1)
+            .map(|script| classify_script(script))
+            .unwrap_or(Interactivity::Batch),
+        _ => Interactivity::Batch,
+    }
+}
+
+/// git subcommands that open an editor or prompt per hunk
+fn classify_git(args: &[&str]) -> Interactivity {
+    // Skip global options (-C dir, -c key=val) to find the subcommand
+    let mut rest = args;
+    while let Some((first, tail)) = rest.split_first() {
+        match *first {
+            "-C" | "-c" => rest = tail.get(1..).unwrap_or(&[]),
+            a if a.starts_with('-') => rest = tail,
+            _ => break,
+        }
+    }
+    let Some((sub, opts)) = rest.split_first() else {
+        return Interactivity::Batch;
+    };
+    let has = |flags: &[&str]| opts.iter().any(|a| flags.contains(a));
+
+    match *sub {
+        "rebase" if has(&["-i", "--interactive"]) => Interactivity::Interactive(
+            "git rebase -i opens an editor. Use a non-interactive rebase or \
+             GIT_SEQUENCE_EDITOR"
+                .into(),
+        ),
+        "add" | "checkout" | "reset" | "stash"
+            if has(&["-p", "--patch", "-i", "--interactive"]) =>
+        {
+            Interactivity::Interactive(format!(
+                "git {} --patch prompts per hunk. Stage explicit paths instead",
+                sub
+            ))
+        }
+        "commit"
+            if !opts.iter().any(|a| {
+                matches!(*a, "--file" | "--no-edit" | "--reuse-message")
+                    || short_message_flag(a)
+                    || a.starts_with("--message")
+                    || a.starts_with("--file=")
+                    || a.starts_with("--fixup")
+            }) =>
+        {
+            Interactivity::Interactive(
+                "git commit without a message opens an editor. Pass -m MESSAGE".into(),
+            )
+        }
+        _ => Interactivity::Batch,
+    }
+}
+
+/// A short-flag cluster such as `-m`, `-am`, `-aF` or `-vCHEAD` that supplies
+/// the commit message. Letters after one that takes a value (`-c`, `-S`, `-u`)
+/// are that value, not flags.
+fn short_message_flag(arg: &str) -> bool {
+    let Some(cluster) = arg.strip_prefix('-').filter(|c| !c.starts_with('-')) else {
+        return false;
+    };
+    for c in cluster.chars() {
+        match c {
+            'm' | 'F' | 'C' => return true,
+            'c' | 'S' | 'u' | 't' => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Check each simple command in a `sh -c` script
+fn classify_script(script: &str) -> Interactivity {
+    let mut result = Interactivity::Batch;
+    for segment in script.split(['\n', ';', '|', '&']) {
+        let Ok(words) = shellwords::split(segment.trim()) else {
+            continue;
+        };
+        // Skip leading VAR=value assignments and wrappers
+        let words: Vec<&str> = words
+            .iter()
+            .map(|w| w.as_str())
+            .skip_while(|w| w.contains('=') && !w.starts_with('-'))
+            .skip_while(|w| matches!(*w, "sudo" | "env" | "exec" | "command" | "time"))
+            .collect();
+        if let Some((cmd, args)) = words.split_first() {
+            match classify(cmd, args) {
+                Interactivity::Interactive(msg) => return Interactivity::Interactive(msg),
+                Interactivity::NeedsPty => result = Interactivity::NeedsPty,
+                Interactivity::Batch => {}
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interactive_programs() {
+        assert!(matches!(
+            classify("vim", &["a.txt"]),
+            Interactivity::Interactive(_)
+        ));
+        assert!(matches!(
+            classify("navi", &[]),
+            Interactivity::Interactive(_)
+        ));
+        assert_eq!(
+            classify("navi", &["--query", "tar", "--best-match"]),
+            Interactivity::Batch
+        );
+        assert!(matches!(
+            classify("gh", &["auth", "login"]),
+            Interactivity::Interactive(_)
+        ));
+        assert_eq!(
+            classify("gh", &["auth", "login", "--with-token"]),
+            Interactivity::Batch
+        );
+    }
+
+    #[test]
+    fn test_git_editor_detection() {
+        assert!(matches!(
+            classify("git", &["commit"]),
+            Interactivity::Interactive(_)
+        ));
+        assert_eq!(
+            classify("git", &["commit", "-m", "msg"]),
+            Interactivity::Batch
+        );
+        assert!(matches!(
+            classify("git", &["-C", "/repo", "rebase", "-i", "HEAD~3"]),
+            Interactivity::Interactive(_)
+        ));
+        for cluster in [["-am", "msg"], ["-aF", "msg.txt"], ["-C", "HEAD"]] {
+            assert_eq!(
+                classify("git", &["commit", cluster[0], cluster[1]]),
+                Interactivity::Batch
+            );
+        }
+        // -c reuses a message but still opens the editor
+        assert!(matches!(
+            classify("git", &["commit", "-c", "HEAD"]),
+            Interactivity::Interactive(_)
+        ));
+        assert!(matches!(
+            classify("git", &["commit", "-a"]),
+            Interactivity::Interactive(_)
+        ));
+        assert_eq!(
+            classify("bash", &["-c", "git add . && git commit -am 'wip'"]),
+            Interactivity::Batch
+        );
+    }
+
+    #[test]
+    fn test_tty_flags_and_scripts() {
+        assert_eq!(
+            classify("podman", &["run", "-t", "alpine", "ls"]),
+            Interactivity::NeedsPty
+        );
+        assert_eq!(
+            classify("podman", &["build", "-t", "img", "."]),
+            Interactivity::Batch
+        );
+        assert!(matches!(
+            classify("bash", &["-c", "cd /tmp && GIT_DIR=x git commit"]),
+            Interactivity::Interactive(_)
+        ));
+        assert_eq!(
+            classify("bash", &["-c", "ls | wc -l"]),
+            Interactivity::Batch
+        );
+    }
+}
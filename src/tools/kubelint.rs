@@ -0,0 +1,169 @@
+// modern-cli-mcp/src/tools/kubelint.rs
+//! Best-practice findings for Kubernetes manifests.
+//!
+//! kube-linter and polaris report in different shapes: kube-linter lists
+//! failed checks with remediation text but no severity, polaris nests
+//! pass/fail results per object, pod and container with a severity but only
+//! a short message. Both are reduced to one finding per failed check and
+//! object. kube-linter findings are graded with polaris' `danger`/`warning`
+//! scale, `danger` for the checks that weaken workload isolation.
+
+use serde_json::{json, Value};
+
+/// kube-linter checks reported as `danger`; the rest are `warning`
+const DANGER_CHECKS: &[&str] = &[
+    "access-to-create-pods",
+    "access-to-secrets",
+    "cluster-admin-role-binding",
+    "docker-sock",
+    "host-ipc",
+    "host-network",
+    "host-pid",
+    "privilege-escalation-container",
+    "privileged-container",
+    "privileged-ports",
+    "run-as-non-root",
+    "sensitive-host-mounts",
+    "unsafe-sysctls",
+    "writable-host-mount",
+];
+
+/// `Kind/namespace/name`, leaving out an empty namespace
+fn object_id(kind: &str, namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        format!("{}/{}", kind, name)
+    } else {
+        format!("{}/{}/{}", kind, namespace, name)
+    }
+}
+
+/// Findings from `kube-linter lint --format json`
+pub fn parse_kube_linter(output: &str) -> Result<Vec<Value>, String> {
+    let parsed: Value = serde_json::from_str(output)
+        .map_err(|e| format!("Unexpected kube-linter output: {}", e))?;
+    let findings = parsed["Reports"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|report| {
+            let check = report["Check"].as_str().unwrap_or("");
+            let object = &report["Object"]["K8sObject"];
+            json!({
+                "check": check,
+                "severity": if DANGER_CHECKS.contains(&check) { "danger" } else { "warning" },
+                "message": report["Diagnostic"]["Message"],
+                "remediation": report["Remediation"],
+                "object": object_id(
+                    object["GroupVersionKind"]["Kind"].as_str().unwrap_or(""),
+                    object["Namespace"].as_str().unwrap_or(""),
+                    object["Name"].as_str().unwrap_or(""),
+                ),
+                "file": report["Object"]["Metadata"]["FilePath"],
+            })
+        })
+        .collect();
+    Ok(findings)
+}
+
+/// Failed, non-ignored checks in one polaris result map
+fn polaris_failures(results: &Value, object: &str, container: Option<&str>, out: &mut Vec<Value>) {
+    let Some(results) = results.as_object() else {
+        return;
+    };
+    for (id, result) in results {
+        let severity = result["Severity"].as_str().unwrap_or("warning");
+        if result["Success"].as_bool() != Some(false) || severity == "ignore" {
+            continue;
+        }
+        let details: Vec<&str> = result["Details"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|d| d.as_str())
+            .collect();
+        out.push(json!({
+            "check": id,
+            "severity": severity,
+            "category": result["Category"],
+            "message": result["Message"],
+            "remediation": if details.is_empty() { Value::Null } else { json!(details.join(" ")) },
+            "object": object,
+            "container": container,
+        }));
+    }
+}
+
+/// Findings from `polaris audit --format json`
+pub fn parse_polaris(output: &str) -> Result<Vec<Value>, String> {
+    let parsed: Value =
+        serde_json::from_str(output).map_err(|e| format!("Unexpected polaris output: {}", e))?;
+    let mut findings = vec![];
+    for item in parsed["Results"].as_array().into_iter().flatten() {
+        let object = object_id(
+            item["Kind"].as_str().unwrap_or(""),
+            item["Namespace"].as_str().unwrap_or(""),
+            item["Name"].as_str().unwrap_or(""),
+        );
+        polaris_failures(&item["Results"], &object, None, &mut findings);
+        let pod = &item["PodResult"];
+        polaris_failures(&pod["Results"], &object, None, &mut findings);
+        for container in pod["ContainerResults"].as_array().into_iter().flatten() {
+            polaris_failures(
+                &container["Results"],
+                &object,
+                container["Name"].as_str(),
+                &mut findings,
+            );
+        }
+    }
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kube_linter() {
+        let output = r#"{"Checks":[],"Reports":[{
+            "Diagnostic":{"Message":"container \"app\" is Privileged hence allows privilege escalation."},
+            "Check":"privileged-container",
+            "Remediation":"Do not run your container as privileged unless it is required.",
+            "Object":{"Metadata":{"FilePath":"deploy.yaml"},
+                "K8sObject":{"Namespace":"web","Name":"app","GroupVersionKind":{"Group":"apps","Version":"v1","Kind":"Deployment"}}}
+        },{
+            "Diagnostic":{"Message":"container \"app\" has cpu request 0"},
+            "Check":"unset-cpu-requirements",
+            "Remediation":"Set CPU requests for your container.",
+            "Object":{"Metadata":{"FilePath":"deploy.yaml"},
+                "K8sObject":{"Namespace":"","Name":"app","GroupVersionKind":{"Kind":"Deployment"}}}
+        }]}"#;
+        let findings = parse_kube_linter(output).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0]["severity"], "danger");
+        assert_eq!(findings[0]["object"], "Deployment/web/app");
+        assert_eq!(findings[1]["severity"], "warning");
+        assert_eq!(findings[1]["object"], "Deployment/app");
+        assert!(parse_kube_linter("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_polaris() {
+        let output = r#"{"Results":[{
+            "Name":"app","Namespace":"web","Kind":"Deployment",
+            "Results":{"deploymentMissingReplicas":{"ID":"deploymentMissingReplicas","Message":"Only one replica is scheduled","Success":false,"Severity":"warning","Category":"Reliability"}},
+            "PodResult":{"Name":"","Results":{
+                "hostNetworkSet":{"Message":"Host network is not configured","Success":true,"Severity":"danger","Category":"Security"}
+            },"ContainerResults":[{"Name":"app","Results":{
+                "runAsRootAllowed":{"Message":"Should not be allowed to run as root","Success":false,"Severity":"danger","Category":"Security","Details":["Set runAsNonRoot: true"]},
+                "tagNotSpecified":{"Message":"Image tag should be specified","Success":false,"Severity":"ignore","Category":"Reliability"}
+            }}]}
+        }]}"#;
+        let findings = parse_polaris(output).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0]["check"], "deploymentMissingReplicas");
+        assert_eq!(findings[1]["severity"], "danger");
+        assert_eq!(findings[1]["container"], "app");
+        assert_eq!(findings[1]["remediation"], "Set runAsNonRoot: true");
+    }
+}
@@ -0,0 +1,250 @@
+// modern-cli-mcp/src/tools/kubewait.rs
+//! `kubectl wait` invocations.
+//!
+//! kubectl only reports a bad combination of name, selector and `--all`, or
+//! an unparsable timeout, after it has contacted the cluster, and a bare
+//! number of seconds is not a Go duration at all. The request is checked and
+//! normalized here first so the mistake comes back at once with a clear
+//! message.
+
+use super::parse_duration_secs;
+
+/// A wait as requested
+#[derive(Debug, Default)]
+pub struct Wait<'a> {
+    /// Resource type, or `type/name`
+    pub resource: &'a str,
+    pub name: Option<&'a str>,
+    pub condition: Option<&'a str>,
+    pub selector: Option<&'a str>,
+    pub namespace: Option<&'a str>,
+    pub all: bool,
+    pub timeout: Option<&'a str>,
+}
+
+/// The checked wait and the kubectl arguments for it
+#[derive(Debug, PartialEq)]
+pub struct Plan {
+    pub target: String,
+    pub condition: String,
+    pub timeout: String,
+    pub args: Vec<String>,
+}
+
+impl Wait<'_> {
+    /// Check the wait and build its arguments
+    pub fn plan(&self) -> Result<Plan, String> {
+        let resource = self.resource.trim();
+        if resource.is_empty() {
+            return Err("resource is required".into());
+        }
+        let named = resource.contains('/');
+        let name = self.name.filter(|n| !n.is_empty());
+        let selector = self.selector.filter(|s| !s.is_empty());
+        if named && name.is_some() {
+            return Err(format!(
+                "Give the name either in resource ({}) or in name, not both",
+                resource
+            ));
+        }
+        let named = named || name.is_some();
+        if named && (selector.is_some() || self.all) {
+            return Err("A named resource cannot be combined with selector or all".into());
+        }
+        if selector.is_some() && self.all {
+            return Err("Use either selector or all, not both".into());
+        }
+        if !named && selector.is_none() && !self.all {
+            return Err(format!(
+                "Nothing to wait for: name a {} or give a selector or all=true",
+                resource
+            ));
+        }
+
+        let condition = match self.condition.map(str::trim).filter(|c| !c.is_empty()) {
+            None => "condition=Ready".to_string(),
+            Some(c @ ("delete" | "create")) => c.to_string(),
+            Some(c) if c.starts_with("condition=") || c.starts_with("jsonpath=") => c.to_string(),
+            // A bare condition name, as in `Available`
+            Some(c) if !c.contains('=') => format!("condition={}", c),
+            Some(c) => {
+                return Err(format!(
+                    "Unsupported condition '{}'. Use condition=<name>, jsonpath=<expr>=<value>, delete or create",
+                    c
+                ))
+            }
+        };
+        let timeout = match self.timeout {
+            None => "60s".to_string(),
+            Some(t) => match parse_duration_secs(t) {
+                Some(secs) => format!("{}s", secs),
+                None => return Err(format!("Invalid timeout '{}'. Use e.g. 30s, 5m, 1h", t)),
+            },
+        };
+
+        let target = match name {
+            Some(name) => format!("{}/{}", resource, name),
+            None => resource.to_string(),
+        };
+        let mut args = vec![
+            "wait".to_string(),
+            target.clone(),
+            format!("--for={}", condition),
+            format!("--timeout={}", timeout),
+        ];
+        if let Some(ns) = self.namespace.filter(|n| !n.is_empty()) {
+            args.extend(["-n".into(), ns.to_string()]);
+        }
+        if let Some(selector) = selector {
+            args.extend(["-l".into(), selector.to_string()]);
+        }
+        if self.all {
+            args.push("--all".into());
+        }
+        Ok(Plan {
+            target,
+            condition,
+            timeout,
+            args,
+        })
+    }
+}
+
+/// Resources kubectl reported as done (`... condition met` or `... deleted`)
+pub fn met(stdout: &str) -> Vec<&str> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter_map(|l| {
+            l.strip_suffix(" condition met")
+                .or_else(|| l.strip_suffix(" deleted"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_args() {
+        let plan = Wait {
+            resource: "deployment",
+            name: Some("web"),
+            namespace: Some("prod"),
+            ..Default::default()
+        }
+        .plan()
+        .unwrap();
+        assert_eq!(plan.target, "deployment/web");
+        assert_eq!(
+            plan.args,
+            [
+                "wait",
+                "deployment/web",
+                "--for=condition=Ready",
+                "--timeout=60s",
+                "-n",
+                "prod"
+            ]
+        );
+
+        let plan = Wait {
+            resource: "pods",
+            selector: Some("app=nginx"),
+            condition: Some("Available"),
+            timeout: Some("5m"),
+            ..Default::default()
+        }
+        .plan()
+        .unwrap();
+        assert_eq!(plan.condition, "condition=Available");
+        assert_eq!(plan.timeout, "300s");
+        assert_eq!(
+            &plan.args[2..],
+            [
+                "--for=condition=Available",
+                "--timeout=300s",
+                "-l",
+                "app=nginx"
+            ]
+        );
+
+        let plan = Wait {
+            resource: "job/migrate",
+            condition: Some("jsonpath={.status.succeeded}=1"),
+            timeout: Some("90"),
+            ..Default::default()
+        }
+        .plan()
+        .unwrap();
+        assert_eq!(plan.args[2], "--for=jsonpath={.status.succeeded}=1");
+        assert_eq!(plan.args[3], "--timeout=90s");
+
+        let plan = Wait {
+            resource: "pods",
+            all: true,
+            condition: Some("delete"),
+            ..Default::default()
+        }
+        .plan()
+        .unwrap();
+        assert_eq!(plan.args.last().unwrap(), "--all");
+        assert_eq!(plan.condition, "delete");
+    }
+
+    #[test]
+    fn test_plan_rejects_bad_combinations() {
+        let err = |wait: Wait| wait.plan().unwrap_err();
+        assert!(err(Wait {
+            resource: "deployment/web",
+            name: Some("api"),
+            ..Default::default()
+        })
+        .contains("not both"));
+        assert!(err(Wait {
+            resource: "deployment",
+            name: Some("web"),
+            selector: Some("app=web"),
+            ..Default::default()
+        })
+        .contains("cannot be combined"));
+        assert!(err(Wait {
+            resource: "pods/web-0",
+            all: true,
+            ..Default::default()
+        })
+        .contains("cannot be combined"));
+        assert!(err(Wait {
+            resource: "pods",
+            selector: Some("app=web"),
+            all: true,
+            ..Default::default()
+        })
+        .contains("either selector or all"));
+        assert!(err(Wait {
+            resource: "pods",
+            ..Default::default()
+        })
+        .contains("Nothing to wait for"));
+        assert!(err(Wait {
+            resource: "pods/web-0",
+            timeout: Some("soon"),
+            ..Default::default()
+        })
+        .contains("Invalid timeout"));
+        assert!(err(Wait {
+            resource: "pods/web-0",
+            condition: Some("phase=Running"),
+            ..Default::default()
+        })
+        .contains("Unsupported condition"));
+    }
+
+    #[test]
+    fn test_met() {
+        let stdout = "pod/web-0 condition met\npod/web-1 condition met\npod/old deleted\n";
+        assert_eq!(met(stdout), ["pod/web-0", "pod/web-1", "pod/old"]);
+        assert!(met("error: timed out waiting for the condition\n").is_empty());
+    }
+}
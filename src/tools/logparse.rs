@@ -0,0 +1,328 @@
+// modern-cli-mcp/src/tools/logparse.rs
+//! Log summarization.
+//!
+//! Detects the format of each line (JSON lines, syslog, or plain text with a
+//! leading timestamp and level token) and reduces the whole input to level
+//! counts, the most frequent error messages, and time-bucketed counts.
+
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+/// RFC 3164 syslog: `Jan  2 03:04:05 host prog[123]: message`
+static SYSLOG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:<\d+>)?([A-Z][a-z]{2}) +(\d{1,2}) (\d{2}:\d{2}):\d{2} \S+ [^:\[\s]+(?:\[\d+\])?: ?(.*)$",
+    )
+    .unwrap()
+});
+
+/// ISO-ish timestamp anywhere near the start of the line
+static ISO_TS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d{4}-\d{2}-\d{2})[T ](\d{2}:\d{2})(?::\d{2}(?:[.,]\d+)?)?").unwrap()
+});
+
+/// Level token in plain-text logs
+static LEVEL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(trace|debug|info|notice|warn(?:ing)?|error|err|fatal|crit(?:ical)?|panic|emerg|alert)\b")
+        .unwrap()
+});
+
+/// Variable parts of a message collapsed when grouping errors
+static VARIABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}|0x[0-9a-f]+|\b[0-9a-f]{12,}\b|\d+(?:\.\d+)*")
+        .unwrap()
+});
+
+/// Time bucket granularity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl Granularity {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "minute" | "m" => Some(Self::Minute),
+            "hour" | "h" => Some(Self::Hour),
+            "day" | "d" => Some(Self::Day),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Minute => "minute",
+            Self::Hour => "hour",
+            Self::Day => "day",
+        }
+    }
+}
+
+/// (day, hour, minute) bucket keys
+type TimeKeys = (String, String, String);
+
+/// One parsed log line
+struct Entry {
+    format: &'static str,
+    level: &'static str,
+    time: Option<TimeKeys>,
+    message: String,
+}
+
+/// Summarize raw log text
+pub fn summarize(input: &str, top: usize, granularity: Option<Granularity>) -> Value {
+    let mut total = 0usize;
+    let mut formats: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut levels: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut errors: HashMap<String, (usize, &'static str, String)> = HashMap::new();
+    let mut entries: Vec<(Option<TimeKeys>, bool)> = vec![];
+
+    for line in input.lines().filter(|l| !l.trim().is_empty()) {
+        total += 1;
+        let entry = parse_line(line);
+        *formats.entry(entry.format).or_default() += 1;
+        *levels.entry(entry.level).or_default() += 1;
+
+        let is_error = matches!(entry.level, "error" | "fatal");
+        if is_error {
+            let key = VARIABLE.replace_all(&entry.message, "<*>");
+            let key: String = key.chars().take(200).collect();
+            let slot = errors
+                .entry(key)
+                .or_insert_with(|| (0, entry.level, entry.message.chars().take(500).collect()));
+            slot.0 += 1;
+        }
+        entries.push((entry.time, is_error));
+    }
+
+    let mut top_errors: Vec<(String, (usize, &str, String))> = errors.into_iter().collect();
+    top_errors.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then_with(|| a.0.cmp(&b.0)));
+    let top_errors: Vec<Value> = top_errors
+        .into_iter()
+        .take(top)
+        .map(|(pattern, (count, level, example))| {
+            json!({
+                "pattern": pattern,
+                "count": count,
+                "level": level,
+                "example": example,
+            })
+        })
+        .collect();
+
+    // Pick the finest granularity that keeps the bucket list readable
+    let granularity = granularity.unwrap_or_else(|| {
+        let distinct = |f: fn(&TimeKeys) -> &String| {
+            let mut keys: Vec<&String> =
+                entries.iter().filter_map(|e| e.0.as_ref().map(f)).collect();
+            keys.sort();
+            keys.dedup();
+            keys.len()
+        };
+        if distinct(|t| &t.2) <= 60 {
+            Granularity::Minute
+        } else if distinct(|t| &t.1) <= 48 {
+            Granularity::Hour
+        } else {
+            Granularity::Day
+        }
+    });
+
+    let mut buckets: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for (time, is_error) in &entries {
+        if let Some((day, hour, minute)) = time {
+            let key = match granularity {
+                Granularity::Minute => minute,
+                Granularity::Hour => hour,
+                Granularity::Day => day,
+            };
+            let slot = buckets.entry(key.as_str()).or_default();
+            slot.0 += 1;
+            if *is_error {
+                slot.1 += 1;
+            }
+        }
+    }
+    let timestamped = entries.iter().filter(|e| e.0.is_some()).count();
+    let buckets: Vec<Value> = buckets
+        .into_iter()
+        .map(|(time, (count, errors))| json!({ "time": time, "count": count, "errors": errors }))
+        .collect();
+
+    json!({
+        "lines": total,
+        "formats": formats,
+        "levels": levels,
+        "top_errors": top_errors,
+        "time_buckets": {
+            "granularity": granularity.name(),
+            "timestamped_lines": timestamped,
+            "buckets": buckets,
+        },
+    })
+}
+
+fn parse_line(line: &str) -> Entry {
+    let trimmed = line.trim();
+    if trimmed.starts_with('{') {
+        if let Ok(obj) = serde_json::from_str::<Value>(trimmed) {
+            return parse_json_line(&obj, trimmed);
+        }
+    }
+
+    if let Some(c) = SYSLOG.captures(line) {
+        let day = format!("{} {:>2}", &c[1], &c[2]);
+        let message = c[4].to_string();
+        return Entry {
+            format: "syslog",
+            level: level_from_text(&message),
+            time: Some((
+                day.clone(),
+                format!("{} {}", day, &c[3][..2]),
+                format!("{} {}", day, &c[3]),
+            )),
+            message,
+        };
+    }
+
+    // Only trust timestamps near the start so IDs in the message don't count
+    let head: String = line.chars().take(40).collect();
+    let ts = ISO_TS.captures(&head);
+    let time = ts.as_ref().map(|c| iso_keys(&c[1], &c[2]));
+
+    // Drop the timestamp/level prefix so identical messages group together
+    let prefix_end = LEVEL
+        .find(line)
+        .filter(|m| m.start() < 60)
+        .map(|m| m.end())
+        .or_else(|| ts.as_ref().and_then(|c| c.get(0)).map(|m| m.end()))
+        .unwrap_or(0);
+    let message = line[prefix_end..]
+        .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | ']' | '-' | '|'))
+        .trim_end();
+    let message = if message.is_empty() { trimmed } else { message };
+
+    Entry {
+        format: if time.is_some() {
+            "timestamped"
+        } else {
+            "plain"
+        },
+        level: level_from_text(line),
+        time,
+        message: message.to_string(),
+    }
+}
+
+fn parse_json_line(obj: &Value, raw: &str) -> Entry {
+    let field = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| obj.get(*k).filter(|v| !v.is_null()))
+    };
+    let level = field(&["level", "lvl", "severity", "log.level", "levelname"])
+        .and_then(|v| match v {
+            Value::String(s) => Some(normalize_level(s)),
+            // pino/bunyan numeric levels
+            Value::Number(n) => n.as_u64().map(|n| match n {
+                0..=10 => "trace",
+                11..=20 => "debug",
+                21..=30 => "info",
+                31..=40 => "warn",
+                41..=50 => "error",
+                _ => "fatal",
+            }),
+            _ => None,
+        })
+        .unwrap_or("unknown");
+    let message = field(&["msg", "message", "event", "error"])
+        .map(|v| {
+            v.as_str()
+                .map(String::from)
+                .unwrap_or_else(|| v.to_string())
+        })
+        .unwrap_or_else(|| raw.to_string());
+    let time = field(&["time", "timestamp", "ts", "@timestamp", "asctime", "date"]).and_then(|v| {
+        match v {
+            Value::String(s) => ISO_TS.captures(s).map(|c| iso_keys(&c[1], &c[2])),
+            Value::Number(n) => {
+                let secs = n.as_f64()?;
+                // Millisecond epochs are common in JS loggers
+                let secs = if secs > 1e11 { secs / 1000.0 } else { secs };
+                let dt = chrono::DateTime::from_timestamp(secs as i64, 0)?;
+                let s = dt.format("%Y-%m-%d %H:%M").to_string();
+                Some(iso_keys(&s[..10], &s[11..]))
+            }
+            _ => None,
+        }
+    });
+    Entry {
+        format: "json",
+        level,
+        time,
+        message,
+    }
+}
+
+fn iso_keys(date: &str, hm: &str) -> TimeKeys {
+    (
+        date.to_string(),
+        format!("{} {}", date, &hm[..2]),
+        format!("{} {}", date, hm),
+    )
+}
+
+fn level_from_text(text: &str) -> &'static str {
+    LEVEL
+        .captures(text)
+        .map(|c| normalize_level(&c[1]))
+        .unwrap_or("unknown")
+}
+
+fn normalize_level(level: &str) -> &'static str {
+    match level.to_ascii_lowercase().as_str() {
+        "trace" => "trace",
+        "debug" => "debug",
+        "info" | "notice" | "information" => "info",
+        "warn" | "warning" => "warn",
+        "error" | "err" => "error",
+        "fatal" | "crit" | "critical" | "panic" | "emerg" | "alert" => "fatal",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_formats() {
+        let logs = r#"{"level":"error","msg":"db timeout after 3000ms","time":"2024-05-01T10:00:12Z"}
+{"level":30,"msg":"request done","time":1714557620000}
+2024-05-01 10:01:02 ERROR db timeout after 5000ms
+2024-05-01 10:01:03 WARN slow query
+May  1 10:02:00 web sshd[42]: error: connection reset
+"#;
+        let summary = summarize(logs, 5, None);
+        assert_eq!(summary["lines"], 5);
+        assert_eq!(summary["formats"]["json"], 2);
+        assert_eq!(summary["formats"]["syslog"], 1);
+        assert_eq!(summary["levels"]["error"], 3);
+        assert_eq!(summary["levels"]["info"], 1);
+        assert_eq!(summary["top_errors"][0]["count"], 2);
+        assert_eq!(summary["time_buckets"]["granularity"], "minute");
+    }
+
+    #[test]
+    fn test_error_grouping() {
+        let logs = "ERROR user 12 not found\nERROR user 98 not found\nERROR disk full\n";
+        let summary = summarize(logs, 1, Some(Granularity::Hour));
+        let top = summary["top_errors"].as_array().unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0]["pattern"], "user <*> not found");
+        assert_eq!(summary["time_buckets"]["timestamped_lines"], 0);
+    }
+}
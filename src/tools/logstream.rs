@@ -0,0 +1,172 @@
+// modern-cli-mcp/src/tools/logstream.rs
+//! Bounded log following with resumable cursors.
+//!
+//! Followed logs are read with timestamps on, so every line starts with the
+//! RFC 3339 time Kubernetes recorded it at. The newest time seen per
+//! namespace/pod/container is the cursor for that stream. Resuming asks the
+//! CLI for a window reaching back to the oldest cursor and drops lines at or
+//! before each stream's own cursor, so nothing is returned twice and nothing
+//! between two calls is lost.
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Lines kept per stream; older lines are counted as dropped
+pub const MAX_LINES_PER_STREAM: usize = 500;
+
+/// `(timestamp, message)` for a line that starts with an RFC 3339 time
+pub fn split_timestamp(line: &str) -> Option<(DateTime<Utc>, &str)> {
+    let (ts, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let time = DateTime::parse_from_rfc3339(ts).ok()?.with_timezone(&Utc);
+    Some((time, rest))
+}
+
+/// A log line and the time it was recorded, when it carried one
+type Line = (Option<DateTime<Utc>>, String);
+
+/// Lines per stream, keyed `namespace/pod/container`
+#[derive(Debug, Default)]
+pub struct Streams {
+    streams: BTreeMap<String, Vec<Line>>,
+    skipped: usize,
+}
+
+impl Streams {
+    /// Add a line unless its time is at or before the stream's cursor
+    pub fn push(&mut self, key: &str, line: &str, cursors: &Map<String, Value>) {
+        let (time, message) = match split_timestamp(line) {
+            Some((time, message)) => (Some(time), message),
+            None => (None, line),
+        };
+        let cursor = cursors
+            .get(key)
+            .and_then(|c| c.as_str())
+            .and_then(|c| DateTime::parse_from_rfc3339(c).ok());
+        if let (Some(time), Some(cursor)) = (time, cursor) {
+            if time <= cursor {
+                self.skipped += 1;
+                return;
+            }
+        }
+        self.streams
+            .entry(key.to_string())
+            .or_default()
+            .push((time, message.to_string()));
+    }
+
+    /// Lines of `stern --output json --timestamps`
+    pub fn push_stern(&mut self, output: &str, cursors: &Map<String, Value>) {
+        for entry in output
+            .lines()
+            .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+        {
+            let key = stream_key(
+                entry["namespace"].as_str().unwrap_or(""),
+                entry["podName"].as_str().unwrap_or(""),
+                entry["containerName"].as_str().unwrap_or(""),
+            );
+            let message = entry["message"].as_str().unwrap_or("");
+            self.push(&key, message.trim_end_matches('\n'), cursors);
+        }
+    }
+
+    /// Per-stream lines plus the cursors to resume from, carrying over
+    /// cursors of streams that had nothing new
+    pub fn finish(self, mut cursors: Map<String, Value>) -> Value {
+        let mut streams = vec![];
+        let mut total = 0;
+        for (key, lines) in self.streams {
+            if let Some(last) = lines.iter().rev().find_map(|(t, _)| *t) {
+                cursors.insert(key.clone(), json!(last.to_rfc3339()));
+            }
+            total += lines.len();
+            let dropped = lines.len().saturating_sub(MAX_LINES_PER_STREAM);
+            let mut parts = key.splitn(3, '/');
+            streams.push(json!({
+                "namespace": parts.next(),
+                "pod": parts.next(),
+                "container": parts.next(),
+                "count": lines.len(),
+                "dropped": dropped,
+                "lines": lines[dropped..]
+                    .iter()
+                    .map(|(t, m)| json!({ "time": t.map(|t| t.to_rfc3339()), "message": m }))
+                    .collect::<Vec<_>>(),
+            }));
+        }
+        json!({
+            "lines": total,
+            "already_seen": self.skipped,
+            "streams": streams,
+            "cursors": cursors,
+        })
+    }
+}
+
+/// Cursor key for a stream
+pub fn stream_key(namespace: &str, pod: &str, container: &str) -> String {
+    format!("{}/{}/{}", namespace, pod, container)
+}
+
+/// Oldest cursor, to ask for everything after it
+pub fn oldest_cursor(cursors: &Map<String, Value>) -> Option<DateTime<Utc>> {
+    cursors
+        .values()
+        .filter_map(|c| c.as_str())
+        .filter_map(|c| DateTime::parse_from_rfc3339(c).ok())
+        .map(|t| t.with_timezone(&Utc))
+        .min()
+}
+
+/// `--since` window (whole seconds, rounded up) reaching back to `cursor`
+pub fn since_window(cursor: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let millis = (now - cursor).num_milliseconds().max(0);
+    format!("{}s", (millis + 999) / 1000 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streams_and_cursors() {
+        let output = r#"{"message":"2024-05-01T10:00:00.000000001Z starting\n","namespace":"prod","podName":"api-1","containerName":"app"}
+{"message":"2024-05-01T10:00:01Z ready","namespace":"prod","podName":"api-1","containerName":"app"}
+{"message":"2024-05-01T10:00:02Z hello","namespace":"prod","podName":"api-2","containerName":"app"}
+not json"#;
+        let mut cursors = Map::new();
+        cursors.insert(
+            "prod/api-1/app".into(),
+            json!("2024-05-01T10:00:00.000000001Z"),
+        );
+        cursors.insert("prod/gone/app".into(), json!("2024-04-30T00:00:00Z"));
+
+        let mut streams = Streams::default();
+        streams.push_stern(output, &cursors);
+        let result = streams.finish(cursors);
+        assert_eq!(result["lines"], 2);
+        assert_eq!(result["already_seen"], 1);
+        assert_eq!(result["streams"][0]["pod"], "api-1");
+        assert_eq!(result["streams"][0]["lines"][0]["message"], "ready");
+        assert_eq!(
+            result["cursors"]["prod/api-2/app"],
+            "2024-05-01T10:00:02+00:00"
+        );
+        // Streams without new lines keep their cursor
+        assert_eq!(result["cursors"]["prod/gone/app"], "2024-04-30T00:00:00Z");
+    }
+
+    #[test]
+    fn test_since_window() {
+        let mut cursors = Map::new();
+        cursors.insert("a".into(), json!("2024-05-01T10:00:30Z"));
+        cursors.insert("b".into(), json!("2024-05-01T10:00:00.5Z"));
+        let oldest = oldest_cursor(&cursors).unwrap();
+        let now = DateTime::parse_from_rfc3339("2024-05-01T10:01:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(since_window(oldest, now), "61s");
+        assert!(split_timestamp("plain line").is_none());
+    }
+}
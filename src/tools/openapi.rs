@@ -0,0 +1,459 @@
+// modern-cli-mcp/src/tools/openapi.rs
+//! OpenAPI (3.x) and Swagger (2.0) specs as callable operations.
+//!
+//! A spec is reduced to its operations: method, path template, parameters
+//! and whether a body is expected. Arguments for an operation are checked
+//! against those parameters before a request is built, so a typo'd or
+//! missing parameter fails here instead of as an opaque 400 from the API.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// One operation parameter
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Param {
+    pub name: String,
+    /// path, query, header, or cookie
+    #[serde(rename = "in")]
+    pub location: String,
+    pub required: bool,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<String>,
+    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<Value>,
+}
+
+/// One API operation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Operation {
+    /// operationId, or `method_path` when the spec has none
+    pub id: String,
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub params: Vec<Param>,
+    /// None when the operation takes no body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_required: Option<bool>,
+}
+
+/// A request ready to send
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ApiRequest {
+    pub method: String,
+    pub url: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Header and query names whose values are credentials
+const SECRET_NAMES: &[&str] = &[
+    "authorization",
+    "cookie",
+    "token",
+    "secret",
+    "password",
+    "apikey",
+    "api-key",
+    "api_key",
+    "session",
+    "signature",
+];
+
+impl ApiRequest {
+    /// Copy with credential values masked, for showing a request back
+    pub fn redacted(&self) -> Self {
+        let mask = |pairs: &[(String, String)]| {
+            pairs
+                .iter()
+                .map(|(name, value)| {
+                    let lower = name.to_ascii_lowercase();
+                    match SECRET_NAMES.iter().any(|s| lower.contains(s)) {
+                        true => (name.clone(), "***".to_string()),
+                        false => (name.clone(), value.clone()),
+                    }
+                })
+                .collect()
+        };
+        Self {
+            query: mask(&self.query),
+            headers: mask(&self.headers),
+            ..self.clone()
+        }
+    }
+}
+
+/// Follow a local `#/...` reference
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    match value["$ref"].as_str().and_then(|r| r.strip_prefix('#')) {
+        Some(pointer) => spec.pointer(pointer).unwrap_or(value),
+        None => value,
+    }
+}
+
+fn parse_param(spec: &Value, raw: &Value) -> Option<Param> {
+    let p = resolve(spec, raw);
+    let location = p["in"].as_str()?;
+    if location == "body" || location == "formData" {
+        // Swagger 2 bodies are handled as the request body
+        return None;
+    }
+    // OpenAPI 3 nests the type under schema; Swagger 2 has it inline
+    let schema = resolve(
+        spec,
+        if p["schema"].is_object() {
+            &p["schema"]
+        } else {
+            p
+        },
+    );
+    Some(Param {
+        name: p["name"].as_str()?.to_string(),
+        location: location.to_string(),
+        required: location == "path" || p["required"].as_bool().unwrap_or(false),
+        schema_type: schema["type"].as_str().map(String::from),
+        allowed: schema["enum"].as_array().cloned().unwrap_or_default(),
+    })
+}
+
+/// Every operation in the spec, in path order
+pub fn operations(spec: &Value) -> Vec<Operation> {
+    let Some(paths) = spec["paths"].as_object() else {
+        return vec![];
+    };
+    let mut ops = vec![];
+    for (path, item) in paths {
+        let item = resolve(spec, item);
+        let shared: Vec<Param> = item["parameters"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| parse_param(spec, p))
+            .collect();
+        for method in METHODS {
+            let Some(op) = item.get(*method).filter(|o| o.is_object()) else {
+                continue;
+            };
+            let mut params: Vec<Param> = op["parameters"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|p| parse_param(spec, p))
+                .collect();
+            // Operation parameters override path-level ones of the same name and location
+            for p in &shared {
+                if !params
+                    .iter()
+                    .any(|q| q.name == p.name && q.location == p.location)
+                {
+                    params.push(p.clone());
+                }
+            }
+
+            let swagger_body = op["parameters"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|p| resolve(spec, p))
+                .find(|p| matches!(p["in"].as_str(), Some("body" | "formData")));
+            let body_required = match (resolve(spec, &op["requestBody"]), swagger_body) {
+                (rb, _) if rb.is_object() => Some(rb["required"].as_bool().unwrap_or(false)),
+                (_, Some(p)) => Some(p["required"].as_bool().unwrap_or(false)),
+                _ => None,
+            };
+
+            ops.push(Operation {
+                id: op["operationId"]
+                    .as_str()
+                    .map(String::from)
+                    .unwrap_or_else(|| {
+                        let slug: String = path
+                            .chars()
+                            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                            .collect();
+                        format!("{}{}", method, slug.trim_end_matches('_'))
+                    }),
+                method: method.to_uppercase(),
+                path: path.clone(),
+                summary: op["summary"]
+                    .as_str()
+                    .or(op["description"].as_str())
+                    .map(|s| s.lines().next().unwrap_or("").to_string()),
+                params,
+                body_required,
+            });
+        }
+    }
+    ops
+}
+
+/// Base URL from `servers` (OpenAPI 3) or `host`/`basePath` (Swagger 2).
+/// Relative server URLs are resolved against the spec's own URL.
+pub fn base_url(spec: &Value, spec_source: &str) -> Option<String> {
+    let url = match spec["servers"][0]["url"].as_str() {
+        Some(url) => url.to_string(),
+        None => {
+            let host = spec["host"].as_str()?;
+            let scheme = spec["schemes"][0].as_str().unwrap_or("https");
+            format!(
+                "{}://{}{}",
+                scheme,
+                host,
+                spec["basePath"].as_str().unwrap_or("")
+            )
+        }
+    };
+    if url.contains("://") {
+        return Some(url.trim_end_matches('/').to_string());
+    }
+    // Relative server URL: take scheme and host from where the spec came from
+    let rest = spec_source.split_once("://")?;
+    let host = rest.1.split('/').next()?;
+    Some(format!(
+        "{}://{}{}",
+        rest.0,
+        host,
+        url.trim_end_matches('/')
+    ))
+}
+
+fn value_matches(param: &Param, value: &Value) -> bool {
+    let type_ok = match (param.schema_type.as_deref(), value) {
+        (Some("integer"), Value::Number(n)) => n.is_i64() || n.is_u64(),
+        (Some("integer"), Value::String(s)) => s.parse::<i64>().is_ok(),
+        (Some("number"), Value::Number(_)) => true,
+        (Some("number"), Value::String(s)) => s.parse::<f64>().is_ok(),
+        (Some("boolean"), Value::Bool(_)) => true,
+        (Some("boolean"), Value::String(s)) => s == "true" || s == "false",
+        (Some("array"), _) => true,
+        (Some("string"), Value::String(_) | Value::Number(_) | Value::Bool(_)) => true,
+        (None, _) => true,
+        _ => false,
+    };
+    let enum_ok = param.allowed.is_empty()
+        || param
+            .allowed
+            .iter()
+            .any(|a| a == value || a.as_str() == Some(&as_text(value)));
+    type_ok && enum_ok
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Percent-encode a path segment or query string key/value
+pub fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Check `args` against the operation and build the request, or list
+/// every problem found
+pub fn build_request(
+    op: &Operation,
+    base: &str,
+    args: &Map<String, Value>,
+    body: Option<&str>,
+) -> Result<ApiRequest, Vec<String>> {
+    let mut errors = vec![];
+    for name in args.keys() {
+        if !op.params.iter().any(|p| &p.name == name) {
+            let known: Vec<&str> = op.params.iter().map(|p| p.name.as_str()).collect();
+            errors.push(format!(
+                "unknown parameter '{}' (expected one of: {})",
+                name,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            ));
+        }
+    }
+
+    let mut request = ApiRequest {
+        method: op.method.clone(),
+        url: String::new(),
+        query: vec![],
+        headers: vec![],
+        body: body.map(String::from),
+    };
+    let mut path = op.path.clone();
+    for param in &op.params {
+        let Some(value) = args.get(&param.name) else {
+            if param.required {
+                errors.push(format!(
+                    "missing required {} parameter '{}'",
+                    param.location, param.name
+                ));
+            }
+            continue;
+        };
+        if !value_matches(param, value) {
+            let expected = match (&param.schema_type, param.allowed.is_empty()) {
+                (_, false) => format!("one of {}", Value::Array(param.allowed.clone())),
+                (Some(t), true) => t.clone(),
+                (None, true) => "a value".into(),
+            };
+            errors.push(format!(
+                "parameter '{}' must be {}, got {}",
+                param.name, expected, value
+            ));
+            continue;
+        }
+        let values: Vec<String> = match value {
+            Value::Array(items) => items.iter().map(as_text).collect(),
+            other => vec![as_text(other)],
+        };
+        match param.location.as_str() {
+            "path" => {
+                path = path.replace(
+                    &format!("{{{}}}", param.name),
+                    &percent_encode(&values.join(",")),
+                )
+            }
+            "query" => request
+                .query
+                .extend(values.into_iter().map(|v| (param.name.clone(), v))),
+            "header" => request.headers.push((param.name.clone(), values.join(","))),
+            "cookie" => request.headers.push((
+                "Cookie".into(),
+                format!("{}={}", param.name, values.join(",")),
+            )),
+            _ => {}
+        }
+    }
+
+    match op.body_required {
+        Some(true) if body.is_none() => errors.push("request body is required".into()),
+        None if body.is_some() => {
+            errors.push(format!("{} {} takes no request body", op.method, op.path))
+        }
+        _ => {}
+    }
+    if let Some(body) = body {
+        if serde_json::from_str::<Value>(body).is_err() {
+            errors.push("body must be valid JSON".into());
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    request.url = format!("{}{}", base.trim_end_matches('/'), path);
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.0.0",
+            "servers": [{"url": "/v1"}],
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [{"$ref": "#/components/parameters/PetId"}],
+                    "get": {
+                        "operationId": "getPet",
+                        "summary": "Get a pet",
+                        "parameters": [
+                            {"name": "fields", "in": "query", "schema": {"type": "string", "enum": ["name", "all"]}},
+                            {"name": "X-Trace", "in": "header", "schema": {"type": "string"}}
+                        ]
+                    },
+                    "put": {"requestBody": {"required": true}}
+                }
+            },
+            "components": {"parameters": {"PetId": {"name": "petId", "in": "path", "schema": {"type": "integer"}}}}
+        })
+    }
+
+    #[test]
+    fn test_operations() {
+        let spec = spec();
+        let ops = operations(&spec);
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[0].id, "getPet");
+        assert_eq!(ops[0].params.len(), 3);
+        assert!(ops[0]
+            .params
+            .iter()
+            .any(|p| p.name == "petId" && p.required));
+        assert_eq!(ops[1].id, "put_pets__petId");
+        assert_eq!(ops[1].body_required, Some(true));
+        assert_eq!(
+            base_url(&spec, "https://api.example.com/openapi.json").as_deref(),
+            Some("https://api.example.com/v1")
+        );
+        let swagger =
+            json!({"swagger": "2.0", "host": "h.io", "basePath": "/api", "schemes": ["http"]});
+        assert_eq!(
+            base_url(&swagger, "spec.yaml").as_deref(),
+            Some("http://h.io/api")
+        );
+    }
+
+    #[test]
+    fn test_build_request() {
+        let ops = operations(&spec());
+        let args = json!({"petId": 7, "fields": "all", "X-Trace": "abc"});
+        let req = build_request(&ops[0], "https://x/v1", args.as_object().unwrap(), None).unwrap();
+        assert_eq!(req.url, "https://x/v1/pets/7");
+        assert_eq!(req.query, [("fields".to_string(), "all".to_string())]);
+        assert_eq!(req.headers[0].0, "X-Trace");
+
+        let bad = json!({"petId": "seven", "fields": "some", "color": "red"});
+        let errors = build_request(&ops[0], "", bad.as_object().unwrap(), Some("{}")).unwrap_err();
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("unknown parameter 'color'")));
+        assert!(errors.iter().any(|e| e.contains("must be integer")));
+
+        let errors =
+            build_request(&ops[1], "", json!({"petId": 1}).as_object().unwrap(), None).unwrap_err();
+        assert_eq!(errors, ["request body is required"]);
+    }
+
+    #[test]
+    fn test_redacted() {
+        let pair = |k: &str, v: &str| (k.to_string(), v.to_string());
+        let req = ApiRequest {
+            method: "GET".into(),
+            url: "https://x/v1".into(),
+            query: vec![pair("api_key", "k1"), pair("page", "2")],
+            headers: vec![
+                pair("Authorization", "Bearer abc"),
+                pair("Cookie", "sid=1"),
+                pair("X-Auth-Token", "t"),
+                pair("X-Trace", "abc"),
+            ],
+            body: None,
+        };
+        let shown = req.redacted();
+        assert_eq!(shown.query, [pair("api_key", "***"), pair("page", "2")]);
+        let values: Vec<&str> = shown.headers.iter().map(|(_, v)| v.as_str()).collect();
+        assert_eq!(values, ["***", "***", "***", "abc"]);
+        assert_eq!(shown.url, req.url);
+    }
+}
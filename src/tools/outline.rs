@@ -0,0 +1,258 @@
+// modern-cli-mcp/src/tools/outline.rs
+//! Source outlines from ast-grep's tree-sitter parse.
+//!
+//! `outline_read` lists a file's functions, types and impls with their line
+//! and byte ranges, then reads a single symbol by name instead of the whole
+//! file. The symbols come from `sg scan` with one inline rule per node kind,
+//! capturing the node's name field.
+
+use serde::Serialize;
+use std::path::Path;
+
+/// Symbol kinds per language: (label, tree-sitter node kind, name field)
+fn kinds(language: &str) -> &'static [(&'static str, &'static str, &'static str)] {
+    match language {
+        "rust" => &[
+            ("function", "function_item", "name"),
+            ("struct", "struct_item", "name"),
+            ("enum", "enum_item", "name"),
+            ("trait", "trait_item", "name"),
+            ("impl", "impl_item", "type"),
+            ("module", "mod_item", "name"),
+            ("macro", "macro_definition", "name"),
+            ("type", "type_item", "name"),
+        ],
+        "python" => &[
+            ("function", "function_definition", "name"),
+            ("class", "class_definition", "name"),
+        ],
+        "javascript" => &[
+            ("function", "function_declaration", "name"),
+            ("class", "class_declaration", "name"),
+            ("method", "method_definition", "name"),
+        ],
+        "typescript" | "tsx" => &[
+            ("function", "function_declaration", "name"),
+            ("class", "class_declaration", "name"),
+            ("method", "method_definition", "name"),
+            ("interface", "interface_declaration", "name"),
+            ("type", "type_alias_declaration", "name"),
+            ("enum", "enum_declaration", "name"),
+        ],
+        "go" => &[
+            ("function", "function_declaration", "name"),
+            ("method", "method_declaration", "name"),
+            ("type", "type_spec", "name"),
+        ],
+        "java" => &[
+            ("class", "class_declaration", "name"),
+            ("interface", "interface_declaration", "name"),
+            ("enum", "enum_declaration", "name"),
+            ("method", "method_declaration", "name"),
+            ("constructor", "constructor_declaration", "name"),
+        ],
+        "c" => &[
+            ("function", "function_definition", "declarator"),
+            ("struct", "struct_specifier", "name"),
+        ],
+        "cpp" => &[
+            ("function", "function_definition", "declarator"),
+            ("struct", "struct_specifier", "name"),
+            ("class", "class_specifier", "name"),
+            ("namespace", "namespace_definition", "name"),
+        ],
+        _ => &[],
+    }
+}
+
+/// ast-grep language for a file extension
+pub fn language_for(path: &Path) -> Option<&'static str> {
+    Some(match path.extension()?.to_str()? {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "go" => "go",
+        "java" => "java",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "cxx" | "hpp" | "hh" => "cpp",
+        _ => return None,
+    })
+}
+
+/// Inline rules for `sg scan --inline-rules`, or None for unsupported languages
+pub fn rules(language: &str) -> Option<String> {
+    let kinds = kinds(language);
+    if kinds.is_empty() {
+        return None;
+    }
+    let rules: Vec<String> = kinds
+        .iter()
+        .map(|(label, kind, field)| {
+            format!(
+                "id: {label}\nlanguage: {language}\nrule:\n  kind: {kind}\n  has:\n    field: {field}\n    pattern: $NAME\n"
+            )
+        })
+        .collect();
+    Some(rules.join("---\n"))
+}
+
+/// One symbol in a file; lines are 1-based and inclusive
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    pub line_start: u64,
+    pub line_end: u64,
+    pub byte_start: u64,
+    pub byte_end: u64,
+}
+
+impl Symbol {
+    /// `Container::name` (or `.` outside Rust/C++), or the bare name
+    pub fn qualified(&self, language: &str) -> String {
+        let sep = if matches!(language, "rust" | "cpp") {
+            "::"
+        } else {
+            "."
+        };
+        match &self.container {
+            Some(c) => format!("{}{}{}", c, sep, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Build the outline from `sg scan --json=stream` output
+pub fn parse_outline(output: &str) -> Vec<Symbol> {
+    let mut symbols: Vec<Symbol> = output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|m| {
+            let range = &m["range"];
+            let raw_name = m["metaVariables"]["single"]["NAME"]["text"].as_str()?;
+            Some(Symbol {
+                name: clean_name(raw_name),
+                kind: m["ruleId"].as_str()?.to_string(),
+                container: None,
+                line_start: range["start"]["line"].as_u64()? + 1,
+                line_end: range["end"]["line"].as_u64()? + 1,
+                byte_start: range["byteOffset"]["start"].as_u64()?,
+                byte_end: range["byteOffset"]["end"].as_u64()?,
+            })
+        })
+        .collect();
+    symbols.sort_by_key(|s| (s.byte_start, std::cmp::Reverse(s.byte_end)));
+    symbols.dedup_by(|a, b| a.byte_start == b.byte_start && a.byte_end == b.byte_end);
+
+    // The innermost enclosing symbol is the container
+    let spans: Vec<(u64, u64, String)> = symbols
+        .iter()
+        .map(|s| (s.byte_start, s.byte_end, s.name.clone()))
+        .collect();
+    for symbol in symbols.iter_mut() {
+        symbol.container = spans
+            .iter()
+            .filter(|(start, end, _)| {
+                *start <= symbol.byte_start
+                    && symbol.byte_end <= *end
+                    && (*start, *end) != (symbol.byte_start, symbol.byte_end)
+            })
+            .min_by_key(|(start, end, _)| end - start)
+            .map(|(_, _, name)| name.clone());
+    }
+    symbols
+}
+
+/// C declarators carry the parameter list (`*parse(char *s)`); keep the name
+fn clean_name(raw: &str) -> String {
+    let head = raw.split('(').next().unwrap_or(raw);
+    head.trim_start_matches(['*', '&', ' '])
+        .split_whitespace()
+        .last()
+        .unwrap_or(head)
+        .to_string()
+}
+
+/// Symbols matching `query` by name or qualified name
+pub fn find<'a>(symbols: &'a [Symbol], query: &str, language: &str) -> Vec<&'a Symbol> {
+    symbols
+        .iter()
+        .filter(|s| s.name == query || s.qualified(language) == query)
+        .collect()
+}
+
+/// First line of a symbol including the doc comments, attributes and
+/// decorators directly above it
+pub fn leading_start(lines: &[&str], line_start: u64) -> u64 {
+    let mut start = line_start as usize;
+    while start > 1 {
+        let above = lines.get(start - 2).map(|l| l.trim_start()).unwrap_or("");
+        let attached = ["///", "//!", "//", "#[", "@", "/**", "* ", "*/", "#"]
+            .iter()
+            .any(|p| above.starts_with(p))
+            || above == "*";
+        if !attached {
+            break;
+        }
+        start -= 1;
+    }
+    start as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sg_match(rule: &str, name: &str, lines: (u64, u64), bytes: (u64, u64)) -> String {
+        serde_json::json!({
+            "ruleId": rule,
+            "range": {
+                "byteOffset": {"start": bytes.0, "end": bytes.1},
+                "start": {"line": lines.0, "column": 0},
+                "end": {"line": lines.1, "column": 1}
+            },
+            "metaVariables": {"single": {"NAME": {"text": name}}, "multi": {}}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_parse_outline() {
+        let output = [
+            sg_match("impl", "Parser", (4, 20), (60, 400)),
+            sg_match("function", "parse", (6, 10), (100, 200)),
+            sg_match("struct", "Parser", (0, 2), (0, 40)),
+        ]
+        .join("\n");
+        let symbols = parse_outline(&output);
+        assert_eq!(symbols.len(), 3);
+        assert_eq!(symbols[0].kind, "struct");
+        assert_eq!(symbols[2].line_start, 7);
+        assert_eq!(symbols[2].container.as_deref(), Some("Parser"));
+        assert_eq!(symbols[2].qualified("rust"), "Parser::parse");
+
+        assert_eq!(find(&symbols, "Parser::parse", "rust").len(), 1);
+        assert_eq!(find(&symbols, "Parser", "rust").len(), 2);
+
+        assert_eq!(
+            clean_name("*parse_args(int argc, char **argv)"),
+            "parse_args"
+        );
+        assert!(rules("rust")
+            .unwrap()
+            .contains("kind: impl_item\n  has:\n    field: type"));
+        assert!(rules("cobol").is_none());
+    }
+
+    #[test]
+    fn test_leading_start() {
+        let source = "use x;\n\n/// Docs\n#[inline]\nfn a() {}\n";
+        let lines: Vec<&str> = source.lines().collect();
+        assert_eq!(leading_start(&lines, 5), 3);
+        assert_eq!(leading_start(&lines, 1), 1);
+    }
+}
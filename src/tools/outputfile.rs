@@ -0,0 +1,104 @@
+// modern-cli-mcp/src/tools/outputfile.rs
+//! `output_path` for text tools.
+//!
+//! A text tool can write its result to a file instead of returning it, so a
+//! large transform never passes through the conversation. The target is
+//! checked like any other write: absolute, not blocked by .agentignore,
+//! inside the allowed roots and in a trusted workspace. An existing file is
+//! replaced unless the caller moves it to the graveyard first.
+
+use crate::ignore::AgentIgnore;
+use crate::trust::Trust;
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// `output_path` once it is fit to write to
+pub fn check<'a>(
+    output_path: &'a str,
+    ignore: &AgentIgnore,
+    trust: &Trust,
+) -> Result<&'a Path, String> {
+    let path = Path::new(output_path);
+    if !path.is_absolute() {
+        return Err("output_path must be absolute".into());
+    }
+    ignore.validate_path(path)?;
+    trust.check(path)?;
+    Ok(path)
+}
+
+/// Write `content` to `path`, replacing whatever is there
+pub async fn write(tool: &str, path: &Path, content: &str) -> Result<Value, String> {
+    let replaced = path.exists();
+    tokio::fs::write(path, content)
+        .await
+        .map_err(|e| format!("Failed to write output: {}", e))?;
+    Ok(json!({
+        "success": true,
+        "tool": tool,
+        "output_path": path.display().to_string(),
+        "bytes_written": content.len(),
+        "lines_written": content.lines().count(),
+        "replaced": replaced,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trusted(dir: &Path) -> Trust {
+        Trust::with_file(true, &[dir.to_path_buf()], None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_write_and_overwrite() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("out.json");
+        let target_str = target.to_string_lossy();
+        let path = check(&target_str, &AgentIgnore::default(), &trusted(tmp.path())).unwrap();
+
+        let written = write("jq", path, "{\"a\": 1}\n{\"b\": 2}\n").await.unwrap();
+        assert_eq!(written["bytes_written"], 18);
+        assert_eq!(written["lines_written"], 2);
+        assert_eq!(written["replaced"], false);
+
+        // A second write replaces the first in full
+        let written = write("jq", path, "[]\n").await.unwrap();
+        assert_eq!(written["replaced"], true);
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "[]\n");
+
+        let missing_dir = tmp.path().join("missing/out.json");
+        assert!(write("jq", &missing_dir, "x").await.is_err());
+    }
+
+    #[test]
+    fn test_check_refuses_blocked_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("project")).unwrap();
+        std::fs::write(root.join("project/.agentignore"), "*.secret\n").unwrap();
+        let ignore = AgentIgnore::default()
+            .with_roots(&[root.join("project")])
+            .unwrap();
+        let trust = trusted(&root);
+
+        let out = |rel: &str| root.join(rel).to_string_lossy().into_owned();
+        assert!(check(&out("project/out.txt"), &ignore, &trust).is_ok());
+        assert!(check("out.txt", &ignore, &trust)
+            .unwrap_err()
+            .contains("must be absolute"));
+        assert!(check(&out("project/keys.secret"), &ignore, &trust)
+            .unwrap_err()
+            .contains(".agentignore"));
+        assert!(check(&out("elsewhere.txt"), &ignore, &trust)
+            .unwrap_err()
+            .contains("outside the allowed roots"));
+        let untrusted = Trust::with_file(true, &[], None).unwrap();
+        assert!(
+            check(&out("project/out.txt"), &AgentIgnore::default(), &untrusted)
+                .unwrap_err()
+                .contains("not trusted")
+        );
+    }
+}
@@ -0,0 +1,117 @@
+// modern-cli-mcp/src/tools/patchfiles.rs
+//! Patch files exported by format_patch and export_diff.
+//!
+//! git runs inside the repository, so an `output` it is given, and the file
+//! names it prints back, are relative to the repository, which is itself
+//! relative to the call's working directory.
+
+use crate::workdir;
+use std::path::PathBuf;
+
+/// Where `output` lands when git writes it from inside `repo`
+pub fn output_path(repo: &str, output: &str) -> PathBuf {
+    workdir::resolve(repo).join(output)
+}
+
+/// The patch files `git format-patch -o` listed on stdout, and their
+/// contents concatenated
+pub fn read_written<'a>(repo: &str, stdout: &'a str) -> (Vec<&'a str>, String) {
+    let files: Vec<&str> = stdout.lines().filter(|l| !l.is_empty()).collect();
+    let mut patch = String::new();
+    for file in &files {
+        patch.push_str(&std::fs::read_to_string(output_path(repo, file)).unwrap_or_default());
+    }
+    (files, patch)
+}
+
+/// Commit subjects from format-patch mailbox output, without the `[PATCH n/m]` prefix
+pub fn subjects(mbox: &str) -> Vec<String> {
+    mbox.lines()
+        .filter_map(|l| l.strip_prefix("Subject: "))
+        .map(|s| match s.strip_prefix("[PATCH") {
+            Some(rest) => rest.split_once("] ").map_or(rest, |(_, s)| s).to_string(),
+            None => s.to_string(),
+        })
+        .collect()
+}
+
+/// The diff part of each message in format-patch mailbox output; the mail
+/// headers, diffstat and `-- ` signature would otherwise read as hunk lines
+pub fn mbox_diffs(mbox: &str) -> String {
+    let mut diffs = String::new();
+    let mut in_diff = false;
+    for line in mbox.lines() {
+        if line.starts_with("diff --git ") {
+            in_diff = true;
+        } else if line == "-- " || (line.starts_with("From ") && line.ends_with(" 2001")) {
+            in_diff = false;
+        }
+        if in_diff {
+            diffs.push_str(line);
+            diffs.push('\n');
+        }
+    }
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MBOX: &str = "From 1111111111111111111111111111111111111111 Mon Sep 17 00:00:00 2001
+From: Ada <ada@example.com>
+Subject: [PATCH 1/2] Fix parser
+---
+ src/lib.rs | 2 +-
+ 1 file changed, 1 insertion(+), 1 deletion(-)
+
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1 @@
+-old
++new
+-- 
+2.45.0
+";
+
+    #[tokio::test]
+    async fn test_written_patches_resolve_against_cwd() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("repo/out")).unwrap();
+        std::fs::write(tmp.path().join("repo/out/0001-Fix-parser.patch"), MBOX).unwrap();
+
+        let cwd = tmp.path().join("repo");
+        workdir::scope(cwd.clone(), async {
+            assert_eq!(output_path(".", "fix.patch"), cwd.join("fix.patch"));
+            assert_eq!(
+                output_path(".", "/tmp/fix.patch"),
+                PathBuf::from("/tmp/fix.patch")
+            );
+
+            let (files, patch) = read_written(".", "out/0001-Fix-parser.patch\n\n");
+            assert_eq!(files, ["out/0001-Fix-parser.patch"]);
+            assert_eq!(patch, MBOX);
+        })
+        .await;
+
+        // Outside the call's directory the same relative names find nothing
+        let (_, patch) = read_written("repo", "out/0001-Fix-parser.patch\n");
+        assert!(patch.is_empty());
+        workdir::scope(tmp.path().to_path_buf(), async {
+            let (_, patch) = read_written("repo", "out/0001-Fix-parser.patch\n");
+            assert_eq!(patch, MBOX);
+        })
+        .await;
+    }
+
+    #[test]
+    fn test_subjects_and_diffs() {
+        assert_eq!(subjects(MBOX), ["Fix parser"]);
+        assert_eq!(subjects("Subject: Plain\n"), ["Plain"]);
+        let diffs = mbox_diffs(MBOX);
+        assert!(diffs.starts_with("diff --git a/src/lib.rs"));
+        assert!(diffs.ends_with("+new\n"));
+        assert!(!diffs.contains("1 file changed"));
+    }
+}
@@ -0,0 +1,235 @@
+// modern-cli-mcp/src/tools/patching.rs
+//! Reading GNU `patch` results back into per-hunk outcomes.
+//!
+//! `patch` reports fuzz, offsets and failures as prose on stdout and writes
+//! failed hunks to a reject file. These helpers turn both into structured
+//! data so a partially applied patch can be reported hunk by hunk.
+
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+/// Outcome of one hunk
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HunkResult {
+    pub number: usize,
+    pub header: String,
+    /// applied, failed, or ignored (already applied / reversed)
+    pub status: &'static str,
+    /// Line the hunk was applied at, or expected at when it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fuzz: Option<u32>,
+}
+
+/// A hunk that could not be applied
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Conflict {
+    pub file: String,
+    pub hunk: String,
+    pub reason: String,
+    /// The rejected hunk, for applying by hand
+    pub text: String,
+}
+
+/// How a patch was applied, hunk by hunk
+#[derive(Debug, Clone)]
+pub struct PatchOutcome {
+    /// patch (clean or fuzzy), 3way (merged against the git base), or partial
+    pub method: &'static str,
+    pub hunks: Vec<HunkResult>,
+    pub conflicts: Vec<Conflict>,
+    /// Why the three-way merge was not used, when it was tried
+    pub three_way: Option<String>,
+    /// Raw `patch` output
+    pub output: String,
+    /// Whether `patch` exited cleanly
+    pub success: bool,
+    /// 1: some hunks failed, 2: serious trouble (unreadable patch or file)
+    pub exit_code: Option<i32>,
+}
+
+impl PatchOutcome {
+    /// Hunks that changed the file
+    pub fn applied(&self) -> usize {
+        self.hunks
+            .iter()
+            .filter(|h| h.status != "failed" && h.status != "ignored")
+            .count()
+    }
+
+    /// `patch` gave up, or not a single hunk was applied
+    pub fn failed(&self) -> bool {
+        self.exit_code == Some(2) || self.applied() == 0
+    }
+}
+
+static HUNK_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^Hunk #(\d+) (succeeded|FAILED|ignored) at (\d+)(?: with fuzz (\d+))?(?: \(offset (-?\d+) lines?\))?",
+    )
+    .unwrap()
+});
+
+/// `@@ -a,b +c,d @@` headers of a unified diff, in order
+pub fn hunk_headers(patch: &str) -> Vec<String> {
+    patch
+        .lines()
+        .filter(|l| l.starts_with("@@ "))
+        .map(|l| match l[3..].find(" @@") {
+            Some(end) => l[..end + 6].to_string(),
+            None => l.to_string(),
+        })
+        .collect()
+}
+
+/// Per-hunk outcomes from `patch` output; hunks it doesn't mention applied
+/// exactly where the patch said
+pub fn parse_patch_output(patch: &str, output: &str) -> Vec<HunkResult> {
+    let reversed = output.contains("Reversed (or previously applied) patch detected");
+    let mut hunks: Vec<HunkResult> = hunk_headers(patch)
+        .into_iter()
+        .enumerate()
+        .map(|(i, header)| HunkResult {
+            number: i + 1,
+            line: header_new_start(&header),
+            header,
+            status: if reversed { "ignored" } else { "applied" },
+            offset: None,
+            fuzz: None,
+        })
+        .collect();
+
+    for caps in output.lines().filter_map(|l| HUNK_LINE.captures(l)) {
+        let number: usize = caps[1].parse().unwrap_or(0);
+        let Some(hunk) = hunks.get_mut(number.wrapping_sub(1)) else {
+            continue;
+        };
+        hunk.status = match &caps[2] {
+            "succeeded" => "applied",
+            "FAILED" => "failed",
+            _ => "ignored",
+        };
+        hunk.line = caps[3].parse().ok();
+        hunk.fuzz = caps.get(4).and_then(|m| m.as_str().parse().ok());
+        hunk.offset = caps.get(5).and_then(|m| m.as_str().parse().ok());
+    }
+    hunks
+}
+
+/// Rejected hunks from a `.rej` file, matched with the failed or ignored
+/// hunks (in order) to give each a reason
+pub fn parse_rejects(file: &str, rejects: &str, hunks: &[HunkResult]) -> Vec<Conflict> {
+    let mut bodies: Vec<String> = vec![];
+    for line in rejects.lines() {
+        if line.starts_with("@@ ") {
+            bodies.push(String::new());
+        } else if bodies.is_empty() {
+            // --- / +++ file header
+            continue;
+        }
+        if let Some(body) = bodies.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+
+    let rejected = hunks.iter().filter(|h| h.status != "applied");
+    bodies
+        .into_iter()
+        .zip(rejected)
+        .map(|(text, hunk)| Conflict {
+            file: file.to_string(),
+            hunk: hunk.header.clone(),
+            reason: match (hunk.status, hunk.line) {
+                ("ignored", _) => "already applied (patch looks reversed or applied before)".into(),
+                (_, Some(line)) => format!(
+                    "context not found near line {}; the file has diverged from the patch",
+                    line
+                ),
+                _ => "context not found".into(),
+            },
+            text,
+        })
+        .collect()
+}
+
+/// Base blob id from a git `index <old>..<new>` line, if present
+pub fn base_blob(patch: &str) -> Option<&str> {
+    patch
+        .lines()
+        .find_map(|l| l.strip_prefix("index "))
+        .and_then(|ids| ids.split("..").next())
+        .filter(|id| id.len() >= 7 && id.chars().all(|c| c.is_ascii_hexdigit()))
+        .filter(|id| id.chars().any(|c| c != '0'))
+}
+
+fn header_new_start(header: &str) -> Option<usize> {
+    header
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))
+        .and_then(|range| range.split(',').next())
+        .and_then(|n| n.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "--- a/f.txt\n+++ b/f.txt\n@@ -1,3 +1,3 @@ fn main\n a\n-b\n+B\n c\n@@ -10,3 +10,3 @@\n x\n-y\n+Y\n z\n";
+
+    #[test]
+    fn test_parse_patch_output() {
+        let output = "patching file f.txt\nHunk #1 succeeded at 3 with fuzz 1 (offset 2 lines).\nHunk #2 FAILED at 10.\n1 out of 2 hunks FAILED -- saving rejects to file f.txt.rej\n";
+        let hunks = parse_patch_output(PATCH, output);
+        assert_eq!(hunks[0].header, "@@ -1,3 +1,3 @@");
+        assert_eq!(hunks[0].status, "applied");
+        assert_eq!((hunks[0].fuzz, hunks[0].offset), (Some(1), Some(2)));
+        assert_eq!(hunks[1].status, "failed");
+
+        let rej = "--- f.txt\n+++ f.txt\n@@ -10,3 +10,3 @@\n x\n-y\n+Y\n z\n";
+        let conflicts = parse_rejects("f.txt", rej, &hunks);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].hunk, "@@ -10,3 +10,3 @@");
+        assert!(conflicts[0].reason.contains("line 10"));
+        assert!(conflicts[0].text.starts_with("@@ -10,3"));
+
+        let reversed = parse_patch_output(
+            PATCH,
+            "Reversed (or previously applied) patch detected!  Skipping patch.\n",
+        );
+        assert!(reversed.iter().all(|h| h.status == "ignored"));
+    }
+
+    #[test]
+    fn test_patch_outcome_failed() {
+        let outcome = |output: &str, exit_code| PatchOutcome {
+            method: "partial",
+            hunks: parse_patch_output(PATCH, output),
+            conflicts: vec![],
+            three_way: None,
+            output: output.to_string(),
+            success: exit_code == Some(0),
+            exit_code,
+        };
+        let partial = outcome("Hunk #2 FAILED at 10.\n", Some(1));
+        assert_eq!(partial.applied(), 1);
+        assert!(!partial.failed());
+        assert!(outcome("Hunk #1 FAILED at 1.\nHunk #2 FAILED at 10.\n", Some(1)).failed());
+        assert!(outcome("patch: **** malformed patch at line 5\n", Some(2)).failed());
+        assert!(!outcome("", Some(0)).failed());
+    }
+
+    #[test]
+    fn test_base_blob() {
+        assert_eq!(
+            base_blob("diff --git a/f b/f\nindex 422c2b7..de98044 100644\n"),
+            Some("422c2b7")
+        );
+        assert_eq!(base_blob("index 0000000..de98044\n"), None);
+        assert_eq!(base_blob(PATCH), None);
+    }
+}
@@ -0,0 +1,144 @@
+// modern-cli-mcp/src/tools/pathargs.rs
+//! Arguments that name files.
+//!
+//! Every call is checked against .agentignore and the allowed roots before
+//! the tool runs. `path` is checked for every tool; other fields opt in by
+//! carrying `"format": "path"` in their schema, set on the request struct with
+//! `#[schemars(extend("format" = "path"))]`. A field holds one path or a
+//! whitespace-separated list; `x-separator` gives another separator and
+//! `x-relative-to` names the argument its paths are relative to (e.g. files
+//! inside the repository given as `path`).
+
+use rmcp::model::JsonObject;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Schema format marking a path-typed field
+pub const FORMAT: &str = "path";
+
+/// Names of the arguments holding paths, `path` first
+pub fn fields(schema: &JsonObject) -> Vec<&str> {
+    let mut fields = vec!["path"];
+    let properties = schema.get("properties").and_then(Value::as_object);
+    fields.extend(
+        properties
+            .into_iter()
+            .flatten()
+            .filter(|(name, prop)| *name != "path" && prop["format"] == FORMAT)
+            .map(|(name, _)| name.as_str()),
+    );
+    fields
+}
+
+/// Every path named in `arguments`, resolved the way the tool will resolve
+/// it. `-` (stdin) and URLs are not paths.
+pub fn paths(schema: &JsonObject, arguments: &JsonObject, cwd: Option<&Path>) -> Vec<PathBuf> {
+    let property = |name: &str| schema.get("properties").and_then(|p| p.get(name));
+    let mut paths = vec![];
+    for field in fields(schema) {
+        let Some(value) = arguments.get(field).and_then(Value::as_str) else {
+            continue;
+        };
+        let prop = property(field);
+        let base = prop
+            .and_then(|p| p["x-relative-to"].as_str())
+            .and_then(|other| arguments.get(other))
+            .and_then(Value::as_str)
+            .map(|dir| resolve(dir, cwd));
+        let separator = prop.and_then(|p| p["x-separator"].as_str());
+        let items: Vec<&str> = match separator {
+            Some(sep) => value.split(sep).map(str::trim).collect(),
+            None => value.split_whitespace().collect(),
+        };
+        for item in items {
+            if item.is_empty() || item == "-" || item.contains("://") {
+                continue;
+            }
+            paths.push(match (&base, item.starts_with("~/")) {
+                (Some(base), false) => base.join(item),
+                _ => resolve(item, cwd),
+            });
+        }
+    }
+    paths
+}
+
+/// `path` with `~/` expanded, joined onto `cwd` when relative
+fn resolve(path: &str, cwd: Option<&Path>) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => match cwd {
+            Some(cwd) => cwd.join(path),
+            None => PathBuf::from(path),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ignore::AgentIgnore;
+    use rmcp::handler::server::tool::schema_for_type;
+    use serde_json::json;
+
+    fn args(value: Value) -> JsonObject {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_path_fields_from_schema() {
+        let delta = schema_for_type::<super::super::DeltaRequest>();
+        assert_eq!(fields(&delta), ["path", "file_a", "file_b"]);
+        let difft = schema_for_type::<super::super::DifftasticRequest>();
+        assert_eq!(fields(&difft), ["path", "left", "right"]);
+        let sad = schema_for_type::<super::super::SadRequest>();
+        assert_eq!(fields(&sad), ["path", "files"]);
+
+        let git_add = schema_for_type::<super::super::GitAddRequest>();
+        let cwd = Path::new("/work");
+        assert_eq!(
+            paths(
+                &git_add,
+                &args(json!({ "path": "repo", "files": "src/a.rs  b.rs" })),
+                Some(cwd)
+            ),
+            [
+                PathBuf::from("/work/repo"),
+                PathBuf::from("/work/repo/src/a.rs"),
+                PathBuf::from("/work/repo/b.rs")
+            ]
+        );
+        let compress = schema_for_type::<super::super::OuchCompressRequest>();
+        assert_eq!(
+            paths(
+                &compress,
+                &args(json!({ "files": "a.txt, b.txt", "output": "-" })),
+                Some(cwd)
+            ),
+            [PathBuf::from("/work/a.txt"), PathBuf::from("/work/b.txt")]
+        );
+    }
+
+    #[test]
+    fn test_ignored_file_a_is_refused() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join(".agentignore"), "*.secret\n").unwrap();
+        std::fs::write(temp.path().join("key.secret"), "").unwrap();
+        std::fs::write(temp.path().join("notes.txt"), "").unwrap();
+        let ignore = AgentIgnore::default();
+        let delta = schema_for_type::<super::super::DeltaRequest>();
+        let check = |file_a: &str| {
+            paths(
+                &delta,
+                &args(json!({ "file_a": file_a, "file_b": "notes.txt" })),
+                Some(temp.path()),
+            )
+            .iter()
+            .try_for_each(|p| ignore.validate_path(p))
+        };
+
+        let err = check("key.secret").unwrap_err();
+        assert!(err.contains(".agentignore"), "{}", err);
+        assert!(check("notes.txt").is_ok());
+    }
+}
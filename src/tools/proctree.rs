@@ -0,0 +1,139 @@
+// modern-cli-mcp/src/tools/proctree.rs
+//! Process tree cleanup for spawned commands.
+//!
+//! Each command runs as the leader of its own process group, so a timeout or
+//! a cancelled tool call can signal the whole group. This catches
+//! grandchildren such as the compilers `make` spawns, which killing only the
+//! direct child would orphan.
+
+use tokio::process::Command;
+
+/// Make the command lead a new process group that does not outlive the server
+pub fn isolate(command: &mut Command) {
+    command.kill_on_drop(true);
+    #[cfg(unix)]
+    command.process_group(0);
+    #[cfg(target_os = "linux")]
+    // SAFETY: prctl is async-signal-safe and touches no shared state
+    unsafe {
+        command.pre_exec(|| {
+            libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+            Ok(())
+        });
+    }
+}
+
+/// Kills the child's process group when dropped unless disarmed.
+///
+/// Dropping happens both on timeout and when the tool call future is
+/// cancelled, so no code path can leak the tree.
+pub struct TreeGuard {
+    pgid: Option<u32>,
+}
+
+impl TreeGuard {
+    pub fn new(pid: Option<u32>) -> Self {
+        Self { pgid: pid }
+    }
+
+    /// The leader exited normally; leave any deliberately detached work alone
+    pub fn disarm(mut self) {
+        self.pgid = None;
+    }
+
+    /// Kill the group now and describe what was killed
+    pub fn kill(mut self) -> Vec<String> {
+        self.pgid.take().map(kill_group).unwrap_or_default()
+    }
+}
+
+impl Drop for TreeGuard {
+    fn drop(&mut self) {
+        if let Some(pgid) = self.pgid.take() {
+            kill_group(pgid);
+        }
+    }
+}
+
+/// SIGKILL every process in the group, returning `name[pid]` for each
+fn kill_group(pgid: u32) -> Vec<String> {
+    let members = group_members(pgid);
+    #[cfg(unix)]
+    // SAFETY: kill(2) with a negative pid signals a process group
+    unsafe {
+        libc::kill(-(pgid as i32), libc::SIGKILL);
+    }
+    members
+}
+
+/// List processes in a group from /proc (empty where /proc is unavailable)
+fn group_members(pgid: u32) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return vec![];
+    };
+    let mut members: Vec<(u32, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+            // "pid (comm) state ppid pgrp ..."; comm may itself contain ") "
+            let (head, rest) = stat.rsplit_once(") ")?;
+            let comm = head.split_once(" (")?.1;
+            let mut fields = rest.split_whitespace();
+            // Zombies are already dead, just not yet reaped
+            if fields.next()? == "Z" {
+                return None;
+            }
+            let pgrp: u32 = fields.nth(1)?.parse().ok()?;
+            (pgrp == pgid).then(|| (pid, comm.to_string()))
+        })
+        .collect();
+    members.sort();
+    members
+        .into_iter()
+        .map(|(pid, comm)| format!("{}[{}]", comm, pid))
+        .collect()
+}
+
+/// Format the timeout error, naming the processes that were killed
+pub fn timeout_message(cmd: &str, timeout_secs: u64, killed: &[String]) -> String {
+    if killed.is_empty() {
+        format!("Command '{}' timed out after {} seconds", cmd, timeout_secs)
+    } else {
+        format!(
+            "Command '{}' timed out after {} seconds; killed {} process(es): {}",
+            cmd,
+            timeout_secs,
+            killed.len(),
+            killed.join(", ")
+        )
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeout_kills_grandchildren() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "sleep 30 & sleep 30"]);
+        isolate(&mut command);
+        let child = command.spawn().unwrap();
+        let guard = TreeGuard::new(child.id());
+        let pgid = child.id().unwrap();
+
+        let waited = tokio::time::timeout(
+            std::time::Duration::from_millis(300),
+            child.wait_with_output(),
+        )
+        .await;
+        assert!(waited.is_err());
+
+        let killed = guard.kill();
+        assert!(killed.iter().filter(|p| p.starts_with("sleep[")).count() >= 2);
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(group_members(pgid).iter().all(|p| !p.starts_with("sleep[")));
+    }
+}
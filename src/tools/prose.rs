@@ -0,0 +1,170 @@
+// modern-cli-mcp/src/tools/prose.rs
+//! Spelling and style findings from typos, codespell and vale.
+//!
+//! Each checker reports differently: typos logs one JSON object per line,
+//! codespell prints `path:line: word ==> fix, fix`, and vale emits a map
+//! from file to alerts. All three become the same finding shape. A finding
+//! is fixable when the speller offers exactly one correction, which is what
+//! `typos --write-changes` and `codespell -w` will apply; vale has no fixer.
+
+use serde_json::{json, Value};
+
+fn typo_message(word: &str, suggestions: &[String]) -> String {
+    match suggestions {
+        [] => format!("`{}` is misspelled", word),
+        _ => format!("`{}` should be {}", word, suggestions.join(" or ")),
+    }
+}
+
+/// Findings from `typos --format json`
+pub fn parse_typos(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+        .filter(|entry| entry["type"] == "typo")
+        .map(|entry| {
+            let word = entry["typo"].as_str().unwrap_or("");
+            let suggestions: Vec<String> = entry["corrections"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|c| c.as_str().map(String::from))
+                .collect();
+            json!({
+                "path": entry["path"],
+                "line": entry["line_num"],
+                "column": entry["byte_offset"].as_u64().map(|o| o + 1),
+                "word": word,
+                "message": typo_message(word, &suggestions),
+                "severity": "warning",
+                "rule": "typos",
+                "fixable": suggestions.len() == 1,
+                "suggestions": suggestions,
+            })
+        })
+        .collect()
+}
+
+/// Findings from codespell's `path:line: word ==> fix, fix | reason` lines
+pub fn parse_codespell(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (location, rest) = line.split_once(": ")?;
+            let (path, line_num) = location.rsplit_once(':')?;
+            let line_num: u64 = line_num.parse().ok()?;
+            let (word, fixes) = rest.split_once(" ==> ")?;
+            let (fixes, reason) = match fixes.split_once(" | ") {
+                Some((fixes, reason)) => (fixes, Some(reason.trim())),
+                None => (fixes, None),
+            };
+            let suggestions: Vec<String> = fixes
+                .split(',')
+                .map(|f| f.trim().to_string())
+                .filter(|f| !f.is_empty())
+                .collect();
+            // codespell -w leaves words with a reason attached alone
+            let fixable = suggestions.len() == 1 && reason.is_none();
+            let mut message = typo_message(word.trim(), &suggestions);
+            if let Some(reason) = reason {
+                message.push_str(&format!(" ({})", reason));
+            }
+            Some(json!({
+                "path": path,
+                "line": line_num,
+                "column": null,
+                "word": word.trim(),
+                "suggestions": suggestions,
+                "message": message,
+                "severity": "warning",
+                "rule": "codespell",
+                "fixable": fixable,
+            }))
+        })
+        .collect()
+}
+
+/// Findings from `vale --output=JSON`, ordered by file then line
+pub fn parse_vale(output: &str) -> Result<Vec<Value>, String> {
+    let files: serde_json::Map<String, Value> =
+        serde_json::from_str(output.trim()).map_err(|e| format!("Invalid vale output: {}", e))?;
+    let mut findings = vec![];
+    for (path, alerts) in &files {
+        for alert in alerts.as_array().into_iter().flatten() {
+            // Only replace actions carry replacement text in their params
+            let suggestions = match alert["Action"]["Name"].as_str() {
+                Some("replace") => alert["Action"]["Params"].clone(),
+                _ => json!([]),
+            };
+            findings.push(json!({
+                "path": path,
+                "line": alert["Line"],
+                "column": alert["Span"][0],
+                "word": alert["Match"],
+                "suggestions": suggestions,
+                "message": alert["Message"],
+                "severity": alert["Severity"].as_str().unwrap_or("warning"),
+                "rule": alert["Check"].as_str().unwrap_or("vale"),
+                "fixable": false,
+            }));
+        }
+    }
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_typos() {
+        let output = r#"{"type":"typo","path":"README.md","line_num":3,"byte_offset":10,"typo":"teh","corrections":["the"]}
+{"type":"typo","path":"src/lib.rs","line_num":7,"byte_offset":0,"typo":"ba","corrections":["by","be"]}
+{"type":"binary_file","path":"logo.png"}"#;
+        let findings = parse_typos(output);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0]["column"], 11);
+        assert_eq!(findings[0]["fixable"], true);
+        assert_eq!(findings[0]["message"], "`teh` should be the");
+        assert_eq!(findings[1]["fixable"], false);
+        assert_eq!(findings[1]["suggestions"], json!(["by", "be"]));
+    }
+
+    #[test]
+    fn test_parse_codespell() {
+        let output = "./docs/guide.md:12: recieve ==> receive\n\
+                      ./src/main.rs:40: adn ==> and, ad\n\
+                      ./src/lib.rs:2: clas ==> class | disabled because of name clash in c++\n";
+        let findings = parse_codespell(output);
+        assert_eq!(findings.len(), 3);
+        assert_eq!(findings[0]["path"], "./docs/guide.md");
+        assert_eq!(findings[0]["line"], 12);
+        assert_eq!(findings[0]["fixable"], true);
+        assert_eq!(findings[1]["fixable"], false);
+        assert_eq!(findings[2]["fixable"], false);
+        assert_eq!(
+            findings[2]["message"],
+            "`clas` should be class (disabled because of name clash in c++)"
+        );
+    }
+
+    #[test]
+    fn test_parse_vale() {
+        let output = r#"{"README.md": [
+            {"Check": "Vale.Spelling", "Line": 4, "Span": [9, 15], "Match": "Kuberntes",
+             "Message": "Did you really mean 'Kuberntes'?", "Severity": "error",
+             "Action": {"Name": "suggest", "Params": ["spellings"]}},
+            {"Check": "write-good.Weasel", "Line": 9, "Span": [1, 4], "Match": "very",
+             "Message": "'very' is a weasel word!", "Severity": "warning",
+             "Action": {"Name": "replace", "Params": ["really"]}}
+        ]}"#;
+        let findings = parse_vale(output).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0]["rule"], "Vale.Spelling");
+        assert_eq!(findings[0]["column"], 9);
+        assert_eq!(findings[1]["severity"], "warning");
+        assert_eq!(findings[0]["suggestions"], json!([]));
+        assert_eq!(findings[1]["suggestions"], json!(["really"]));
+        assert!(parse_vale("E100 [loadINI] Runtime error").is_err());
+    }
+}
@@ -0,0 +1,561 @@
+// modern-cli-mcp/src/tools/registry.rs
+//! Package metadata from crates.io, npm and PyPI.
+//!
+//! Each registry's JSON is reduced to the same shape: latest version and its
+//! release date, releases newest first with their yanked or deprecated state,
+//! downloads, and the features of one version (Cargo features, PyPI extras;
+//! npm has neither, so its engines and peer dependencies stand in). A semver
+//! requirement picks the highest release that satisfies it. PyPI versions are
+//! padded to three parts for that comparison, and PEP 440 pre-releases
+//! (`1.0rc1`) only count when pre-releases are asked for.
+
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Registry {
+    Crates,
+    Npm,
+    Pypi,
+}
+
+/// What to report besides the package overview
+#[derive(Debug, Default)]
+pub struct Query {
+    /// Version whose details (features, engines, extras) to show; default latest
+    pub version: Option<String>,
+    /// Semver requirement for `latest_matching`
+    pub requirement: Option<String>,
+    pub prerelease: bool,
+    pub limit: usize,
+}
+
+struct Release {
+    version: String,
+    released: Option<String>,
+    yanked: bool,
+    /// Deprecation message (npm) or yank reason (PyPI)
+    note: Option<String>,
+}
+
+/// Registry-independent view of a package document
+struct Package {
+    description: Option<String>,
+    license: Option<String>,
+    homepage: Option<String>,
+    repository: Option<String>,
+    latest: Option<String>,
+    deprecated: Option<String>,
+    downloads: Value,
+    releases: Vec<Release>,
+}
+
+impl Registry {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "crates" | "crates.io" | "cargo" | "rust" => Ok(Registry::Crates),
+            "npm" | "node" | "js" => Ok(Registry::Npm),
+            "pypi" | "pip" | "python" => Ok(Registry::Pypi),
+            _ => Err(format!(
+                "Unknown registry '{}'. Available: crates, npm, pypi",
+                name
+            )),
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Registry::Crates => "crates",
+            Registry::Npm => "npm",
+            Registry::Pypi => "pypi",
+        }
+    }
+
+    /// Package document URL, after checking the name is one the registry
+    /// could hold (so it cannot alter the URL)
+    pub fn package_url(&self, package: &str) -> Result<String, String> {
+        let valid = !package.is_empty()
+            && package
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+            || (*self == Registry::Npm
+                && package.starts_with('@')
+                && package[1..].split_once('/').is_some_and(|(scope, name)| {
+                    !scope.is_empty()
+                        && !name.is_empty()
+                        && format!("{}{}", scope, name)
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+                }));
+        if !valid {
+            return Err(format!("Invalid {} package name: {}", self.id(), package));
+        }
+        Ok(match self {
+            Registry::Crates => format!("https://crates.io/api/v1/crates/{}", package),
+            Registry::Npm => format!("https://registry.npmjs.org/{}", package.replace('/', "%2F")),
+            Registry::Pypi => format!("https://pypi.org/pypi/{}/json", package),
+        })
+    }
+
+    /// Download counts live outside the package document on npm and PyPI
+    pub fn downloads_url(&self, package: &str) -> Option<String> {
+        match self {
+            Registry::Crates => None,
+            Registry::Npm => Some(format!(
+                "https://api.npmjs.org/downloads/point/last-week/{}",
+                package
+            )),
+            Registry::Pypi => Some(format!(
+                "https://pypistats.org/api/packages/{}/recent",
+                package.to_lowercase()
+            )),
+        }
+    }
+
+    /// PyPI only describes the latest release in the package document; other
+    /// versions need their own
+    pub fn version_url(&self, package: &str, version: &str) -> Option<String> {
+        match self {
+            Registry::Pypi => Some(format!(
+                "https://pypi.org/pypi/{}/{}/json",
+                package, version
+            )),
+            _ => None,
+        }
+    }
+
+    /// The latest version named by a package document
+    pub fn latest(&self, doc: &Value) -> Option<String> {
+        let latest = match self {
+            Registry::Crates => doc["crate"]["max_stable_version"]
+                .as_str()
+                .or_else(|| doc["crate"]["max_version"].as_str()),
+            Registry::Npm => doc["dist-tags"]["latest"].as_str(),
+            Registry::Pypi => doc["info"]["version"].as_str(),
+        };
+        latest.map(String::from)
+    }
+
+    /// Reduce the fetched documents to the common result. `version_doc` is
+    /// the PyPI document of `query.version` when that is not the latest.
+    pub fn summarize(
+        &self,
+        package: &str,
+        doc: &Value,
+        downloads: Option<&Value>,
+        version_doc: Option<&Value>,
+        query: &Query,
+    ) -> Result<Value, String> {
+        let parsed = match self {
+            Registry::Crates => crates_package(doc),
+            Registry::Npm => npm_package(doc, downloads),
+            Registry::Pypi => pypi_package(doc, downloads),
+        };
+        let mut releases = parsed.releases;
+        releases.sort_by(|a, b| b.released.cmp(&a.released));
+
+        let latest = parsed
+            .latest
+            .clone()
+            .or_else(|| releases.first().map(|r| r.version.clone()));
+        let selected = query.version.clone().or(latest.clone());
+        let details = match &selected {
+            Some(version) => {
+                let release = releases
+                    .iter()
+                    .find(|r| &r.version == version)
+                    .ok_or_else(|| {
+                        format!("{} has no version {} on {}", package, version, self.id())
+                    })?;
+                let mut details = match self {
+                    Registry::Crates => crates_version(doc, version),
+                    Registry::Npm => npm_version(doc, version),
+                    Registry::Pypi => pypi_version(version_doc.unwrap_or(doc)),
+                };
+                details.insert("version".into(), json!(release.version));
+                details.insert("released".into(), json!(release.released));
+                details.insert("yanked".into(), json!(release.yanked));
+                details.insert("note".into(), json!(release.note));
+                Value::Object(details)
+            }
+            None => Value::Null,
+        };
+
+        let latest_matching = match &query.requirement {
+            Some(requirement) => {
+                let req = semver::VersionReq::parse(&requirement.replace("==", "="))
+                    .map_err(|e| format!("Invalid requirement '{}': {}", requirement, e))?;
+                releases
+                    .iter()
+                    .filter(|r| !r.yanked && (query.prerelease || !is_prerelease(&r.version)))
+                    .filter_map(|r| Some((coerce(&r.version)?, r)))
+                    .filter(|(v, _)| {
+                        req.matches(v) || (query.prerelease && req.matches(&release_of(v)))
+                    })
+                    .max_by(|(a, _), (b, _)| a.cmp(b))
+                    .map(|(_, r)| json!({"version": r.version, "released": r.released}))
+            }
+            None => None,
+        };
+
+        let listed: Vec<Value> = releases
+            .iter()
+            .filter(|r| query.prerelease || !is_prerelease(&r.version))
+            .take(query.limit)
+            .map(|r| {
+                json!({
+                    "version": r.version,
+                    "released": r.released,
+                    "yanked": r.yanked,
+                    "note": r.note,
+                })
+            })
+            .collect();
+        let latest_released = releases
+            .iter()
+            .find(|r| Some(&r.version) == latest.as_ref())
+            .and_then(|r| r.released.clone());
+
+        Ok(json!({
+            "registry": self.id(),
+            "package": package,
+            "description": parsed.description,
+            "license": parsed.license,
+            "homepage": parsed.homepage,
+            "repository": parsed.repository,
+            "latest": latest,
+            "latest_released": latest_released,
+            "deprecated": parsed.deprecated,
+            "downloads": parsed.downloads,
+            "requirement": query.requirement,
+            "latest_matching": latest_matching,
+            "version": details,
+            "version_count": releases.len(),
+            "versions": listed,
+        }))
+    }
+}
+
+/// Parse `1.2.3`, `v1.2`, `2` or PyPI's `1.2.3.4` as a semver version
+fn coerce(version: &str) -> Option<semver::Version> {
+    let version = version.trim_start_matches('v');
+    if let Ok(v) = semver::Version::parse(version) {
+        return Some(v);
+    }
+    let mut parts: Vec<u64> = vec![];
+    for part in version.split('.') {
+        parts.push(part.parse().ok()?);
+    }
+    parts.resize(parts.len().max(3), 0);
+    Some(semver::Version::new(parts[0], parts[1], parts[2]))
+}
+
+/// The release a pre-release leads up to, so `^1.2` can match `1.3.0-rc.1`
+fn release_of(version: &semver::Version) -> semver::Version {
+    semver::Version::new(version.major, version.minor, version.patch)
+}
+
+fn is_prerelease(version: &str) -> bool {
+    match coerce(version) {
+        Some(v) => !v.pre.is_empty(),
+        // PEP 440: 1.0a1, 1.0b2, 1.0rc1, 1.0.dev3; post releases are final
+        None => {
+            let lower = version.to_lowercase();
+            !lower.contains("post") && lower.chars().any(|c| c.is_ascii_alphabetic())
+        }
+    }
+}
+
+fn text(value: &Value) -> Option<String> {
+    value.as_str().filter(|s| !s.is_empty()).map(String::from)
+}
+
+fn crates_package(doc: &Value) -> Package {
+    let krate = &doc["crate"];
+    let versions = doc["versions"].as_array().cloned().unwrap_or_default();
+    let latest = Registry::Crates.latest(doc);
+    let deprecated = versions
+        .iter()
+        .find(|v| v["num"].as_str() == latest.as_deref())
+        .filter(|v| v["yanked"] == true)
+        .map(|_| "latest version is yanked".to_string());
+    Package {
+        description: text(&krate["description"]),
+        license: versions.first().and_then(|v| text(&v["license"])),
+        homepage: text(&krate["homepage"]),
+        repository: text(&krate["repository"]),
+        latest,
+        deprecated,
+        downloads: json!({
+            "total": krate["downloads"],
+            "recent_90d": krate["recent_downloads"],
+        }),
+        releases: versions
+            .iter()
+            .filter_map(|v| {
+                Some(Release {
+                    version: v["num"].as_str()?.to_string(),
+                    released: text(&v["created_at"]),
+                    yanked: v["yanked"] == true,
+                    note: None,
+                })
+            })
+            .collect(),
+    }
+}
+
+fn crates_version(doc: &Value, version: &str) -> Map<String, Value> {
+    let v = doc["versions"]
+        .as_array()
+        .and_then(|vs| vs.iter().find(|v| v["num"].as_str() == Some(version)))
+        .cloned()
+        .unwrap_or_default();
+    let mut details = Map::new();
+    details.insert("features".into(), v["features"].clone());
+    details.insert("rust_version".into(), v["rust_version"].clone());
+    details.insert("license".into(), v["license"].clone());
+    details.insert("downloads".into(), v["downloads"].clone());
+    details
+}
+
+fn npm_package(doc: &Value, downloads: Option<&Value>) -> Package {
+    let latest = Registry::Npm.latest(doc);
+    let versions = doc["versions"].as_object().cloned().unwrap_or_default();
+    let repository = text(&doc["repository"]["url"]).or_else(|| text(&doc["repository"]));
+    Package {
+        description: text(&doc["description"]),
+        license: text(&doc["license"]),
+        homepage: text(&doc["homepage"]),
+        repository,
+        deprecated: latest
+            .as_ref()
+            .and_then(|l| text(&versions.get(l)?["deprecated"])),
+        latest,
+        downloads: json!({
+            "last_week": downloads.map(|d| d["downloads"].clone()),
+        }),
+        releases: versions
+            .iter()
+            .map(|(version, v)| Release {
+                version: version.clone(),
+                released: text(&doc["time"][version]),
+                yanked: false,
+                note: text(&v["deprecated"]),
+            })
+            .collect(),
+    }
+}
+
+fn npm_version(doc: &Value, version: &str) -> Map<String, Value> {
+    let v = &doc["versions"][version];
+    let names = |deps: &Value| -> Vec<String> {
+        deps.as_object()
+            .map(|d| d.keys().cloned().collect())
+            .unwrap_or_default()
+    };
+    let mut details = Map::new();
+    details.insert("engines".into(), v["engines"].clone());
+    details.insert("peer_dependencies".into(), v["peerDependencies"].clone());
+    details.insert("dependencies".into(), json!(names(&v["dependencies"])));
+    details.insert("license".into(), v["license"].clone());
+    details.insert(
+        "types".into(),
+        json!(v["types"].is_string() || v["typings"].is_string()),
+    );
+    details
+}
+
+fn pypi_package(doc: &Value, downloads: Option<&Value>) -> Package {
+    let info = &doc["info"];
+    let releases = doc["releases"].as_object().cloned().unwrap_or_default();
+    let inactive = info["classifiers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .find(|c| c.starts_with("Development Status :: 7"))
+        .map(String::from);
+    let project_url = |keys: &[&str]| {
+        let urls = info["project_urls"].as_object()?;
+        urls.iter()
+            .find(|(k, _)| keys.iter().any(|key| k.eq_ignore_ascii_case(key)))
+            .and_then(|(_, v)| text(v))
+    };
+    let data = downloads.map(|d| &d["data"]);
+    Package {
+        description: text(&info["summary"]),
+        license: text(&info["license_expression"]).or_else(|| text(&info["license"])),
+        homepage: text(&info["home_page"]).or_else(|| project_url(&["Homepage", "Home"])),
+        repository: project_url(&["Repository", "Source", "Source Code", "Code"]),
+        latest: Registry::Pypi.latest(doc),
+        deprecated: text(&info["yanked_reason"])
+            .filter(|_| info["yanked"] == true)
+            .or(inactive),
+        downloads: json!({
+            "last_day": data.map(|d| d["last_day"].clone()),
+            "last_week": data.map(|d| d["last_week"].clone()),
+            "last_month": data.map(|d| d["last_month"].clone()),
+        }),
+        releases: releases
+            .iter()
+            .map(|(version, files)| {
+                let files = files.as_array().cloned().unwrap_or_default();
+                Release {
+                    version: version.clone(),
+                    released: files
+                        .iter()
+                        .filter_map(|f| text(&f["upload_time_iso_8601"]))
+                        .min(),
+                    yanked: !files.is_empty() && files.iter().all(|f| f["yanked"] == true),
+                    note: files.first().and_then(|f| text(&f["yanked_reason"])),
+                }
+            })
+            .collect(),
+    }
+}
+
+fn pypi_version(doc: &Value) -> Map<String, Value> {
+    let info = &doc["info"];
+    let requires: Vec<&str> = info["requires_dist"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    // provides_extra is often missing; the markers of requires_dist name them
+    let mut extras: Vec<String> = info["provides_extra"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.as_str().map(String::from))
+        .collect();
+    for requirement in &requires {
+        if let Some((_, marker)) = requirement.split_once("extra ==") {
+            let extra = marker
+                .trim()
+                .trim_start_matches(['"', '\''])
+                .split(['"', '\''])
+                .next()
+                .unwrap_or_default();
+            if !extra.is_empty() && !extras.iter().any(|e| e == extra) {
+                extras.push(extra.to_string());
+            }
+        }
+    }
+    let dependencies: Vec<&str> = requires
+        .iter()
+        .filter(|r| !r.contains("extra =="))
+        .copied()
+        .collect();
+    let mut details = Map::new();
+    details.insert("features".into(), json!(extras));
+    details.insert("requires_python".into(), info["requires_python"].clone());
+    details.insert("dependencies".into(), json!(dependencies));
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(requirement: Option<&str>) -> Query {
+        Query {
+            requirement: requirement.map(String::from),
+            limit: 10,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_crates_and_requirements() {
+        let doc = json!({
+            "crate": {"max_version": "2.0.0-rc.1", "max_stable_version": "1.4.0",
+                      "downloads": 900, "recent_downloads": 90, "description": "Things"},
+            "versions": [
+                {"num": "2.0.0-rc.1", "created_at": "2025-03-01T00:00:00+00:00", "yanked": false, "features": {}},
+                {"num": "1.3.2", "created_at": "2025-02-01T00:00:00+00:00", "yanked": false, "features": {}},
+                {"num": "1.4.0", "created_at": "2025-01-01T00:00:00+00:00", "yanked": false,
+                 "features": {"default": ["std"], "std": []}, "rust_version": "1.70", "license": "MIT"},
+                {"num": "1.4.1", "created_at": "2025-01-15T00:00:00+00:00", "yanked": true, "features": {}},
+                {"num": "0.9.0", "created_at": "2024-01-01T00:00:00+00:00", "yanked": false, "features": {}},
+            ]
+        });
+        let result = Registry::Crates
+            .summarize("things", &doc, None, None, &query(Some("^1.2")))
+            .unwrap();
+        assert_eq!(result["latest"], "1.4.0");
+        assert_eq!(result["latest_released"], "2025-01-01T00:00:00+00:00");
+        // Highest match wins over the most recent release; yanked ones never match
+        assert_eq!(result["latest_matching"]["version"], "1.4.0");
+        assert_eq!(result["version"]["features"]["default"][0], "std");
+        assert_eq!(result["version"]["rust_version"], "1.70");
+        assert_eq!(result["versions"][0]["version"], "1.3.2");
+        assert_eq!(result["version_count"], 5);
+
+        let none = Registry::Crates
+            .summarize("things", &doc, None, None, &query(Some(">=3")))
+            .unwrap();
+        assert!(none["latest_matching"].is_null());
+        assert!(Registry::Crates
+            .summarize("things", &doc, None, None, &query(Some("not a req")))
+            .is_err());
+    }
+
+    #[test]
+    fn test_npm_and_pypi() {
+        let doc = json!({
+            "dist-tags": {"latest": "3.1.0"},
+            "description": "A lib",
+            "repository": {"type": "git", "url": "git+https://github.com/o/lib.git"},
+            "time": {"3.1.0": "2025-05-01T00:00:00.000Z", "3.0.0": "2025-04-01T00:00:00.000Z"},
+            "versions": {
+                "3.0.0": {"deprecated": "Use 3.1"},
+                "3.1.0": {"engines": {"node": ">=18"}, "peerDependencies": {"react": "^18"},
+                          "dependencies": {"tslib": "^2"}},
+            }
+        });
+        let downloads = json!({"downloads": 1234});
+        let result = Registry::Npm
+            .summarize("lib", &doc, Some(&downloads), None, &query(Some("~3.0")))
+            .unwrap();
+        assert_eq!(result["downloads"]["last_week"], 1234);
+        assert_eq!(result["version"]["engines"]["node"], ">=18");
+        assert_eq!(result["latest_matching"]["version"], "3.0.0");
+        assert_eq!(result["versions"][1]["note"], "Use 3.1");
+        assert!(result["deprecated"].is_null());
+
+        let doc = json!({
+            "info": {"version": "2.1", "summary": "Py lib", "requires_python": ">=3.9",
+                     "project_urls": {"Source": "https://github.com/o/py"},
+                     "requires_dist": ["idna>=2", "socks; extra == \"socks\""]},
+            "releases": {
+                "2.1": [{"upload_time_iso_8601": "2025-02-01T00:00:00Z", "yanked": false}],
+                "2.2rc1": [{"upload_time_iso_8601": "2025-03-01T00:00:00Z", "yanked": false}],
+                "1.9.4": [{"upload_time_iso_8601": "2024-01-01T00:00:00Z", "yanked": true,
+                           "yanked_reason": "broken"}],
+            }
+        });
+        let result = Registry::Pypi
+            .summarize("py", &doc, None, None, &query(Some(">=1,<3")))
+            .unwrap();
+        assert_eq!(result["latest_matching"]["version"], "2.1");
+        assert_eq!(result["version"]["features"], json!(["socks"]));
+        assert_eq!(result["version"]["dependencies"], json!(["idna>=2"]));
+        assert_eq!(result["repository"], "https://github.com/o/py");
+        // The release candidate is left out unless pre-releases are asked for
+        assert_eq!(result["versions"].as_array().unwrap().len(), 2);
+        assert_eq!(result["versions"][1]["note"], "broken");
+    }
+
+    #[test]
+    fn test_package_urls() {
+        assert_eq!(
+            Registry::Npm.package_url("@types/node").unwrap(),
+            "https://registry.npmjs.org/@types%2Fnode"
+        );
+        assert!(Registry::Crates.package_url("@types/node").is_err());
+        assert!(Registry::Pypi.package_url("x/../y").is_err());
+        assert!(Registry::Npm.package_url("a?b").is_err());
+        assert_eq!(Registry::parse("cargo").unwrap(), Registry::Crates);
+        assert!(Registry::parse("maven").is_err());
+    }
+}
@@ -0,0 +1,234 @@
+// modern-cli-mcp/src/tools/release.rs
+//! Verifying downloaded release artifacts.
+//!
+//! Checksum files come in GNU (`<hex>  name`, `*name` for binary mode) and
+//! BSD (`SHA256 (name) = <hex>`) styles, or hold a bare digest; the entry for
+//! the artifact is found by file name and the algorithm by digest length.
+//! GPG results are read from `--status-fd` lines rather than the localized
+//! human output. A signature over the checksums file covers the artifact
+//! only once the artifact's digest matches an entry in it.
+
+use serde_json::{json, Value};
+use std::io::Read;
+use std::path::Path;
+
+/// `(algorithm, hex digest, name in the file)` for `artifact` in a checksums
+/// file; a file holding a single unnamed digest matches any artifact
+pub fn find_checksum(text: &str, artifact: &str) -> Option<(&'static str, String, Option<String>)> {
+    let mut entries: Vec<(String, Option<String>)> = vec![];
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // BSD style: SHA256 (name) = digest
+        if let Some((lhs, digest)) = line.split_once(") = ") {
+            if let Some((_, name)) = lhs.split_once(" (") {
+                entries.push((digest.trim().to_string(), Some(name.to_string())));
+                continue;
+            }
+        }
+        let mut parts = line.splitn(2, char::is_whitespace);
+        let digest = parts.next().unwrap_or("").to_string();
+        let name = parts
+            .next()
+            .map(|n| n.trim().trim_start_matches('*').to_string())
+            .filter(|n| !n.is_empty());
+        entries.push((digest, name));
+    }
+
+    let file_name = |n: &str| n.rsplit('/').next().unwrap_or(n).to_string();
+    let wanted = file_name(artifact);
+    let (digest, name) = match entries.as_slice() {
+        [(digest, None)] => (digest.clone(), None),
+        _ => entries
+            .into_iter()
+            .find(|(_, name)| name.as_deref().map(file_name).as_deref() == Some(wanted.as_str()))?,
+    };
+    let digest = digest.to_ascii_lowercase();
+    if !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((algorithm_for(&digest)?, digest, name))
+}
+
+/// Hash algorithm implied by a hex digest's length
+fn algorithm_for(digest: &str) -> Option<&'static str> {
+    match digest.len() {
+        32 => Some("md5"),
+        40 => Some("sha1"),
+        64 => Some("sha256"),
+        128 => Some("sha512"),
+        _ => None,
+    }
+}
+
+/// Lowercase hex SHA-256 of a file, read in chunks
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hash = hmac_sha256::Hash::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hash.update(&buf[..n]);
+    }
+    Ok(hash
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// `gpg` or `cosign`, from the signature's name and content: armored or
+/// binary OpenPGP packets are gpg, base64 text and sigstore bundles cosign
+pub fn signature_kind(path: &str, content: &[u8]) -> &'static str {
+    let lower = path.to_ascii_lowercase();
+    if lower.ends_with(".asc") || lower.ends_with(".gpg") {
+        return "gpg";
+    }
+    if lower.ends_with(".bundle") || lower.ends_with(".sigstore") || lower.ends_with(".json") {
+        return "cosign";
+    }
+    match std::str::from_utf8(content) {
+        Ok(text) if text.starts_with("-----BEGIN PGP") => "gpg",
+        Ok(text)
+            if text.trim().bytes().all(|b| {
+                b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=' | b'\n' | b'\r')
+            }) =>
+        {
+            "cosign"
+        }
+        _ => "gpg",
+    }
+}
+
+/// Verdict, signer and key from `gpg --status-fd 1 --verify` status lines
+pub fn parse_gpg_status(status: &str) -> Value {
+    let mut outcome = "no signature found";
+    let mut signer = None;
+    let mut key_id = None;
+    let mut fingerprint = None;
+    for line in status.lines() {
+        let Some(rest) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+        let mut words = rest.splitn(3, ' ');
+        let keyword = words.next().unwrap_or("");
+        let id = words.next().map(String::from);
+        let tail = words.next().map(String::from);
+        match keyword {
+            "GOODSIG" => {
+                outcome = "good signature";
+                key_id = id;
+                signer = tail;
+            }
+            "VALIDSIG" => fingerprint = id,
+            "BADSIG" => {
+                outcome = "bad signature";
+                key_id = id;
+                signer = tail;
+            }
+            "EXPKEYSIG" | "REVKEYSIG" => {
+                outcome = if keyword == "EXPKEYSIG" {
+                    "signed by an expired key"
+                } else {
+                    "signed by a revoked key"
+                };
+                key_id = id;
+                signer = tail;
+            }
+            "NO_PUBKEY" => {
+                outcome = "public key not available";
+                key_id = id;
+            }
+            "ERRSIG" if key_id.is_none() => {
+                outcome = "signature could not be checked";
+                key_id = id;
+            }
+            _ => {}
+        }
+    }
+    // gpg reports expired and revoked keys instead of GOODSIG, so only a
+    // GOODSIG outcome counts
+    json!({
+        "verified": outcome == "good signature",
+        "status": outcome,
+        "signer": signer,
+        "key_id": key_id,
+        "fingerprint": fingerprint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_checksum() {
+        let gnu = "# release checksums\n\
+            3b0c...bad  tool-linux-arm64.tar.gz\n\
+            9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08 *dist/tool-linux-amd64.tar.gz\n";
+        let (algo, digest, name) = find_checksum(gnu, "/tmp/tool-linux-amd64.tar.gz").unwrap();
+        assert_eq!(algo, "sha256");
+        assert!(digest.starts_with("9f86d0"));
+        assert_eq!(name.as_deref(), Some("dist/tool-linux-amd64.tar.gz"));
+        assert!(find_checksum(gnu, "tool-darwin.tar.gz").is_none());
+        // A non-hex digest never matches
+        assert!(find_checksum(gnu, "tool-linux-arm64.tar.gz").is_none());
+
+        let bsd = "SHA1 (tool.zip) = A94A8FE5CCB19BA61C4C0873D391E987982FBBD3\n";
+        let (algo, digest, _) = find_checksum(bsd, "tool.zip").unwrap();
+        assert_eq!(
+            (algo, digest.as_str()),
+            ("sha1", "a94a8fe5ccb19ba61c4c0873d391e987982fbbd3")
+        );
+
+        let bare = "d41d8cd98f00b204e9800998ecf8427e\n";
+        assert_eq!(find_checksum(bare, "anything").unwrap().0, "md5");
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.txt");
+        std::fs::write(&path, "test").unwrap();
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+    }
+
+    #[test]
+    fn test_signature_kind() {
+        assert_eq!(signature_kind("SHA256SUMS.asc", b""), "gpg");
+        assert_eq!(signature_kind("tool.sigstore.json", b"{}"), "cosign");
+        assert_eq!(signature_kind("tool.sig", b"MEUCIQDx+/abc=\n"), "cosign");
+        assert_eq!(signature_kind("tool.sig", &[0x89, 0x02, 0x33]), "gpg");
+    }
+
+    #[test]
+    fn test_parse_gpg_status() {
+        let good = "[GNUPG:] NEWSIG\n\
+            [GNUPG:] GOODSIG 4AEE18F83AFDEB23 Release Bot <release@example.com>\n\
+            [GNUPG:] VALIDSIG 5DE3E0509C47EA3CF04A42D34AEE18F83AFDEB23 2024-05-01 1714521600 0 4 0 1 10 00 5DE3E0509C47EA3CF04A42D34AEE18F83AFDEB23\n";
+        let result = parse_gpg_status(good);
+        assert_eq!(result["verified"], true);
+        assert_eq!(result["signer"], "Release Bot <release@example.com>");
+        assert_eq!(
+            result["fingerprint"],
+            "5DE3E0509C47EA3CF04A42D34AEE18F83AFDEB23"
+        );
+
+        let missing = "[GNUPG:] ERRSIG 4AEE18F83AFDEB23 1 10 00 1714521600 9 -\n\
+            [GNUPG:] NO_PUBKEY 4AEE18F83AFDEB23\n";
+        let result = parse_gpg_status(missing);
+        assert_eq!(result["verified"], false);
+        assert_eq!(result["status"], "public key not available");
+
+        let expired = "[GNUPG:] EXPKEYSIG 4AEE18F83AFDEB23 Old Key\n\
+            [GNUPG:] VALIDSIG 5DE3 2020-01-01\n";
+        assert_eq!(parse_gpg_status(expired)["verified"], false);
+    }
+}
@@ -0,0 +1,281 @@
+// modern-cli-mcp/src/tools/replacing.rs
+//! Project-wide replacements staged in memory and applied all or nothing.
+//!
+//! Every candidate file is rewritten in memory first, so a preview and an
+//! apply see exactly the same changes. Applying backs up each original
+//! before the first write; if any write fails, the files already written are
+//! restored from those backups.
+
+use regex::{NoExpand, Regex};
+use std::path::{Path, PathBuf};
+
+/// A file's replaced content, not yet written
+#[derive(Debug, Clone, PartialEq)]
+pub struct Staged {
+    pub path: String,
+    pub updated: String,
+    pub replacements: usize,
+}
+
+/// Result of writing the staged files
+#[derive(Debug, Default, PartialEq)]
+pub struct Applied {
+    pub error: Option<String>,
+    pub rolled_back: bool,
+}
+
+/// Candidate files from `rg -l`, and its complaints as warnings. rg exits 2
+/// for any error, including one unreadable file among many matches, so only
+/// an error without any match fails the search.
+pub fn candidates(
+    stdout: &str,
+    stderr: &str,
+    exit_code: Option<i32>,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut files: Vec<String> = stdout
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect();
+    files.sort();
+    let warnings: Vec<String> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect();
+    if exit_code == Some(2) && files.is_empty() {
+        return Err(match warnings.is_empty() {
+            true => "rg failed".to_string(),
+            false => warnings.join("\n"),
+        });
+    }
+    Ok((files, warnings))
+}
+
+/// The regex `rg` searched with. rg matches line by line, so `^` and `$`
+/// anchor at line boundaries here too rather than at the file's ends.
+pub fn regex(pattern: &str, fixed_strings: bool, ignore_case: bool) -> Result<Regex, regex::Error> {
+    let pattern = match fixed_strings {
+        true => regex::escape(pattern),
+        false => pattern.to_string(),
+    };
+    let flags = if ignore_case { "(?mi)" } else { "(?m)" };
+    Regex::new(&format!("{}{}", flags, pattern))
+}
+
+/// Replace every match in `original`; None when nothing changes. A
+/// `literal` replacement is inserted as is, without expanding `$1`/`$name`.
+pub fn stage(
+    path: &str,
+    original: &str,
+    re: &Regex,
+    replacement: &str,
+    literal: bool,
+) -> Option<Staged> {
+    let replacements = re.find_iter(original).count();
+    if replacements == 0 {
+        return None;
+    }
+    let updated = match literal {
+        true => re.replace_all(original, NoExpand(replacement)),
+        false => re.replace_all(original, replacement),
+    }
+    .into_owned();
+    (updated != original).then(|| Staged {
+        path: path.to_string(),
+        updated,
+        replacements,
+    })
+}
+
+/// Back up each original under `backup_dir`, then write the staged content.
+/// Nothing is written when a backup fails; a failed write restores the
+/// files written before it.
+pub fn apply(staged: &[Staged], backup_dir: &Path) -> Applied {
+    match backup(staged, backup_dir) {
+        Ok(backups) => write(staged, &backups),
+        Err(e) => Applied {
+            error: Some(e),
+            rolled_back: false,
+        },
+    }
+}
+
+/// Copy each staged file's original under `backup_dir`, in order
+pub fn backup(staged: &[Staged], backup_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    staged
+        .iter()
+        .map(|file| {
+            let rel = file.path.trim_start_matches("./").trim_start_matches('/');
+            let backup = backup_dir.join(rel);
+            if let Some(parent) = backup.parent() {
+                std::fs::create_dir_all(parent)
+                    .and_then(|_| std::fs::copy(&file.path, &backup))
+                    .map_err(|e| format!("Backup of {} failed: {}", file.path, e))?;
+            }
+            Ok(backup)
+        })
+        .collect()
+}
+
+/// Write the staged content, restoring from `backups` (one per staged file)
+/// when a write fails
+pub fn write(staged: &[Staged], backups: &[PathBuf]) -> Applied {
+    for (written, file) in staged.iter().enumerate() {
+        if let Err(e) = std::fs::write(&file.path, &file.updated) {
+            // Include the failed file: a partial write may have truncated it
+            for (file, backup) in staged.iter().zip(backups).take(written + 1) {
+                let _ = std::fs::copy(backup, &file.path);
+            }
+            return Applied {
+                error: Some(format!("Write to {} failed: {}", file.path, e)),
+                rolled_back: true,
+            };
+        }
+    }
+    Applied::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> (tempfile::TempDir, Vec<String>) {
+        let tmp = tempfile::tempdir().unwrap();
+        let paths: Vec<String> = ["a.rs", "b.rs"]
+            .iter()
+            .map(|name| {
+                let path = tmp.path().join(name);
+                std::fs::write(&path, "let foo = foo + 1;\n").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        (tmp, paths)
+    }
+
+    fn stage_all(paths: &[String]) -> Vec<Staged> {
+        let re = Regex::new(r"\bfoo\b").unwrap();
+        paths
+            .iter()
+            .filter_map(|p| stage(p, &std::fs::read_to_string(p).unwrap(), &re, "bar", false))
+            .collect()
+    }
+
+    #[test]
+    fn test_candidates_keep_matches_despite_errors() {
+        let (files, warnings) = candidates(
+            "src/b.rs\nsrc/a.rs\n",
+            "rg: src/secret: Permission denied (os error 13)\n",
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(files, ["src/a.rs", "src/b.rs"]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(candidates("", "", Some(1)).unwrap().0.len(), 0);
+        assert!(candidates("", "rg: regex parse error", Some(2))
+            .unwrap_err()
+            .contains("regex parse error"));
+    }
+
+    #[test]
+    fn test_preview_and_apply() {
+        let (tmp, paths) = project();
+        let staged = stage_all(&paths);
+        assert_eq!(staged.len(), 2);
+        assert_eq!(staged[0].replacements, 2);
+        assert_eq!(staged[0].updated, "let bar = bar + 1;\n");
+        // Staging alone leaves the files untouched
+        assert_eq!(
+            std::fs::read_to_string(&paths[0]).unwrap(),
+            "let foo = foo + 1;\n"
+        );
+        let re = Regex::new("foo").unwrap();
+        assert!(stage("c.rs", "nothing here", &re, "bar", false).is_none());
+        assert!(stage("c.rs", "foo", &re, "foo", false).is_none());
+
+        let backups = tmp.path().join("backup");
+        assert_eq!(apply(&staged, &backups), Applied::default());
+        for path in &paths {
+            assert_eq!(
+                std::fs::read_to_string(path).unwrap(),
+                "let bar = bar + 1;\n"
+            );
+        }
+        let rel = paths[0].trim_start_matches('/');
+        assert_eq!(
+            std::fs::read_to_string(backups.join(rel)).unwrap(),
+            "let foo = foo + 1;\n"
+        );
+    }
+
+    #[test]
+    fn test_regex_anchors_match_per_line() {
+        let re = regex("^foo$", false, false).unwrap();
+        let staged = stage("a.txt", "x\nfoo\nfoo bar\nfoo\n", &re, "baz", false).unwrap();
+        assert_eq!(staged.updated, "x\nbaz\nfoo bar\nbaz\n");
+        assert_eq!(staged.replacements, 2);
+
+        let re = regex("a.b", true, true).unwrap();
+        assert!(re.is_match("A.B"));
+        assert!(!re.is_match("axb"));
+    }
+
+    #[test]
+    fn test_literal_replacement_is_not_expanded() {
+        let re = Regex::new(&regex::escape("PATH")).unwrap();
+        let literal = stage("env.sh", "echo $PATH\n", &re, "$HOME", true).unwrap();
+        assert_eq!(literal.updated, "echo $$HOME\n");
+        // Without `literal`, `$HOME` names a capture group that does not exist
+        let expanded = stage("env.sh", "echo $PATH\n", &re, "$HOME", false).unwrap();
+        assert_eq!(expanded.updated, "echo $\n");
+
+        let re = Regex::new(r"(\w+)@(\w+)").unwrap();
+        let swapped = stage("a.txt", "user@host", &re, "$2@$1", false).unwrap();
+        assert_eq!(swapped.updated, "host@user");
+    }
+
+    #[test]
+    fn test_failed_write_rolls_back() {
+        let (tmp, paths) = project();
+        let mut staged = stage_all(&paths);
+        // A directory can be neither copied as a backup nor written as a file
+        let dir = tmp.path().join("c.rs");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join("inner"), "").unwrap();
+        staged.push(Staged {
+            path: dir.to_string_lossy().to_string(),
+            updated: "x".into(),
+            replacements: 1,
+        });
+
+        let applied = apply(&staged, &tmp.path().join("backup"));
+        assert!(applied.error.unwrap().contains("Backup of"));
+        assert!(!applied.rolled_back);
+        assert_eq!(
+            std::fs::read_to_string(&paths[0]).unwrap(),
+            "let foo = foo + 1;\n"
+        );
+
+        // Backups succeed, the write to the last file fails
+        let mut staged = stage_all(&paths);
+        let mut backups = backup(&staged, &tmp.path().join("backup2")).unwrap();
+        let spare = tmp.path().join("spare");
+        std::fs::write(&spare, "").unwrap();
+        staged.push(Staged {
+            path: dir.to_string_lossy().to_string(),
+            updated: "x".into(),
+            replacements: 1,
+        });
+        backups.push(spare);
+        let applied = write(&staged, &backups);
+        assert!(applied.rolled_back);
+        assert!(applied.error.unwrap().contains("Write to"));
+        for path in &paths {
+            assert_eq!(
+                std::fs::read_to_string(path).unwrap(),
+                "let foo = foo + 1;\n"
+            );
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// modern-cli-mcp/src/tools/repos.rs
+//! Git repository discovery for workspaces holding many repositories, and
+//! the per-repository operations run across them.
+
+use crate::ignore::AgentIgnore;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Directories never worth descending into when looking for repositories
+const SKIP_DIRS: &[&str] = &["node_modules", "target", ".direnv", ".venv", "vendor"];
+
+/// Upper bound on repositories returned by one scan
+pub const MAX_REPOS: usize = 500;
+
+/// Find git repositories under `root` (including `root` itself), up to
+/// `max_depth` directories deep. A directory counts when it has a `.git`
+/// directory or file (worktrees, submodules); scanning continues inside
+/// repositories so nested ones are found too. `.agentignore`d paths are
+/// skipped.
+pub fn discover(root: &Path, max_depth: usize, ignore: &AgentIgnore) -> Vec<PathBuf> {
+    let mut repos = vec![];
+    let mut stack = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = stack.pop() {
+        if repos.len() >= MAX_REPOS {
+            break;
+        }
+        if dir.join(".git").exists() {
+            repos.push(dir.clone());
+        }
+        if depth >= max_depth {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+            .filter(|e| {
+                let name = e.file_name();
+                let name = name.to_string_lossy();
+                !name.starts_with('.') && !SKIP_DIRS.contains(&name.as_ref())
+            })
+            .map(|e| e.path())
+            .filter(|p| !ignore.is_ignored(p))
+            .collect();
+        // Reverse so the stack pops children in name order
+        children.sort_by(|a, b| b.cmp(a));
+        stack.extend(children.into_iter().map(|p| (p, depth + 1)));
+    }
+    repos
+}
+
+/// Repositories from a comma-separated list
+pub fn parse_list(list: &str) -> Vec<PathBuf> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// git arguments for a foreach `operation`; `count` commits for log
+pub fn foreach_args(operation: &str, count: u32) -> Result<Vec<String>, String> {
+    let args: Vec<String> = match operation {
+        "status" => vec!["status".into(), "--porcelain=v2".into(), "--branch".into()],
+        "pull" => vec!["pull".into(), "--ff-only".into()],
+        "fetch" => vec!["fetch".into(), "--prune".into()],
+        "log" => vec![
+            "log".into(),
+            format!("-n{}", count),
+            COMPACT_LOG_FORMAT.into(),
+        ],
+        other => {
+            return Err(format!(
+                "Unsupported foreach operation: {}. Available: status, pull, fetch, log",
+                other
+            ))
+        }
+    };
+    Ok(args)
+}
+
+/// `git log` format read by [`parse_compact_log`]
+pub const COMPACT_LOG_FORMAT: &str = "--format=%h%x1f%an%x1f%ar%x1f%s";
+
+/// Parse `git log` output in [`COMPACT_LOG_FORMAT`]
+pub fn parse_compact_log(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\x1f');
+            Some(json!({
+                "hash": parts.next()?,
+                "author": parts.next()?,
+                "when": parts.next()?,
+                "subject": parts.next()?,
+            }))
+        })
+        .collect()
+}
+
+/// Condense porcelain v2 status into branch, tracking and change counts
+pub fn summarize_status(output: &str) -> Value {
+    let status = super::parse_git_status_porcelain_v2(output);
+    let files = status["files"].as_array().cloned().unwrap_or_default();
+    let count = |key: &str| files.iter().filter(|f| f[key] == true).count();
+    let branch = &status["branch"];
+    json!({
+        "branch": branch["head"],
+        "upstream": branch["upstream"],
+        "ahead": branch["ahead"],
+        "behind": branch["behind"],
+        "dirty": !files.is_empty(),
+        "staged": count("staged"),
+        "unstaged": count("unstaged"),
+        "untracked": count("untracked"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_repos() {
+        let root = tempfile::tempdir().unwrap();
+        for dir in [
+            "a/.git",
+            "b/nested/.git",
+            "b/node_modules/dep/.git",
+            "c/d/e/f/.git",
+        ] {
+            std::fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        std::fs::create_dir_all(root.path().join("wt")).unwrap();
+        std::fs::write(
+            root.path().join("wt/.git"),
+            "gitdir: ../a/.git/worktrees/wt",
+        )
+        .unwrap();
+
+        let ignore = AgentIgnore::default();
+        let repos: Vec<String> = discover(root.path(), 3, &ignore)
+            .iter()
+            .map(|p| p.strip_prefix(root.path()).unwrap().display().to_string())
+            .collect();
+        assert_eq!(repos, ["a", "b/nested", "wt"]);
+        assert_eq!(discover(root.path(), 4, &ignore).len(), 4);
+    }
+
+    #[test]
+    fn test_foreach_args_and_list() {
+        assert_eq!(foreach_args("pull", 5).unwrap(), ["pull", "--ff-only"]);
+        assert_eq!(
+            foreach_args("log", 3).unwrap(),
+            ["log", "-n3", COMPACT_LOG_FORMAT]
+        );
+        assert!(foreach_args("push", 5)
+            .unwrap_err()
+            .contains("Unsupported foreach operation: push"));
+        assert_eq!(
+            parse_list(" a, ,b/c ,"),
+            [PathBuf::from("a"), PathBuf::from("b/c")]
+        );
+    }
+
+    #[test]
+    fn test_parse_compact_log() {
+        let log = "abc1234\x1fAda\x1f2 days ago\x1fFix parser\nbroken line\n";
+        let commits = parse_compact_log(log);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0]["hash"], "abc1234");
+        assert_eq!(commits[0]["author"], "Ada");
+        assert_eq!(commits[0]["subject"], "Fix parser");
+    }
+
+    #[test]
+    fn test_summarize_status() {
+        let status = "# branch.oid 1111111111111111111111111111111111111111
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +2 -1
+1 M. N... 100644 100644 100644 aaaaaaa bbbbbbb src/lib.rs
+1 .M N... 100644 100644 100644 aaaaaaa bbbbbbb README.md
+? notes.txt
+";
+        let summary = summarize_status(status);
+        assert_eq!(summary["branch"], "main");
+        assert_eq!(summary["upstream"], "origin/main");
+        assert_eq!(summary["ahead"], 2);
+        assert_eq!(summary["behind"], 1);
+        assert_eq!(summary["dirty"], true);
+        assert_eq!(summary["staged"], 1);
+        assert_eq!(summary["unstaged"], 1);
+        assert_eq!(summary["untracked"], 1);
+        assert_eq!(summarize_status("# branch.head main\n")["dirty"], false);
+    }
+}
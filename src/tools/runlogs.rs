@@ -0,0 +1,150 @@
+// modern-cli-mcp/src/tools/runlogs.rs
+//! Failing steps of a GitHub Actions run.
+//!
+//! `gh run view --json jobs` says which jobs and steps failed;
+//! `gh run view --log-failed` prints their logs as `job<TAB>step<TAB>line`.
+//! Recent gh releases label every line `UNKNOWN STEP`, so when no line
+//! carries a failing step's name, the job's lines are attributed to its
+//! failing step. Only the last lines of each step are kept, along with the
+//! `##[error]` annotations that usually name the cause.
+
+use serde_json::{json, Value};
+
+/// Step and job conclusions that count as failing
+const FAILED: &[&str] = &["failure", "timed_out", "startup_failure"];
+
+/// Error annotations kept per step
+const MAX_ERRORS: usize = 10;
+
+/// Drop the `2024-05-01T12:00:00.1234567Z ` prefix and a leading BOM
+fn strip_timestamp(line: &str) -> &str {
+    let line = line.trim_start_matches('\u{feff}');
+    match line.split_once(' ') {
+        Some((ts, rest)) if ts.len() >= 20 && ts.ends_with('Z') && ts.as_bytes()[10] == b'T' => {
+            rest
+        }
+        _ => line,
+    }
+}
+
+/// `(job, step, line)` for each line of `--log-failed` output
+fn parse_log(log: &str) -> Vec<(&str, &str, &str)> {
+    log.lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let job = parts.next()?;
+            let step = parts.next()?;
+            Some((job, step, strip_timestamp(parts.next().unwrap_or(""))))
+        })
+        .collect()
+}
+
+fn is_failed(item: &Value) -> bool {
+    FAILED.contains(&item["conclusion"].as_str().unwrap_or(""))
+}
+
+/// One entry per failing step (or failing job without a failing step) with
+/// the last `tail` lines of its log
+pub fn failures(jobs: &Value, log: &str, tail: usize) -> Vec<Value> {
+    let lines = parse_log(log);
+    let mut out = vec![];
+    for job in jobs["jobs"].as_array().into_iter().flatten() {
+        if !is_failed(job) {
+            continue;
+        }
+        let job_name = job["name"].as_str().unwrap_or("");
+        let job_lines: Vec<&(&str, &str, &str)> =
+            lines.iter().filter(|(j, _, _)| *j == job_name).collect();
+        let steps: Vec<&Value> = job["steps"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|s| is_failed(s))
+            .collect();
+        let named = |step: &str| -> Vec<&str> {
+            job_lines
+                .iter()
+                .filter(|(_, s, _)| *s == step)
+                .map(|(_, _, l)| *l)
+                .collect()
+        };
+        let whole_job = || job_lines.iter().map(|(_, _, l)| *l).collect::<Vec<_>>();
+
+        let mut entries: Vec<(Option<&Value>, Vec<&str>)> = steps
+            .iter()
+            .map(|step| (Some(*step), named(step["name"].as_str().unwrap_or(""))))
+            .collect();
+        match entries.as_mut_slice() {
+            [] => entries.push((None, whole_job())),
+            [(_, step_lines)] if step_lines.is_empty() => *step_lines = whole_job(),
+            _ => {}
+        }
+
+        for (step, step_lines) in entries {
+            let errors: Vec<&str> = step_lines
+                .iter()
+                .filter_map(|l| l.split_once("##[error]").map(|(_, e)| e.trim()))
+                .take(MAX_ERRORS)
+                .collect();
+            let start = step_lines.len().saturating_sub(tail);
+            out.push(json!({
+                "job": job_name,
+                "job_id": job["databaseId"],
+                "job_url": job["url"],
+                "step": step.map(|s| &s["name"]),
+                "step_number": step.map(|s| &s["number"]),
+                "conclusion": step.unwrap_or(job)["conclusion"],
+                "total_lines": step_lines.len(),
+                "errors": errors,
+                "lines": &step_lines[start..],
+            }));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jobs() -> Value {
+        json!({"jobs": [
+            {"databaseId": 1, "name": "lint", "conclusion": "success", "steps": []},
+            {"databaseId": 2, "name": "test (ubuntu-latest)", "conclusion": "failure", "url": "https://example/job/2",
+             "steps": [
+                {"name": "Checkout", "number": 1, "conclusion": "success"},
+                {"name": "Run tests", "number": 3, "conclusion": "failure"},
+             ]},
+        ]})
+    }
+
+    #[test]
+    fn test_failures_by_step_name() {
+        let log = "test (ubuntu-latest)\tRun tests\t2024-05-01T12:00:00.1234567Z running 3 tests\n\
+                   test (ubuntu-latest)\tRun tests\t2024-05-01T12:00:01.1234567Z test a ... FAILED\n\
+                   test (ubuntu-latest)\tRun tests\t2024-05-01T12:00:02.1234567Z ##[error]Process completed with exit code 101.\n";
+        let found = failures(&jobs(), log, 2);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0]["step"], "Run tests");
+        assert_eq!(found[0]["total_lines"], 3);
+        assert_eq!(
+            found[0]["lines"],
+            json!([
+                "test a ... FAILED",
+                "##[error]Process completed with exit code 101."
+            ])
+        );
+        assert_eq!(
+            found[0]["errors"],
+            json!(["Process completed with exit code 101."])
+        );
+    }
+
+    #[test]
+    fn test_failures_unknown_step() {
+        let log = "test (ubuntu-latest)\tUNKNOWN STEP\t\u{feff}2024-05-01T12:00:00.1234567Z boom\n";
+        let found = failures(&jobs(), log, 50);
+        assert_eq!(found[0]["step_number"], 3);
+        assert_eq!(found[0]["lines"], json!(["boom"]));
+    }
+}
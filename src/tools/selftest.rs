@@ -0,0 +1,276 @@
+// modern-cli-mcp/src/tools/selftest.rs
+//! Deployment self-test.
+//!
+//! Runs one representative tool per group against throwaway fixtures (a temp
+//! directory with text files, sample JSON, an SSH config, a kustomization and
+//! a one-commit git repository) and records pass, fail or missing binary with
+//! timings, so a new install or a tool upgrade can be checked from inside the
+//! agent. A case passes when the tool returns no error and its output contains
+//! the expected marker.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use rmcp::model::{CallToolResult, RawContent, ResourceContents};
+use serde_json::{json, Value};
+
+use crate::groups::ToolGroup;
+
+/// Text every fixture search, read and diff looks for
+pub const NEEDLE: &str = "selftest-needle";
+
+/// What a group's self-test runs and how it is judged
+pub struct Case {
+    pub tool: &'static str,
+    /// Binary the tool shells out to, if any
+    pub binary: Option<&'static str>,
+    /// Marker the output must contain; empty accepts any successful output
+    pub expect: &'static str,
+}
+
+pub fn case(group: ToolGroup) -> Case {
+    let (tool, binary, expect) = match group {
+        ToolGroup::Filesystem => ("Filesystem - List (eza)", Some("eza"), "notes.txt"),
+        ToolGroup::FileOps => ("File - Write + File - Read", None, NEEDLE),
+        ToolGroup::Search => ("Search - Content (ripgrep)", Some("rg"), NEEDLE),
+        ToolGroup::Text => ("Text - JSON (jq)", Some("jq"), NEEDLE),
+        ToolGroup::Git => ("Git - Status", Some("git"), ""),
+        ToolGroup::GitHub => ("GitHub - Auth Status", Some("gh"), ""),
+        ToolGroup::GitLab => ("GitLab - Auth Status", Some("glab"), ""),
+        ToolGroup::Kubernetes => ("Kubernetes - Kustomize", Some("kustomize"), NEEDLE),
+        ToolGroup::Container => ("Container - Podman", Some("podman"), ""),
+        ToolGroup::Network => ("Network - SSH Config", None, "192.0.2.10"),
+        ToolGroup::System => ("System - Info", None, ""),
+        ToolGroup::Archive => (
+            "Archive - Compress + List (ouch)",
+            Some("ouch"),
+            "notes.txt",
+        ),
+        ToolGroup::Reference => ("Reference - When", None, ""),
+        ToolGroup::Diff => ("Diff - Files (delta)", Some("delta"), ""),
+        ToolGroup::Mcp => ("MCP - Cache Set + Get", None, NEEDLE),
+    };
+    Case {
+        tool,
+        binary,
+        expect,
+    }
+}
+
+/// Create the fixtures in `dir`. The git repository is left out when git is
+/// unavailable; the git case then reports the binary as missing.
+pub fn create_fixtures(dir: &Path) -> std::io::Result<()> {
+    std::fs::write(dir.join("notes.txt"), format!("hello {}\n", NEEDLE))?;
+    std::fs::write(
+        dir.join("notes-v2.txt"),
+        format!("hello {}\nagain\n", NEEDLE),
+    )?;
+    std::fs::write(
+        dir.join("sample.json"),
+        json!({"name": NEEDLE, "items": [1, 2, 3]}).to_string(),
+    )?;
+    std::fs::write(
+        dir.join("ssh_config"),
+        "Host selftest\n  HostName 192.0.2.10\n  User tester\n",
+    )?;
+
+    let k8s = dir.join("k8s");
+    std::fs::create_dir_all(&k8s)?;
+    std::fs::write(
+        k8s.join("kustomization.yaml"),
+        "resources:\n  - configmap.yaml\n",
+    )?;
+    std::fs::write(
+        k8s.join("configmap.yaml"),
+        format!(
+            "apiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: {}\ndata:\n  key: value\n",
+            NEEDLE
+        ),
+    )?;
+
+    let repo = dir.join("repo");
+    std::fs::create_dir_all(&repo)?;
+    std::fs::write(repo.join("README.md"), "# selftest\n")?;
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args([
+                "-c",
+                "user.name=selftest",
+                "-c",
+                "user.email=selftest@localhost",
+            ])
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    };
+    let _ = git(&["init", "-q"]) && git(&["add", "."]) && git(&["commit", "-qm", "init"]);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Status {
+    fn id(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Missing => "missing",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub group: &'static str,
+    pub tool: &'static str,
+    pub status: Status,
+    pub duration: Duration,
+    /// Why a case failed, or which binary is missing
+    pub detail: Option<String>,
+}
+
+impl Outcome {
+    pub fn missing(group: ToolGroup, case: &Case, binary: &str) -> Self {
+        Self {
+            group: group.id(),
+            tool: case.tool,
+            status: Status::Missing,
+            duration: Duration::ZERO,
+            detail: Some(format!("{} is not installed", binary)),
+        }
+    }
+
+    /// Judge a tool result against the case's expected marker
+    pub fn judge<E: std::fmt::Display>(
+        group: ToolGroup,
+        case: &Case,
+        duration: Duration,
+        result: Result<CallToolResult, E>,
+    ) -> Self {
+        let (status, detail) = match result {
+            Err(e) => (Status::Fail, Some(e.to_string())),
+            Ok(result) => {
+                // Summary text and, in dual-response mode, the embedded raw data
+                let text: String = result
+                    .content
+                    .iter()
+                    .filter_map(|c| match &c.raw {
+                        RawContent::Text(t) => Some(t.text.as_str()),
+                        RawContent::Resource(r) => match &r.resource {
+                            ResourceContents::TextResourceContents { text, .. } => Some(text),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let first_line = || {
+                    let line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+                    Some(line.chars().take(200).collect())
+                };
+                if result.is_error == Some(true) {
+                    (Status::Fail, first_line())
+                } else if !text.contains(case.expect) {
+                    (
+                        Status::Fail,
+                        Some(format!("output lacks \"{}\"", case.expect)),
+                    )
+                } else {
+                    (Status::Pass, None)
+                }
+            }
+        };
+        Self {
+            group: group.id(),
+            tool: case.tool,
+            status,
+            duration,
+            detail,
+        }
+    }
+}
+
+/// Counts and per-group results
+pub fn report(outcomes: &[Outcome], total: Duration) -> Value {
+    let count = |status| outcomes.iter().filter(|o| o.status == status).count();
+    let groups: Vec<Value> = outcomes
+        .iter()
+        .map(|o| {
+            json!({
+                "group": o.group,
+                "tool": o.tool,
+                "status": o.status.id(),
+                "duration_ms": o.duration.as_millis() as u64,
+                "detail": o.detail,
+            })
+        })
+        .collect();
+    json!({
+        "healthy": count(Status::Fail) == 0,
+        "passed": count(Status::Pass),
+        "failed": count(Status::Fail),
+        "missing": count(Status::Missing),
+        "duration_ms": total.as_millis() as u64,
+        "groups": groups,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::Content;
+
+    #[test]
+    fn test_fixtures() {
+        let tmp = tempfile::tempdir().unwrap();
+        create_fixtures(tmp.path()).unwrap();
+        let json: Value =
+            serde_json::from_str(&std::fs::read_to_string(tmp.path().join("sample.json")).unwrap())
+                .unwrap();
+        assert_eq!(json["name"], NEEDLE);
+        assert!(tmp.path().join("k8s/kustomization.yaml").exists());
+        if which::which("git").is_ok() {
+            assert!(tmp.path().join("repo/.git").is_dir());
+        }
+    }
+
+    #[test]
+    fn test_judge_and_report() {
+        let search = case(ToolGroup::Search);
+        let found = CallToolResult::success(vec![
+            Content::text("1 match"),
+            Content::embedded_text("data://rg.txt", format!("notes.txt:1:{}", NEEDLE)),
+        ]);
+        let empty = CallToolResult::success(vec![Content::text("")]);
+        let failed = CallToolResult::error(vec![Content::text("\nrg: bad regex\nmore")]);
+        let ms = Duration::from_millis(5);
+        let outcomes = vec![
+            Outcome::judge(ToolGroup::Search, &search, ms, Ok::<_, String>(found)),
+            Outcome::judge(ToolGroup::Search, &search, ms, Ok::<_, String>(empty)),
+            Outcome::judge(ToolGroup::Search, &search, ms, Ok::<_, String>(failed)),
+            Outcome::missing(ToolGroup::Search, &search, "rg"),
+        ];
+        assert_eq!(outcomes[0].status, Status::Pass);
+        assert_eq!(
+            outcomes[1].detail.as_deref(),
+            Some("output lacks \"selftest-needle\"")
+        );
+        assert_eq!(outcomes[2].detail.as_deref(), Some("rg: bad regex"));
+
+        let report = report(&outcomes, ms);
+        assert_eq!(report["healthy"], false);
+        assert_eq!(
+            (report["passed"].as_u64(), report["failed"].as_u64()),
+            (Some(1), Some(2))
+        );
+        assert_eq!(report["groups"][3]["status"], "missing");
+    }
+}
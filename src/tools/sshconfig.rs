@@ -0,0 +1,234 @@
+// modern-cli-mcp/src/tools/sshconfig.rs
+//! SSH client configuration, agent keys and known_hosts entries.
+//!
+//! The `Host` blocks of ~/.ssh/config (and the files it includes) name the
+//! configured hosts. For one host, `ssh -G` prints the effective options the
+//! client would use, so identity files, ports and `IdentitiesOnly` come from
+//! ssh itself rather than from re-implementing its matching rules. Keys are
+//! compared by their SHA-256 fingerprints as printed by `ssh-add -l` and
+//! `ssh-keygen -l`, which is also how host keys from `ssh-keyscan` are
+//! checked against known_hosts.
+
+use serde_json::{json, Map, Value};
+
+/// Configured hosts, in file order, and the `Include` patterns seen
+#[derive(Debug, Default)]
+pub struct Config {
+    pub hosts: Vec<Value>,
+    pub includes: Vec<String>,
+}
+
+/// `Host` blocks of an ssh_config file; `Match` blocks are skipped
+pub fn parse_config(text: &str) -> Config {
+    let mut config = Config::default();
+    let mut current: Option<Map<String, Value>> = None;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = match line.split_once(|c: char| c.is_whitespace() || c == '=') {
+            Some((k, v)) => (
+                k.to_ascii_lowercase(),
+                v.trim_start_matches([' ', '\t', '=']).trim(),
+            ),
+            None => (line.to_ascii_lowercase(), ""),
+        };
+        let value = value.trim_matches('"');
+        match key.as_str() {
+            "host" | "match" => {
+                config.hosts.extend(current.take().map(Value::Object));
+                if key == "host" {
+                    let patterns: Vec<&str> = value.split_whitespace().collect();
+                    let mut host = Map::new();
+                    host.insert("patterns".into(), json!(patterns));
+                    host.insert(
+                        "wildcard".into(),
+                        json!(patterns
+                            .iter()
+                            .all(|p| p.contains(['*', '?']) || p.starts_with('!'))),
+                    );
+                    current = Some(host);
+                }
+            }
+            "include" => config
+                .includes
+                .extend(value.split_whitespace().map(String::from)),
+            "hostname" | "user" | "port" | "proxyjump" | "identitiesonly" => {
+                if let Some(host) = current.as_mut() {
+                    host.entry(key).or_insert_with(|| json!(value));
+                }
+            }
+            "identityfile" => {
+                if let Some(files) = current.as_mut().and_then(|host| {
+                    host.entry("identityfile")
+                        .or_insert_with(|| json!([]))
+                        .as_array_mut()
+                }) {
+                    files.push(json!(value));
+                }
+            }
+            _ => {}
+        }
+    }
+    config.hosts.extend(current.map(Value::Object));
+    config
+}
+
+/// Effective options from `ssh -G`; repeatable options become arrays
+pub fn parse_ssh_g(output: &str) -> Map<String, Value> {
+    let mut options = Map::new();
+    for line in output.lines() {
+        let Some((key, value)) = line.trim().split_once(' ') else {
+            continue;
+        };
+        match key {
+            "identityfile" | "certificatefile" | "localforward" | "remoteforward" => {
+                if let Some(list) = options
+                    .entry(key)
+                    .or_insert_with(|| json!([]))
+                    .as_array_mut()
+                {
+                    list.push(json!(value));
+                }
+            }
+            _ => {
+                options.insert(key.to_string(), json!(value));
+            }
+        }
+    }
+    options
+}
+
+/// Keys from `ssh-add -l -E sha256` or `ssh-keygen -l` lines such as
+/// `256 SHA256:abc... user@host (ED25519)`
+pub fn parse_fingerprints(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.trim().splitn(3, ' ');
+            let bits = parts.next()?.parse::<u32>().ok()?;
+            let fingerprint = parts.next()?;
+            if !fingerprint.contains(':') {
+                return None;
+            }
+            let rest = parts.next().unwrap_or("");
+            let (comment, key_type) = match rest.rfind(" (") {
+                Some(i) if rest.ends_with(')') => (&rest[..i], &rest[i + 2..rest.len() - 1]),
+                _ if rest.starts_with('(') && rest.ends_with(')') => ("", &rest[1..rest.len() - 1]),
+                _ => (rest, ""),
+            };
+            Some(json!({
+                "bits": bits,
+                "fingerprint": fingerprint,
+                "comment": comment,
+                "type": key_type,
+            }))
+        })
+        .collect()
+}
+
+/// Name a host is recorded under in known_hosts
+pub fn known_hosts_name(host: &str, port: u16) -> String {
+    if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{}]:{}", host, port)
+    }
+}
+
+/// `match` when a scanned key is already known, `mismatch` when the host is
+/// known under other keys only, `missing` when it is not known at all
+pub fn host_key_verdict(known: &[Value], scanned: &[Value]) -> &'static str {
+    let fingerprint = |k: &Value| k["fingerprint"].as_str().map(String::from);
+    let known: Vec<String> = known.iter().filter_map(fingerprint).collect();
+    if known.is_empty() {
+        "missing"
+    } else if scanned
+        .iter()
+        .filter_map(fingerprint)
+        .any(|f| known.contains(&f))
+    {
+        "match"
+    } else {
+        "mismatch"
+    }
+}
+
+/// Whether a caller-supplied fingerprint (with or without the `SHA256:`
+/// prefix) is one of the scanned keys
+pub fn fingerprint_expected(expected: &str, scanned: &[Value]) -> bool {
+    let expected = expected.trim();
+    let expected = expected.strip_prefix("SHA256:").unwrap_or(expected);
+    !expected.is_empty()
+        && scanned
+            .iter()
+            .filter_map(|k| k["fingerprint"].as_str())
+            .any(|f| f.strip_prefix("SHA256:").unwrap_or(f) == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let text = "Include config.d/*\n\
+            # work\n\
+            Host gitlab work-gl\n  HostName gitlab.example.com\n  User git\n  \
+            IdentityFile ~/.ssh/id_work\n  IdentityFile ~/.ssh/id_ed25519\n  IdentitiesOnly yes\n\
+            Match host *.internal\n  User admin\n\
+            Host bastion\n  HostName=10.0.0.1\n  Port 2222\n\
+            Host *\n  AddKeysToAgent yes\n";
+        let config = parse_config(text);
+        assert_eq!(config.includes, vec!["config.d/*"]);
+        assert_eq!(config.hosts.len(), 3);
+        assert_eq!(config.hosts[0]["patterns"], json!(["gitlab", "work-gl"]));
+        assert_eq!(
+            config.hosts[0]["identityfile"],
+            json!(["~/.ssh/id_work", "~/.ssh/id_ed25519"])
+        );
+        assert_eq!(config.hosts[1]["hostname"], "10.0.0.1");
+        assert_eq!(config.hosts[1]["port"], "2222");
+        assert!(config.hosts[1]["user"].is_null());
+        assert_eq!(config.hosts[2]["wildcard"], true);
+    }
+
+    #[test]
+    fn test_ssh_g_and_fingerprints() {
+        let options = parse_ssh_g(
+            "hostname gitlab.example.com\nport 22\nidentityfile ~/.ssh/id_rsa\nidentityfile ~/.ssh/id_ed25519\n",
+        );
+        assert_eq!(options["hostname"], "gitlab.example.com");
+        assert_eq!(options["identityfile"].as_array().unwrap().len(), 2);
+
+        let keys = parse_fingerprints(
+            "256 SHA256:AbCdEf me@laptop (ED25519)\n3072 SHA256:XyZ (RSA)\nThe agent has no identities.\n",
+        );
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0]["comment"], "me@laptop");
+        assert_eq!(keys[0]["type"], "ED25519");
+        assert_eq!(keys[1]["comment"], "");
+        assert_eq!(keys[1]["bits"], 3072);
+    }
+
+    #[test]
+    fn test_host_key_verdict() {
+        let key = |f: &str| json!({ "fingerprint": f });
+        assert_eq!(known_hosts_name("example.com", 22), "example.com");
+        assert_eq!(known_hosts_name("example.com", 2222), "[example.com]:2222");
+        assert_eq!(host_key_verdict(&[], &[key("SHA256:a")]), "missing");
+        assert_eq!(
+            host_key_verdict(&[key("SHA256:a")], &[key("SHA256:b"), key("SHA256:a")]),
+            "match"
+        );
+        assert_eq!(
+            host_key_verdict(&[key("SHA256:a")], &[key("SHA256:b")]),
+            "mismatch"
+        );
+        let scanned = [key("SHA256:b"), key("SHA256:c")];
+        assert!(fingerprint_expected("SHA256:c", &scanned));
+        assert!(fingerprint_expected(" b ", &scanned));
+        assert!(!fingerprint_expected("SHA256:a", &scanned));
+        assert!(!fingerprint_expected("", &scanned));
+    }
+}
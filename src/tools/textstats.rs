@@ -0,0 +1,143 @@
+// modern-cli-mcp/src/tools/textstats.rs
+//! Counting text the way people read it.
+//!
+//! Bytes, code points and user-perceived characters (grapheme clusters)
+//! differ as soon as text leaves ASCII: `é` may be one or two code points
+//! and a flag emoji is two code points but one character. Words and
+//! sentences follow Unicode segmentation rules. File input is decoded from
+//! its detected encoding (BOM, UTF-8, or a chardetng guess) first, and the
+//! language is guessed with whatlang.
+
+use serde_json::{json, Value};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Text decoded from bytes, and how
+pub struct Decoded {
+    pub text: String,
+    pub encoding: &'static str,
+    pub bom: bool,
+    /// Malformed sequences were replaced with U+FFFD
+    pub lossy: bool,
+}
+
+/// Decode `bytes` by BOM, as UTF-8 when valid, or by a detected legacy encoding
+pub fn decode(bytes: &[u8]) -> Decoded {
+    if let Some((encoding, bom_len)) = encoding_rs::Encoding::for_bom(bytes) {
+        let (text, lossy) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        return Decoded {
+            text: text.into_owned(),
+            encoding: encoding.name(),
+            bom: true,
+            lossy,
+        };
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Decoded {
+            text: text.to_string(),
+            encoding: if text.is_ascii() { "ASCII" } else { "UTF-8" },
+            bom: false,
+            lossy: false,
+        };
+    }
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    let encoding = detector.guess(None, true);
+    let (text, _, lossy) = encoding.decode(bytes);
+    Decoded {
+        text: text.into_owned(),
+        encoding: encoding.name(),
+        bom: false,
+        lossy,
+    }
+}
+
+fn line_endings(text: &str) -> &'static str {
+    let crlf = text.matches("\r\n").count();
+    let lf = text.matches('\n').count() - crlf;
+    let cr = text.matches('\r').count() - crlf;
+    match (lf > 0, crlf > 0, cr > 0) {
+        (false, false, false) => "none",
+        (true, false, false) => "lf",
+        (false, true, false) => "crlf",
+        (false, false, true) => "cr",
+        _ => "mixed",
+    }
+}
+
+/// Counts, line shape and language for `text` (`bytes` is the size before
+/// decoding)
+pub fn stats(text: &str, bytes: usize) -> Value {
+    let lines: Vec<&str> = text.lines().collect();
+    let longest = lines
+        .iter()
+        .map(|l| l.graphemes(true).count())
+        .max()
+        .unwrap_or(0);
+    let language = whatlang::detect(text).map(|info| {
+        json!({
+            "code": info.lang().code(),
+            "name": info.lang().eng_name(),
+            "script": info.script().name(),
+            "confidence": (info.confidence() * 100.0).round() / 100.0,
+            "reliable": info.is_reliable(),
+        })
+    });
+    json!({
+        "bytes": bytes,
+        "utf8_bytes": text.len(),
+        "chars": text.chars().count(),
+        "graphemes": text.graphemes(true).count(),
+        "words": text.unicode_words().count(),
+        "sentences": text.unicode_sentences().filter(|s| !s.trim().is_empty()).count(),
+        "lines": lines.len(),
+        "blank_lines": lines.iter().filter(|l| l.trim().is_empty()).count(),
+        "longest_line": longest,
+        "line_endings": line_endings(text),
+        "trailing_newline": text.ends_with('\n'),
+        "non_ascii_chars": text.chars().filter(|c| !c.is_ascii()).count(),
+        "control_chars": text
+            .chars()
+            .filter(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+            .count(),
+        "language": language,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        // "é" as e + combining acute, and a two code point flag
+        let text = "Cafe\u{301} au lait 🇫🇷.\r\nSecond line here.\r\n";
+        let s = stats(text, text.len());
+        assert_eq!(s["graphemes"], 34);
+        assert_eq!(s["chars"], 38);
+        assert_eq!(s["words"], 6);
+        assert_eq!(s["lines"], 2);
+        assert_eq!(s["line_endings"], "crlf");
+        assert_eq!(s["longest_line"], 17);
+
+        let s = stats(
+            "Le renard brun rapide saute par-dessus le chien paresseux.",
+            0,
+        );
+        assert_eq!(s["language"]["code"], "fra");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(b"plain").encoding, "ASCII");
+        let utf16 = [0xFF, 0xFE, b'h', 0, b'i', 0];
+        let decoded = decode(&utf16);
+        assert_eq!(
+            (decoded.text.as_str(), decoded.encoding, decoded.bom),
+            ("hi", "UTF-16LE", true)
+        );
+        let latin1 = b"Caf\xe9 cr\xe8me br\xfbl\xe9e, na\xefve fa\xe7ade";
+        let decoded = decode(latin1);
+        assert_eq!(decoded.encoding, "windows-1252");
+        assert!(decoded.text.starts_with("Café crème"));
+    }
+}
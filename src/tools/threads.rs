@@ -0,0 +1,279 @@
+// modern-cli-mcp/src/tools/threads.rs
+//! Issue and pull/merge request threads as markdown resources.
+//!
+//! Viewing an issue, PR or MR renders its description and comments as one
+//! markdown document under a stable URI derived from the web URL:
+//! `github://owner/repo/issues/12`, `github://owner/repo/pull/7`,
+//! `gitlab://group/project/issues/3`, `gitlab://group/project/merge_requests/4`.
+//! The tool response carries only the URI and counts; clients attach the
+//! thread as context by reading the resource, which is fetched again once the
+//! cached copy has expired.
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+}
+
+impl Forge {
+    /// Lowercase name, as in thread URIs and content warnings
+    pub fn name(self) -> &'static str {
+        match self {
+            Forge::GitHub => "github",
+            Forge::GitLab => "gitlab",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Issue,
+    /// Pull request (GitHub) or merge request (GitLab)
+    Change,
+}
+
+/// What a thread URI points at
+#[derive(Debug, PartialEq)]
+pub struct Target {
+    pub forge: Forge,
+    /// `owner/repo` or `group/subgroup/project`
+    pub project: String,
+    pub kind: Kind,
+    pub number: u32,
+}
+
+impl Target {
+    pub fn uri(&self) -> String {
+        let (scheme, segment) = match (self.forge, self.kind) {
+            (Forge::GitHub, Kind::Issue) => ("github", "issues"),
+            (Forge::GitHub, Kind::Change) => ("github", "pull"),
+            (Forge::GitLab, Kind::Issue) => ("gitlab", "issues"),
+            (Forge::GitLab, Kind::Change) => ("gitlab", "merge_requests"),
+        };
+        format!("{}://{}/{}/{}", scheme, self.project, segment, self.number)
+    }
+
+    /// Parse a thread URI
+    pub fn parse(uri: &str) -> Option<Self> {
+        let (forge, rest) = if let Some(rest) = uri.strip_prefix("github://") {
+            (Forge::GitHub, rest)
+        } else {
+            (Forge::GitLab, uri.strip_prefix("gitlab://")?)
+        };
+        let mut parts = rest.rsplitn(3, '/');
+        let number = parts.next()?.parse().ok()?;
+        let kind = match (forge, parts.next()?) {
+            (Forge::GitHub, "issues") | (Forge::GitLab, "issues") => Kind::Issue,
+            (Forge::GitHub, "pull") | (Forge::GitLab, "merge_requests") => Kind::Change,
+            _ => return None,
+        };
+        let project = parts.next().filter(|p| p.contains('/'))?.to_string();
+        Some(Self {
+            forge,
+            project,
+            kind,
+            number,
+        })
+    }
+
+    /// Target of a web URL such as `https://github.com/o/r/pull/7` or
+    /// `https://gitlab.com/g/p/-/merge_requests/4`
+    pub fn from_url(forge: Forge, url: &str) -> Option<Self> {
+        let path = url.split_once("://")?.1.split_once('/')?.1;
+        let path = path.split(['?', '#']).next()?.trim_end_matches('/');
+        let mut parts = path.rsplitn(3, '/');
+        let number = parts.next()?.parse().ok()?;
+        let kind = match parts.next()? {
+            "issues" => Kind::Issue,
+            "pull" | "merge_requests" => Kind::Change,
+            _ => return None,
+        };
+        let project = parts.next()?.trim_end_matches("/-").to_string();
+        Some(Self {
+            forge,
+            project,
+            kind,
+            number,
+        })
+    }
+}
+
+/// One comment, review or note
+#[derive(Debug)]
+pub struct Comment {
+    pub author: String,
+    pub created: String,
+    pub body: String,
+    /// Review verdict such as APPROVED, for PR reviews
+    pub state: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Thread {
+    pub title: String,
+    pub url: String,
+    pub state: String,
+    pub author: String,
+    pub created: String,
+    pub body: String,
+    pub comments: Vec<Comment>,
+}
+
+fn text(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+impl Thread {
+    /// From `gh issue view --json ...,comments` or `gh pr view --json
+    /// ...,body,comments,reviews`. Reviews without a body are verdicts only
+    /// and are listed with their state.
+    pub fn from_gh(view: &Value) -> Self {
+        let mut comments: Vec<Comment> = view["comments"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|c| Comment {
+                author: text(&c["author"]["login"]),
+                created: text(&c["createdAt"]),
+                body: text(&c["body"]),
+                state: None,
+            })
+            .collect();
+        comments.extend(
+            view["reviews"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|r| Comment {
+                    author: text(&r["author"]["login"]),
+                    created: text(&r["submittedAt"]),
+                    body: text(&r["body"]),
+                    state: r["state"].as_str().map(String::from),
+                }),
+        );
+        comments.sort_by(|a, b| a.created.cmp(&b.created));
+        Self {
+            title: text(&view["title"]),
+            url: text(&view["url"]),
+            state: text(&view["state"]),
+            author: text(&view["author"]["login"]),
+            created: text(&view["createdAt"]),
+            body: text(&view["body"]),
+            comments,
+        }
+    }
+
+    /// From `glab issue/mr view --output json` and the notes API; system
+    /// notes (label changes, pushes) are left out
+    pub fn from_glab(view: &Value, notes: &Value) -> Self {
+        let mut comments: Vec<Comment> = notes
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|n| n["system"] != true)
+            .map(|n| Comment {
+                author: text(&n["author"]["username"]),
+                created: text(&n["created_at"]),
+                body: text(&n["body"]),
+                state: None,
+            })
+            .collect();
+        comments.sort_by(|a, b| a.created.cmp(&b.created));
+        Self {
+            title: text(&view["title"]),
+            url: text(&view["web_url"]),
+            state: text(&view["state"]),
+            author: text(&view["author"]["username"]),
+            created: text(&view["created_at"]),
+            body: text(&view["description"]),
+            comments,
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let date = |d: &str| d.get(..10).unwrap_or(d).to_string();
+        let mut out = format!(
+            "# {}\n\n{} · {} · opened by @{} on {}\n\n{}\n",
+            self.title,
+            self.url,
+            self.state,
+            self.author,
+            date(&self.created),
+            if self.body.trim().is_empty() {
+                "_No description._"
+            } else {
+                self.body.trim()
+            }
+        );
+        for comment in &self.comments {
+            let verb = match comment.state.as_deref() {
+                Some(state) => format!("reviewed ({})", state.to_lowercase()),
+                None => "commented".to_string(),
+            };
+            out.push_str(&format!(
+                "\n---\n\n**@{}** {} on {}:\n",
+                comment.author,
+                verb,
+                date(&comment.created)
+            ));
+            if !comment.body.trim().is_empty() {
+                out.push_str(&format!("\n{}\n", comment.body.trim()));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_uris() {
+        let pr = Target::from_url(Forge::GitHub, "https://github.com/o/r/pull/7").unwrap();
+        assert_eq!(pr.uri(), "github://o/r/pull/7");
+        assert_eq!(Target::parse("github://o/r/pull/7"), Some(pr));
+
+        let mr = Target::from_url(
+            Forge::GitLab,
+            "https://gitlab.com/g/sub/p/-/merge_requests/4#note_1",
+        )
+        .unwrap();
+        assert_eq!(mr.project, "g/sub/p");
+        assert_eq!(mr.uri(), "gitlab://g/sub/p/merge_requests/4");
+        assert_eq!(Target::parse(&mr.uri()), Some(mr));
+
+        assert!(Target::parse("github://o/r/pull/x").is_none());
+        assert!(Target::parse("github://r/issues/1").is_none());
+        assert!(Target::parse("gitlab://g/p/pull/1").is_none());
+    }
+
+    #[test]
+    fn test_render_threads() {
+        let view = json!({
+            "title": "Crash on start", "url": "https://github.com/o/r/pull/7", "state": "OPEN",
+            "author": {"login": "ann"}, "createdAt": "2025-01-02T10:00:00Z", "body": "Steps...",
+            "comments": [{"author": {"login": "bob"}, "createdAt": "2025-01-04T00:00:00Z", "body": "Same here"}],
+            "reviews": [{"author": {"login": "cy"}, "submittedAt": "2025-01-03T00:00:00Z",
+                         "state": "APPROVED", "body": ""}]
+        });
+        let markdown = Thread::from_gh(&view).render();
+        assert!(markdown.starts_with("# Crash on start\n"));
+        assert!(markdown.contains("opened by @ann on 2025-01-02"));
+        let review = markdown.find("**@cy** reviewed (approved)").unwrap();
+        assert!(review < markdown.find("**@bob** commented on 2025-01-04").unwrap());
+
+        let view = json!({"title": "T", "web_url": "u", "state": "opened",
+                          "author": {"username": "dee"}, "created_at": "2025-02-01T00:00:00Z"});
+        let notes = json!([
+            {"author": {"username": "eve"}, "created_at": "2025-02-02T00:00:00Z", "body": "Hi", "system": false},
+            {"author": {"username": "bot"}, "created_at": "2025-02-03T00:00:00Z", "body": "added label", "system": true}
+        ]);
+        let thread = Thread::from_glab(&view, &notes);
+        assert_eq!(thread.comments.len(), 1);
+        assert!(thread.render().contains("_No description._"));
+    }
+}
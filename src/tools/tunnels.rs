@@ -0,0 +1,294 @@
+// modern-cli-mcp/src/tools/tunnels.rs
+//! SSH port forwards kept open between tool calls.
+//!
+//! Each tunnel is an `ssh -N` child with `ExitOnForwardFailure`, so a port
+//! that cannot be bound on either side ends the process instead of leaving a
+//! half-working tunnel. A local forward counts as open once its listening
+//! port accepts connections; a remote forward once ssh has stayed up past
+//! authentication. Children are isolated like every other spawned command
+//! and die with the server, and their stderr is kept for `list`.
+
+use super::proctree;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+
+/// Stderr lines kept per tunnel
+const STDERR_LINES: usize = 50;
+
+/// How long a remote forward must survive to count as established
+const REMOTE_SETTLE: Duration = Duration::from_secs(3);
+
+/// `[bind:]port:host:hostport`, the argument of `ssh -L` and `ssh -R`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forward {
+    pub bind: Option<String>,
+    pub port: u16,
+    pub host: String,
+    pub host_port: u16,
+}
+
+impl Forward {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let port = |p: &str| {
+            p.parse::<u16>()
+                .map_err(|_| format!("Invalid port '{}' in forward '{}'", p, spec))
+        };
+        let parts: Vec<&str> = spec.split(':').collect();
+        let (bind, rest) = match parts.len() {
+            3 => (None, &parts[..]),
+            4 => (Some(parts[0].to_string()), &parts[1..]),
+            _ => {
+                return Err(format!(
+                    "Invalid forward '{}': expected [bind:]port:host:hostport",
+                    spec
+                ))
+            }
+        };
+        if rest[1].is_empty() {
+            return Err(format!("Missing target host in forward '{}'", spec));
+        }
+        Ok(Self {
+            bind,
+            port: port(rest[0])?,
+            host: rest[1].to_string(),
+            host_port: port(rest[2])?,
+        })
+    }
+
+    pub fn spec(&self) -> String {
+        let target = format!("{}:{}:{}", self.port, self.host, self.host_port);
+        match &self.bind {
+            Some(bind) => format!("{}:{}", bind, target),
+            None => target,
+        }
+    }
+
+    /// Address a local forward listens on; ssh binds loopback by default
+    fn listen_addr(&self) -> String {
+        let bind = match self.bind.as_deref() {
+            None | Some("") | Some("localhost") | Some("*") | Some("0.0.0.0") => "127.0.0.1",
+            Some(bind) => bind,
+        };
+        format!("{}:{}", bind, self.port)
+    }
+}
+
+struct Tunnel {
+    host: String,
+    reverse: bool,
+    forward: Forward,
+    started: chrono::DateTime<chrono::Utc>,
+    child: Child,
+    stderr: Arc<Mutex<Vec<String>>>,
+}
+
+impl Tunnel {
+    fn describe(&mut self, id: u32) -> Value {
+        let (status, exit_code) = match self.child.try_wait() {
+            Ok(None) => ("running", None),
+            Ok(Some(status)) => ("exited", status.code()),
+            Err(_) => ("unknown", None),
+        };
+        json!({
+            "id": id,
+            "host": self.host,
+            "direction": if self.reverse { "remote" } else { "local" },
+            "forward": self.forward.spec(),
+            "pid": self.child.id(),
+            "started": self.started.to_rfc3339(),
+            "status": status,
+            "exit_code": exit_code,
+            "stderr": self.stderr.lock().clone(),
+        })
+    }
+}
+
+/// Open tunnels, keyed by id
+#[derive(Default)]
+pub struct Tunnels {
+    active: Mutex<BTreeMap<u32, Tunnel>>,
+    next_id: Mutex<u32>,
+}
+
+impl std::fmt::Debug for Tunnels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tunnels")
+            .field("ids", &self.active.lock().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Tunnels {
+    /// Start `ssh -N -L` (or `-R` when `reverse`) to `host` and wait up to
+    /// `timeout` for the forward to be usable
+    pub async fn open(
+        &self,
+        host: &str,
+        forward: Forward,
+        reverse: bool,
+        timeout: Duration,
+    ) -> Result<Value, String> {
+        // Otherwise the readiness probe would reach whatever already listens
+        if !reverse
+            && tokio::net::TcpStream::connect(forward.listen_addr())
+                .await
+                .is_ok()
+        {
+            return Err(format!("{} is already in use", forward.listen_addr()));
+        }
+        let mut command = Command::new("ssh");
+        command
+            .args([
+                "-N",
+                "-o",
+                "ExitOnForwardFailure=yes",
+                "-o",
+                "BatchMode=yes",
+            ])
+            .args(["-o", "ServerAliveInterval=30"])
+            .arg(if reverse { "-R" } else { "-L" })
+            .arg(forward.spec())
+            .arg(host)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        proctree::isolate(&mut command);
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("Failed to start ssh: {}", e))?;
+
+        let stderr = Arc::new(Mutex::new(Vec::new()));
+        if let Some(pipe) = child.stderr.take() {
+            let lines = stderr.clone();
+            tokio::spawn(async move {
+                let mut reader = BufReader::new(pipe).lines();
+                while let Ok(Some(line)) = reader.next_line().await {
+                    let mut lines = lines.lock();
+                    if lines.len() == STDERR_LINES {
+                        lines.remove(0);
+                    }
+                    lines.push(line);
+                }
+            });
+        }
+
+        let started = Instant::now();
+        loop {
+            if let Ok(Some(status)) = child.try_wait() {
+                // Let the reader drain what ssh printed before exiting
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                return Err(format!(
+                    "ssh exited ({}): {}",
+                    status,
+                    stderr.lock().join("\n")
+                ));
+            }
+            let ready = if reverse {
+                started.elapsed() >= REMOTE_SETTLE.min(timeout)
+            } else {
+                tokio::net::TcpStream::connect(forward.listen_addr())
+                    .await
+                    .is_ok()
+            };
+            if ready {
+                break;
+            }
+            if started.elapsed() >= timeout {
+                let _ = child.start_kill();
+                return Err(format!(
+                    "Tunnel not ready after {}s: {}",
+                    timeout.as_secs(),
+                    stderr.lock().join("\n")
+                ));
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+
+        let id = {
+            let mut next = self.next_id.lock();
+            *next += 1;
+            *next
+        };
+        let mut tunnel = Tunnel {
+            host: host.to_string(),
+            reverse,
+            forward,
+            started: chrono::Utc::now(),
+            child,
+            stderr,
+        };
+        let described = tunnel.describe(id);
+        self.active.lock().insert(id, tunnel);
+        Ok(described)
+    }
+
+    /// Every tracked tunnel, including ones whose ssh has since exited
+    pub fn list(&self) -> Vec<Value> {
+        self.active
+            .lock()
+            .iter_mut()
+            .map(|(id, tunnel)| tunnel.describe(*id))
+            .collect()
+    }
+
+    /// Stop one tunnel, or all of them when `id` is None. The whole process
+    /// group is killed, which includes the helper ssh a ProxyJump starts
+    pub fn stop(&self, id: Option<u32>) -> Result<Vec<Value>, String> {
+        let mut active = self.active.lock();
+        let ids: Vec<u32> = match id {
+            Some(id) if active.contains_key(&id) => vec![id],
+            Some(id) => return Err(format!("No tunnel with id {}", id)),
+            None => active.keys().copied().collect(),
+        };
+        Ok(ids
+            .into_iter()
+            .filter_map(|id| {
+                let mut tunnel = active.remove(&id)?;
+                let mut described = tunnel.describe(id);
+                proctree::TreeGuard::new(tunnel.child.id()).kill();
+                described["status"] = json!("stopped");
+                Some(described)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_forward() {
+        let forward = Forward::parse("5432:db.internal:5432").unwrap();
+        assert_eq!(forward.bind, None);
+        assert_eq!(forward.host, "db.internal");
+        assert_eq!(forward.listen_addr(), "127.0.0.1:5432");
+        assert_eq!(forward.spec(), "5432:db.internal:5432");
+
+        let bound = Forward::parse("127.0.0.2:8080:localhost:80").unwrap();
+        assert_eq!(bound.listen_addr(), "127.0.0.2:8080");
+        assert_eq!(bound.spec(), "127.0.0.2:8080:localhost:80");
+    }
+
+    #[test]
+    fn test_parse_forward_errors() {
+        assert!(Forward::parse("8080").is_err());
+        assert!(Forward::parse("8080::80").is_err());
+        assert!(Forward::parse("http:db:5432").is_err());
+        assert!(Forward::parse("8080:db:99999").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stop_unknown() {
+        let tunnels = Tunnels::default();
+        assert!(tunnels.list().is_empty());
+        assert!(tunnels.stop(Some(3)).is_err());
+        assert!(tunnels.stop(None).unwrap().is_empty());
+    }
+}
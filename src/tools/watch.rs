@@ -0,0 +1,156 @@
+// modern-cli-mcp/src/tools/watch.rs
+//! Polling file watcher for build and test loops.
+//!
+//! A snapshot maps each watched file to its modification time and size; two
+//! snapshots differ in the files added, removed or rewritten between them.
+//! Polling instead of inotify keeps the watch bounded and portable, and
+//! honors only .agentignore, like the other walkers. The baseline is taken
+//! after each run, so the run's own build output under `target/` or
+//! `node_modules/` does not trigger the next one. Only pass/fail transitions
+//! are worth reporting; repeated results are counted.
+
+use crate::ignore::AgentIgnore;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Lines of failing output kept per transition
+pub const OUTPUT_LINES: usize = 60;
+
+pub type Snapshot = HashMap<PathBuf, (SystemTime, u64)>;
+
+/// Glob set for the trigger patterns
+pub fn glob_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).map_err(|e| format!("Invalid glob '{}': {}", pattern, e))?);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Modification time and size of every file under `root` that is not
+/// ignored and, when `globs` is given, matches it
+pub fn snapshot(root: &Path, ignore: &Arc<AgentIgnore>, globs: Option<&GlobSet>) -> Snapshot {
+    crate::ignore::walker(root, ignore)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .filter(|e| {
+            globs.is_none_or(|g| g.is_match(e.path().strip_prefix(root).unwrap_or(e.path())))
+        })
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some((e.into_path(), (meta.modified().ok()?, meta.len())))
+        })
+        .collect()
+}
+
+/// Files added, removed or rewritten between two snapshots, sorted
+pub fn changed(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, stamp)| before.get(*path) != Some(stamp))
+        .map(|(path, _)| path.clone())
+        .chain(before.keys().filter(|p| !after.contains_key(*p)).cloned())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// `pass→fail` or `fail→pass` when the result flipped; the first run is
+/// always reported as its own state
+pub fn transition(previous: Option<bool>, passed: bool) -> Option<&'static str> {
+    match (previous, passed) {
+        (None, true) => Some("pass"),
+        (None, false) => Some("fail"),
+        (Some(true), false) => Some("pass→fail"),
+        (Some(false), true) => Some("fail→pass"),
+        _ => None,
+    }
+}
+
+/// Last `max` lines of stdout followed by stderr, where failures end up
+pub fn output_tail(stdout: &str, stderr: &str, max: usize) -> String {
+    let lines: Vec<&str> = stdout.lines().chain(stderr.lines()).collect();
+    let start = lines.len().saturating_sub(max);
+    let mut out = lines[start..].join("\n");
+    if start > 0 {
+        out.insert_str(0, &format!("... {} lines omitted\n", start));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_changed() {
+        let t = SystemTime::UNIX_EPOCH;
+        let before: Snapshot = [
+            (PathBuf::from("a.rs"), (t, 10)),
+            (PathBuf::from("b.rs"), (t, 20)),
+            (PathBuf::from("c.rs"), (t, 30)),
+        ]
+        .into();
+        let mut after = before.clone();
+        after.insert(PathBuf::from("a.rs"), (t + Duration::from_secs(1), 10));
+        after.remove(Path::new("b.rs"));
+        after.insert(PathBuf::from("d.rs"), (t, 1));
+        assert_eq!(
+            changed(&before, &after),
+            vec![
+                PathBuf::from("a.rs"),
+                PathBuf::from("b.rs"),
+                PathBuf::from("d.rs")
+            ]
+        );
+        assert!(changed(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_honors_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "x").unwrap();
+        let ignore = Arc::new(AgentIgnore::default());
+        assert_eq!(snapshot(dir.path(), &ignore, None).len(), 2);
+
+        let globs = glob_set(&["*.rs".to_string()]).unwrap();
+        let snap = snapshot(dir.path(), &ignore, Some(&globs));
+        assert_eq!(snap.len(), 1);
+        assert!(snap.contains_key(&dir.path().join("lib.rs")));
+        assert!(glob_set(&["src/[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_ignores_only_agentignore() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "generated.rs\n").unwrap();
+        std::fs::write(dir.path().join(".agentignore"), "secret.rs\n").unwrap();
+        for name in ["generated.rs", ".hidden.rs", "secret.rs"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        let ignore = Arc::new(AgentIgnore::default());
+        let globs = glob_set(&["*.rs".to_string()]).unwrap();
+        let snap = snapshot(dir.path(), &ignore, Some(&globs));
+        assert!(snap.contains_key(&dir.path().join("generated.rs")));
+        assert!(snap.contains_key(&dir.path().join(".hidden.rs")));
+        assert!(!snap.contains_key(&dir.path().join("secret.rs")));
+    }
+
+    #[test]
+    fn test_transition_and_tail() {
+        assert_eq!(transition(None, false), Some("fail"));
+        assert_eq!(transition(Some(true), false), Some("pass→fail"));
+        assert_eq!(transition(Some(false), true), Some("fail→pass"));
+        assert_eq!(transition(Some(false), false), None);
+
+        let stdout = (1..=5).map(|i| format!("line {}", i)).collect::<Vec<_>>();
+        let tail = output_tail(&stdout.join("\n"), "error: boom", 3);
+        assert_eq!(tail, "... 3 lines omitted\nline 4\nline 5\nerror: boom");
+    }
+}
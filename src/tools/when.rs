@@ -0,0 +1,610 @@
+// modern-cli-mcp/src/tools/when.rs
+//! Turning time expressions into concrete timestamps.
+//!
+//! Understands absolute times (RFC 3339, `2026-10-20 15:00`, `@<unix>`),
+//! everyday phrases (`next tuesday 3pm PST`, `tomorrow noon`, `oct 20 9:30`,
+//! `in 90 minutes`) and cron expressions, and renders the result in any set
+//! of timezones. Zone abbreviations stand for the region that uses them, so
+//! `3pm PST` in July is 3pm Pacific time; a note says when the abbreviation
+//! given differs from the one in effect.
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::sync::LazyLock;
+
+/// A timezone as given by the caller
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    Named(Tz),
+    Fixed(FixedOffset),
+    Local,
+}
+
+const ABBREVIATIONS: &[(&[&str], Tz)] = &[
+    (&["PST", "PDT", "PT"], Tz::America__Los_Angeles),
+    (&["MST", "MDT", "MT"], Tz::America__Denver),
+    (&["CST", "CDT", "CT"], Tz::America__Chicago),
+    (&["EST", "EDT", "ET"], Tz::America__New_York),
+    (&["AKST", "AKDT"], Tz::America__Anchorage),
+    (&["HST"], Tz::Pacific__Honolulu),
+    (&["BST"], Tz::Europe__London),
+    (&["WET", "WEST"], Tz::Europe__Lisbon),
+    (&["CET", "CEST"], Tz::Europe__Berlin),
+    (&["EET", "EEST"], Tz::Europe__Athens),
+    (&["IST"], Tz::Asia__Kolkata),
+    (&["SGT"], Tz::Asia__Singapore),
+    (&["JST"], Tz::Asia__Tokyo),
+    (&["KST"], Tz::Asia__Seoul),
+    (&["AEST", "AEDT"], Tz::Australia__Sydney),
+    (&["NZST", "NZDT"], Tz::Pacific__Auckland),
+];
+
+static OFFSET: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?i:UTC|GMT)?([+-])(\d{1,2})(?::?(\d{2}))?$").unwrap());
+
+impl Zone {
+    /// IANA name, abbreviation, `UTC`, an offset (`+05:30`, `UTC-8`), or `local`
+    pub fn parse(s: &str) -> Option<Zone> {
+        let upper = s.to_ascii_uppercase();
+        match upper.as_str() {
+            "LOCAL" => return Some(Zone::local()),
+            "UTC" | "GMT" | "Z" => return Some(Zone::Named(Tz::UTC)),
+            _ => {}
+        }
+        if let Some((_, tz)) = ABBREVIATIONS
+            .iter()
+            .find(|(names, _)| names.contains(&upper.as_str()))
+        {
+            return Some(Zone::Named(*tz));
+        }
+        if let Some(caps) = OFFSET.captures(s) {
+            let hours: i32 = caps[2].parse().ok()?;
+            let minutes: i32 = caps.get(3).map_or(Some(0), |m| m.as_str().parse().ok())?;
+            let secs = (hours * 3600 + minutes * 60) * if &caps[1] == "-" { -1 } else { 1 };
+            return FixedOffset::east_opt(secs).map(Zone::Fixed);
+        }
+        s.parse::<Tz>().ok().map(Zone::Named)
+    }
+
+    /// The system zone, by IANA name where `TZ` or /etc/localtime tell it
+    pub fn local() -> Zone {
+        let from_env = std::env::var("TZ")
+            .ok()
+            .and_then(|tz| tz.parse::<Tz>().ok());
+        let from_link = || {
+            let target = std::fs::read_link("/etc/localtime").ok()?;
+            let target = target.to_string_lossy();
+            target.split_once("zoneinfo/")?.1.parse::<Tz>().ok()
+        };
+        from_env
+            .or_else(from_link)
+            .map(Zone::Named)
+            .unwrap_or(Zone::Local)
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Zone::Named(tz) => tz.name().to_string(),
+            Zone::Fixed(offset) => offset.to_string(),
+            Zone::Local => "local".to_string(),
+        }
+    }
+
+    /// Wall-clock time in this zone
+    fn naive(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Named(tz) => instant.with_timezone(tz).naive_local(),
+            Zone::Fixed(offset) => instant.with_timezone(offset).naive_local(),
+            Zone::Local => instant.with_timezone(&Local).naive_local(),
+        }
+    }
+
+    /// The instant a wall-clock time denotes; the earlier one when clocks
+    /// fall back, None when they spring forward over it
+    fn instant(&self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            Zone::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc)),
+            Zone::Fixed(offset) => offset
+                .from_local_datetime(&naive)
+                .single()
+                .map(|t| t.with_timezone(&Utc)),
+            Zone::Local => Local
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc)),
+        }
+    }
+
+    /// RFC 3339 and a readable form such as `Tue 2026-10-20 15:00 PDT`
+    fn render(&self, instant: DateTime<Utc>) -> (String, String) {
+        const DISPLAY: &str = "%a %Y-%m-%d %H:%M %Z";
+        match self {
+            Zone::Named(tz) => {
+                let t = instant.with_timezone(tz);
+                (t.to_rfc3339(), t.format(DISPLAY).to_string())
+            }
+            Zone::Fixed(offset) => {
+                let t = instant.with_timezone(offset);
+                (
+                    t.to_rfc3339(),
+                    t.format("%a %Y-%m-%d %H:%M %:z").to_string(),
+                )
+            }
+            Zone::Local => {
+                let t = instant.with_timezone(&Local);
+                (
+                    t.to_rfc3339(),
+                    t.format("%a %Y-%m-%d %H:%M %:z").to_string(),
+                )
+            }
+        }
+    }
+
+    /// Abbreviation in effect at `instant` (named zones only)
+    fn abbreviation(&self, instant: DateTime<Utc>) -> Option<String> {
+        match self {
+            Zone::Named(tz) => Some(instant.with_timezone(tz).format("%Z").to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// A resolved expression
+#[derive(Debug)]
+pub struct Resolved {
+    pub instant: DateTime<Utc>,
+    pub zone: Zone,
+    pub notes: Vec<String>,
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    let w = match word {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "weds" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(w)
+}
+
+fn month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    if word.len() < 3 {
+        return None;
+    }
+    let m = MONTHS.iter().position(|m| word.starts_with(m))? as u32 + 1;
+    // "mar" and "march" but not "marker"
+    let full = NaiveDate::from_ymd_opt(2000, m, 1)?
+        .format("%B")
+        .to_string();
+    (full.to_lowercase().starts_with(word) || word == "sept").then_some(m)
+}
+
+static TIME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d{1,2})(?::(\d{2}))?(?::(\d{2}))?\s*(am|pm|a\.m\.|p\.m\.)?$").unwrap()
+});
+
+/// `3pm`, `3:30 pm`, `15:00`, `noon`, `midnight`
+fn time_of_day(token: &str, meridiem: Option<&str>) -> Option<NaiveTime> {
+    match token {
+        "noon" | "midday" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+    let caps = TIME.captures(token)?;
+    let mut hour: u32 = caps[1].parse().ok()?;
+    let minute: u32 = caps.get(2).map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let second: u32 = caps.get(3).map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let meridiem = caps.get(4).map(|m| m.as_str()).or(meridiem);
+    match meridiem.map(|m| m.starts_with('p')) {
+        Some(pm) if (1..=12).contains(&hour) => hour = hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        // A bare number is only a time with minutes ("15:00"), not "3"
+        None if caps.get(2).is_none() => return None,
+        None => {}
+    }
+    NaiveTime::from_hms_opt(hour, minute, second)
+}
+
+fn unit(word: &str) -> Option<Duration> {
+    let word = word.trim_end_matches('s');
+    let d = match word {
+        "sec" | "second" => Duration::seconds(1),
+        "min" | "minute" => Duration::minutes(1),
+        "h" | "hr" | "hour" => Duration::hours(1),
+        "day" => Duration::days(1),
+        "week" | "wk" => Duration::weeks(1),
+        _ => return None,
+    };
+    Some(d)
+}
+
+/// `count` units as an offset, or an error when it is out of range
+fn scaled(count: &str, unit: Duration) -> Result<Duration, String> {
+    count
+        .parse::<i32>()
+        .ok()
+        .and_then(|n| unit.checked_mul(n))
+        .ok_or_else(|| format!("{} is out of range", count))
+}
+
+/// Resolve a time expression relative to `now`. Times without a zone are
+/// read in `default`.
+pub fn resolve(expr: &str, now: DateTime<Utc>, default: Zone) -> Result<Resolved, String> {
+    let expr = expr.trim();
+    if let Some(secs) = expr.strip_prefix('@').and_then(|s| s.parse::<i64>().ok()) {
+        let instant = DateTime::from_timestamp(secs, 0).ok_or("timestamp out of range")?;
+        return Ok(Resolved {
+            instant,
+            zone: default,
+            notes: vec![],
+        });
+    }
+    if let Ok(t) = DateTime::parse_from_rfc3339(expr) {
+        return Ok(Resolved {
+            instant: t.with_timezone(&Utc),
+            zone: Zone::Fixed(*t.offset()),
+            notes: vec![],
+        });
+    }
+
+    let mut tokens: Vec<&str> = expr
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .collect();
+    let mut notes = vec![];
+    let mut zone = default;
+    let mut zone_word = None;
+    if let Some(z) = tokens
+        .last()
+        .filter(|t| time_of_day(t, None).is_none())
+        .and_then(|t| Zone::parse(t))
+    {
+        zone_word = tokens.pop().map(|t| t.to_ascii_uppercase());
+        zone = z;
+    }
+    let words: Vec<String> = tokens.iter().map(|t| t.to_lowercase()).collect();
+
+    let today = zone.naive(now).date();
+    let mut date: Option<NaiveDate> = None;
+    let mut time: Option<NaiveTime> = None;
+    let mut offset = Duration::zero();
+    let mut relative = false;
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i].as_str();
+        let next = words.get(i + 1).map(|s| s.as_str());
+        match word {
+            "at" | "on" | "the" | "of" | "this" => {}
+            "now" => relative = true,
+            "today" | "tonight" => date = Some(today),
+            "tomorrow" => date = Some(today + Duration::days(1)),
+            "yesterday" => date = Some(today - Duration::days(1)),
+            "in" => {
+                let is_number = next.is_some_and(|n| n.parse::<i64>().is_ok());
+                let (Some(n), Some(d)) = (
+                    next.filter(|_| is_number),
+                    words.get(i + 2).and_then(|u| unit(u)),
+                ) else {
+                    return Err(format!("Expected 'in <number> <unit>' in '{}'", expr));
+                };
+                offset = offset
+                    .checked_add(&scaled(n, d)?)
+                    .ok_or("offset out of range")?;
+                relative = true;
+                i += 2;
+            }
+            "next" | "last" => {
+                let Some(day) = next.and_then(weekday) else {
+                    return Err(format!("Expected a weekday after '{}'", word));
+                };
+                let ahead = (day.num_days_from_monday() as i64
+                    - today.weekday().num_days_from_monday() as i64)
+                    .rem_euclid(7);
+                date = Some(if word == "next" {
+                    notes.push(format!(
+                        "'next {}' read as the first {} after today",
+                        next.unwrap_or(""),
+                        day
+                    ));
+                    today + Duration::days(if ahead == 0 { 7 } else { ahead })
+                } else {
+                    today - Duration::days(if ahead == 0 { 7 } else { 7 - ahead })
+                });
+                i += 1;
+            }
+            w if weekday(w).is_some() => {
+                let day = weekday(w).unwrap();
+                let ahead = (day.num_days_from_monday() as i64
+                    - today.weekday().num_days_from_monday() as i64)
+                    .rem_euclid(7);
+                date = Some(today + Duration::days(ahead));
+            }
+            w if w.parse::<i64>().is_ok() && next.and_then(unit).is_some() => {
+                // "3 days ago" / "2 hours from now"
+                let d = scaled(w, unit(next.unwrap()).unwrap())?;
+                let moved = match words.get(i + 2).map(|s| s.as_str()) {
+                    Some("ago") => {
+                        i += 1;
+                        offset.checked_sub(&d)
+                    }
+                    Some("from") if words.get(i + 3).map(|s| s.as_str()) == Some("now") => {
+                        i += 2;
+                        offset.checked_add(&d)
+                    }
+                    _ => offset.checked_add(&d),
+                };
+                offset = moved.ok_or("offset out of range")?;
+                relative = true;
+                i += 1;
+            }
+            w if month(w).is_some() => {
+                let m = month(w).unwrap();
+                let Some(day) = next
+                    .map(|d| d.trim_end_matches(|c: char| c.is_alphabetic()))
+                    .and_then(|d| d.parse::<u32>().ok())
+                else {
+                    return Err(format!("Expected a day after '{}'", w));
+                };
+                i += 1;
+                let year = match words.get(i + 1).and_then(|y| y.parse::<i32>().ok()) {
+                    Some(y) if y > 1900 => {
+                        i += 1;
+                        Some(y)
+                    }
+                    _ => None,
+                };
+                let mut d = NaiveDate::from_ymd_opt(year.unwrap_or(today.year()), m, day)
+                    .ok_or_else(|| format!("No such date: {} {}", w, day))?;
+                if year.is_none() && d < today {
+                    d = d.with_year(today.year() + 1).unwrap_or(d);
+                }
+                date = Some(d);
+            }
+            w if NaiveDate::parse_from_str(w, "%Y-%m-%d").is_ok() => {
+                date = NaiveDate::parse_from_str(w, "%Y-%m-%d").ok();
+            }
+            w if w.contains('t') && NaiveDateTime::parse_from_str(w, "%Y-%m-%dt%H:%M").is_ok() => {
+                let dt = NaiveDateTime::parse_from_str(w, "%Y-%m-%dt%H:%M").unwrap();
+                date = Some(dt.date());
+                time = Some(dt.time());
+            }
+            w => {
+                let meridiem = next.filter(|n| matches!(*n, "am" | "pm" | "a.m." | "p.m."));
+                match time_of_day(w, meridiem) {
+                    Some(t) => {
+                        time = Some(t);
+                        if meridiem.is_some() {
+                            i += 1;
+                        }
+                    }
+                    None => return Err(format!("Don't understand '{}' in '{}'", w, expr)),
+                }
+            }
+        }
+        i += 1;
+    }
+
+    if date.is_none() && time.is_none() && !relative {
+        return Err(format!("No date or time found in '{}'", expr));
+    }
+
+    let mut local = zone.naive(now);
+    if let Some(d) = date {
+        local = d.and_time(NaiveTime::MIN);
+    }
+    local = local
+        .checked_add_signed(offset)
+        .ok_or_else(|| format!("'{}' is out of range", expr))?;
+    if let Some(t) = time {
+        local = local.date().and_time(t);
+    } else if date.is_some() && !relative {
+        notes.push("no time given; using midnight".to_string());
+    }
+
+    let mut instant = zone.instant(local).ok_or_else(|| {
+        format!(
+            "{} does not exist in {} (clocks spring forward)",
+            local,
+            zone.name()
+        )
+    })?;
+    if time.is_some() && date.is_none() && !relative && instant < now {
+        instant = zone
+            .instant(local + Duration::days(1))
+            .ok_or_else(|| format!("{} does not exist in {}", local, zone.name()))?;
+        notes.push("that time has passed today; using tomorrow".to_string());
+    }
+
+    if let (Some(word), Some(actual)) = (zone_word, zone.abbreviation(instant)) {
+        let is_abbreviation = ABBREVIATIONS
+            .iter()
+            .any(|(names, _)| names.contains(&word.as_str()));
+        if is_abbreviation && word.len() > 2 && word != actual {
+            notes.push(format!(
+                "{} read as {}, which observes {} on that date",
+                word,
+                zone.name(),
+                actual
+            ));
+        }
+    }
+
+    Ok(Resolved {
+        instant,
+        zone,
+        notes,
+    })
+}
+
+/// Next `count` cron occurrences after `now`, evaluated in `zone`
+pub fn cron_occurrences(
+    expr: &str,
+    now: DateTime<Utc>,
+    zone: Zone,
+    count: usize,
+) -> Result<Vec<DateTime<Utc>>, String> {
+    fn next_n<Z: TimeZone>(
+        cron: &croner::Cron,
+        mut at: DateTime<Z>,
+        count: usize,
+    ) -> Result<Vec<DateTime<Utc>>, String> {
+        let mut times = Vec::with_capacity(count);
+        for _ in 0..count {
+            at = cron
+                .find_next_occurrence(&at, false)
+                .map_err(|e| format!("No upcoming occurrence: {}", e))?;
+            times.push(at.with_timezone(&Utc));
+        }
+        Ok(times)
+    }
+    let cron = crate::scheduler::parse_cron(expr)?;
+    match zone {
+        Zone::Named(tz) => next_n(&cron, now.with_timezone(&tz), count),
+        Zone::Fixed(offset) => next_n(&cron, now.with_timezone(&offset), count),
+        Zone::Local => next_n(&cron, now.with_timezone(&Local), count),
+    }
+}
+
+/// Whether `expr` is meant as cron rather than a phrase
+pub fn is_cron(expr: &str) -> bool {
+    let expr = expr.trim();
+    let fields = expr.split_whitespace().count();
+    let starts_like_cron = expr
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || c == '*');
+    (expr.starts_with('@') && expr[1..].parse::<i64>().is_err())
+        || ((5..=6).contains(&fields) && starts_like_cron && !expr.contains(':'))
+}
+
+/// `in 3d 4h`, `2h 5m ago`
+pub fn humanize(delta: Duration) -> String {
+    let secs = delta.num_seconds().abs();
+    if secs < 60 {
+        return "now".to_string();
+    }
+    let parts: Vec<String> = [(86400, "d"), (3600, "h"), (60, "m")]
+        .iter()
+        .scan(secs, |rest, (size, unit)| {
+            let n = *rest / size;
+            *rest %= size;
+            Some((n, unit))
+        })
+        .filter(|(n, _)| *n > 0)
+        .take(2)
+        .map(|(n, unit)| format!("{}{}", n, unit))
+        .collect();
+    if delta.num_seconds() > 0 {
+        format!("in {}", parts.join(" "))
+    } else {
+        format!("{} ago", parts.join(" "))
+    }
+}
+
+/// An instant in each zone
+pub fn render(instant: DateTime<Utc>, now: DateTime<Utc>, zones: &[Zone]) -> Value {
+    let times: Vec<Value> = zones
+        .iter()
+        .map(|z| {
+            let (time, display) = z.render(instant);
+            json!({ "timezone": z.name(), "time": time, "display": display })
+        })
+        .collect();
+    json!({
+        "utc": instant.to_rfc3339(),
+        "unix": instant.timestamp(),
+        "relative": humanize(instant - now),
+        "times": times,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_resolve_phrases() {
+        // Saturday 2026-10-17 12:00 UTC
+        let now = at("2026-10-17T12:00:00Z");
+        let utc = Zone::Named(Tz::UTC);
+
+        let r = resolve("next Tuesday 3pm PST", now, utc).unwrap();
+        // Pacific time is still on PDT (UTC-7) on 2026-10-20
+        assert_eq!(r.instant, at("2026-10-20T22:00:00Z"));
+        assert!(r.notes.iter().any(|n| n.contains("observes PDT")));
+
+        let r = resolve("tomorrow at noon Europe/Berlin", now, utc).unwrap();
+        assert_eq!(r.instant, at("2026-10-18T10:00:00Z"));
+        assert_eq!(
+            resolve("in 90 minutes", now, utc).unwrap().instant,
+            at("2026-10-17T13:30:00Z")
+        );
+        assert_eq!(
+            resolve("3 days ago", now, utc).unwrap().instant,
+            at("2026-10-14T12:00:00Z")
+        );
+        // Out-of-range offsets are errors, not panics
+        assert!(resolve("in 100000000 days", now, utc).is_err());
+        assert!(resolve("99999999999 weeks ago", now, utc).is_err());
+        assert!(resolve("in 2147483647 weeks", now, utc).is_err());
+        assert_eq!(
+            resolve("oct 20 9:30 am UTC+2", now, utc).unwrap().instant,
+            at("2026-10-20T07:30:00Z")
+        );
+        assert_eq!(
+            resolve("jan 5", now, utc).unwrap().instant,
+            at("2027-01-05T00:00:00Z")
+        );
+        // 9am has passed, so the next one
+        let r = resolve("9:00", now, utc).unwrap();
+        assert_eq!(r.instant, at("2026-10-18T09:00:00Z"));
+        assert_eq!(
+            resolve("2026-10-20T15:00:00+09:00", now, utc)
+                .unwrap()
+                .instant,
+            at("2026-10-20T06:00:00Z")
+        );
+        assert!(resolve("whenever", now, utc).is_err());
+        assert!(resolve("3", now, utc).is_err());
+    }
+
+    #[test]
+    fn test_cron_occurrences() {
+        let now = at("2026-10-17T12:00:00Z");
+        assert!(is_cron("0 9 * * 1-5"));
+        assert!(is_cron("@daily"));
+        assert!(!is_cron("in 3 days at 3pm"));
+        assert!(!is_cron("@1760000000"));
+
+        let ny = Zone::parse("America/New_York").unwrap();
+        let times = cron_occurrences("0 9 * * 1-5", now, ny, 2).unwrap();
+        // Monday 09:00 EDT
+        assert_eq!(
+            times,
+            [at("2026-10-19T13:00:00Z"), at("2026-10-20T13:00:00Z")]
+        );
+        assert_eq!(humanize(times[0] - now), "in 2d 1h");
+        assert_eq!(Zone::parse("utc-08:00").unwrap().name(), "-08:00");
+        assert_eq!(Zone::parse("est"), Some(Zone::Named(Tz::America__New_York)));
+    }
+}
@@ -0,0 +1,160 @@
+// modern-cli-mcp/src/tools/workspace.rs
+//! Workspace summary helpers.
+//!
+//! A workspace summary gives an agent its bearings in an unfamiliar
+//! checkout: which ecosystems it uses (from marker files such as Cargo.toml
+//! or flake.nix), which task runner targets exist, and how much code there is
+//! per language according to tokei.
+
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Detect project ecosystems from marker files in `root`
+pub fn detect_project_types(root: &Path) -> Vec<&'static str> {
+    const MARKERS: &[(&str, &str)] = &[
+        ("Cargo.toml", "rust"),
+        ("package.json", "node"),
+        ("pyproject.toml", "python"),
+        ("setup.py", "python"),
+        ("requirements.txt", "python"),
+        ("go.mod", "go"),
+        ("flake.nix", "nix"),
+        ("default.nix", "nix"),
+        ("pom.xml", "java"),
+        ("build.gradle", "java"),
+        ("build.gradle.kts", "kotlin"),
+        ("Gemfile", "ruby"),
+        ("composer.json", "php"),
+        ("mix.exs", "elixir"),
+        ("CMakeLists.txt", "cmake"),
+        ("deno.json", "deno"),
+        ("Dockerfile", "container"),
+        ("Containerfile", "container"),
+        ("docker-compose.yml", "compose"),
+        ("compose.yaml", "compose"),
+        ("Chart.yaml", "helm"),
+        ("kustomization.yaml", "kustomize"),
+    ];
+    // Several markers map to one type, not always adjacently (nix, container)
+    let mut types: Vec<&str> = vec![];
+    for (file, kind) in MARKERS {
+        if root.join(file).exists() && !types.contains(kind) {
+            types.push(kind);
+        }
+    }
+    types
+}
+
+/// Explicit (non-pattern, non-special) targets from a Makefile
+pub fn parse_makefile_targets(text: &str) -> Vec<String> {
+    let mut targets: Vec<String> = vec![];
+    for line in text.lines() {
+        if line.starts_with(['\t', ' ', '#', '.']) {
+            continue;
+        }
+        let Some((head, rest)) = line.split_once(':') else {
+            continue;
+        };
+        // Skip variable assignments (`X := y`, `X ::= y`)
+        if rest.starts_with('=') || rest.starts_with(":=") || head.contains('=') {
+            continue;
+        }
+        for target in head.split_whitespace() {
+            if !target.contains(['%', '$']) && !targets.iter().any(|t| t == target) {
+                targets.push(target.to_string());
+            }
+        }
+    }
+    targets
+}
+
+/// Reduce `tokei --output json` to totals and the top languages by code lines
+pub fn summarize_tokei(stats: &Value) -> Value {
+    let mut languages: Vec<(&String, &Value)> = stats
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| name.as_str() != "Total")
+        .collect();
+    languages.sort_by_key(|(_, v)| std::cmp::Reverse(v["code"].as_u64().unwrap_or(0)));
+
+    let total = &stats["Total"];
+    json!({
+        "code": total["code"],
+        "comments": total["comments"],
+        "files": total["reports"].as_array().map(|r| r.len()),
+        "languages": languages
+            .iter()
+            .take(10)
+            .map(|(name, v)| json!({
+                "language": name,
+                "code": v["code"],
+                "files": v["reports"].as_array().map(|r| r.len()),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_project_types() {
+        let tmp = tempfile::tempdir().unwrap();
+        for marker in [
+            "Cargo.toml",
+            "flake.nix",
+            "default.nix",
+            "Dockerfile",
+            "Containerfile",
+            "pyproject.toml",
+            "requirements.txt",
+        ] {
+            std::fs::write(tmp.path().join(marker), "").unwrap();
+        }
+        assert_eq!(
+            detect_project_types(tmp.path()),
+            ["rust", "python", "nix", "container"]
+        );
+        let empty = tempfile::tempdir().unwrap();
+        assert!(detect_project_types(empty.path()).is_empty());
+    }
+
+    #[test]
+    fn test_parse_makefile_targets() {
+        let makefile = "CC := gcc\nPREFIX ?= /usr\nX ::= y\n.PHONY: all test\n\
+            all: build test\n\t$(CC) -o app\nbuild test: deps\n%.o: %.c\n\
+            $(OUT): all\n# lint: comment\ninstall:\n\tcp app $(PREFIX)\nall: extra\n";
+        assert_eq!(
+            parse_makefile_targets(makefile),
+            ["all", "build", "test", "install"]
+        );
+    }
+
+    #[test]
+    fn test_summarize_tokei() {
+        let stats = json!({
+            "Rust": {"code": 900, "reports": [{}, {}, {}]},
+            "Markdown": {"code": 40, "reports": [{}]},
+            "Nix": {"code": 120, "reports": [{}]},
+            "Total": {"code": 1060, "comments": 80, "reports": [{}, {}, {}, {}, {}]}
+        });
+        let summary = summarize_tokei(&stats);
+        assert_eq!(summary["code"], 1060);
+        assert_eq!(summary["comments"], 80);
+        assert_eq!(summary["files"], 5);
+        let names: Vec<&str> = summary["languages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|l| l["language"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["Rust", "Nix", "Markdown"]);
+        assert_eq!(summary["languages"][0]["files"], 3);
+        assert!(summarize_tokei(&json!({}))["languages"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+// modern-cli-mcp/src/trust.rs
+//! Workspace trust.
+//!
+//! Mutating tools (file writes, moves, git commits, archive extraction) and
+//! tools that run commands (shell, bats, act, benchmarks, watchers, container
+//! builds and exec) only act inside trusted directories, the way editors hold
+//! back tasks and extensions in a freshly cloned repository until it has been
+//! reviewed. Reading and searching work everywhere.
+//!
+//! Trusted directories come from `--trust` and from
+//! ~/.config/modern-cli-mcp/trusted.json (a JSON array of paths). The
+//! trust_workspace tool only adds to that file when the user accepts a prompt
+//! shown by the client (MCP elicitation); the model cannot grant trust by
+//! itself. Trusting a directory trusts everything below it.
+
+use parking_lot::RwLock;
+use std::path::{Path, PathBuf};
+
+use crate::ignore::canonical;
+
+#[derive(Debug, Default)]
+pub struct Trust {
+    /// Off: every directory is treated as trusted
+    enforce: bool,
+    /// Canonical trusted directories from the command line
+    fixed: Vec<PathBuf>,
+    /// Canonical directories from the trust file
+    granted: RwLock<Vec<PathBuf>>,
+    /// Where granted directories are persisted
+    file: Option<PathBuf>,
+}
+
+/// The repository root containing `path`, or the directory itself
+pub fn workspace_root(path: &Path) -> PathBuf {
+    let path = canonical(path);
+    let dir = match path.is_dir() {
+        true => path.clone(),
+        false => path.parent().map(Path::to_path_buf).unwrap_or(path.clone()),
+    };
+    dir.ancestors()
+        .find(|d| d.join(".git").exists())
+        .map(Path::to_path_buf)
+        .unwrap_or(dir)
+}
+
+/// Whether `command` of a container or cluster tool builds or runs code that
+/// the workspace defines (compose files, Dockerfiles, bake files) or that the
+/// caller passes in (exec, run)
+pub fn runs_code(tool: &str, command: &str) -> bool {
+    match tool {
+        "compose" => matches!(
+            command,
+            "up" | "wait" | "build" | "down" | "run" | "exec" | "create" | "start" | "restart"
+        ),
+        "buildx" => matches!(command, "build" | "bake"),
+        "podman" => matches!(
+            command,
+            "run" | "build" | "exec" | "create" | "start" | "restart"
+        ),
+        "buildah" => matches!(command, "run" | "build"),
+        "container" | "kubectl" => command == "exec",
+        _ => false,
+    }
+}
+
+impl Trust {
+    /// Enforcement as configured, with the default trust file
+    pub fn load(enforce: bool, fixed: &[PathBuf]) -> Result<Self, String> {
+        let file = dirs::config_dir().map(|d| d.join("modern-cli-mcp").join("trusted.json"));
+        Self::with_file(enforce, fixed, file)
+    }
+
+    pub(crate) fn with_file(
+        enforce: bool,
+        fixed: &[PathBuf],
+        file: Option<PathBuf>,
+    ) -> Result<Self, String> {
+        let fixed = fixed
+            .iter()
+            .map(|dir| {
+                dir.canonicalize()
+                    .map_err(|e| format!("Trusted directory {}: {}", dir.display(), e))
+            })
+            .collect::<Result<_, _>>()?;
+        let granted = match file.as_deref().filter(|f| f.exists()) {
+            Some(f) => {
+                let text = std::fs::read_to_string(f)
+                    .map_err(|e| format!("Failed to read {}: {}", f.display(), e))?;
+                // Entries may have been edited by hand: symlinks and `..`
+                // would otherwise never match a canonical path
+                serde_json::from_str::<Vec<PathBuf>>(&text)
+                    .map_err(|e| format!("Invalid {}: {}", f.display(), e))?
+                    .iter()
+                    .map(|dir| canonical(dir))
+                    .collect()
+            }
+            None => vec![],
+        };
+        Ok(Self {
+            enforce,
+            fixed,
+            granted: RwLock::new(granted),
+            file,
+        })
+    }
+
+    pub fn enforced(&self) -> bool {
+        self.enforce
+    }
+
+    /// Trusted directories, command line first
+    pub fn roots(&self) -> Vec<PathBuf> {
+        let mut roots = self.fixed.clone();
+        roots.extend(self.granted.read().iter().cloned());
+        roots
+    }
+
+    pub fn is_trusted(&self, path: &Path) -> bool {
+        let real = canonical(path);
+        !self.enforce || self.roots().iter().any(|root| real.starts_with(root))
+    }
+
+    /// Error naming the workspace to trust when `path` is not trusted
+    pub fn check(&self, path: &Path) -> Result<(), String> {
+        if self.is_trusted(path) {
+            return Ok(());
+        }
+        let root = workspace_root(path);
+        Err(format!(
+            "Workspace is not trusted: {}. Review it with mcp trust_workspace \
+             path=\"{}\"; confirm=true then asks the user to approve it, or they can \
+             start the server with --trust {}",
+            root.display(),
+            root.display(),
+            root.display()
+        ))
+    }
+
+    /// Trust `dir` and persist it; returns the canonical directory
+    pub fn grant(&self, dir: &Path) -> Result<PathBuf, String> {
+        let dir = dir
+            .canonicalize()
+            .map_err(|e| format!("{}: {}", dir.display(), e))?;
+        let mut granted = self.granted.write();
+        if !granted.contains(&dir) {
+            granted.push(dir.clone());
+            self.save(&granted)?;
+        }
+        Ok(dir)
+    }
+
+    /// Stop trusting `dir`; false if it was not granted through the trust file
+    pub fn revoke(&self, dir: &Path) -> Result<bool, String> {
+        let dir = canonical(dir);
+        let mut granted = self.granted.write();
+        let before = granted.len();
+        granted.retain(|d| *d != dir);
+        if granted.len() == before {
+            return Ok(false);
+        }
+        self.save(&granted)?;
+        Ok(true)
+    }
+
+    fn save(&self, granted: &[PathBuf]) -> Result<(), String> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let text = serde_json::to_string_pretty(granted).map_err(|e| e.to_string())?;
+        std::fs::write(file, text).map_err(|e| format!("Failed to write {}: {}", file.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trust_covers_subdirectories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        let file = tmp.path().join("trusted.json");
+
+        let trust = Trust::with_file(true, &[], Some(file.clone())).unwrap();
+        let err = trust.check(&repo.join("src/new.rs")).unwrap_err();
+        assert!(err.contains(&repo.canonicalize().unwrap().display().to_string()));
+
+        trust.grant(&repo).unwrap();
+        assert!(trust.check(&repo.join("src/new.rs")).is_ok());
+        assert!(!trust.is_trusted(tmp.path()));
+
+        // Persisted for the next server
+        let reloaded = Trust::with_file(true, &[], Some(file.clone())).unwrap();
+        assert!(reloaded.is_trusted(&repo.join("src")));
+        assert!(reloaded.revoke(&repo).unwrap());
+        assert!(!Trust::with_file(true, &[], Some(file))
+            .unwrap()
+            .is_trusted(&repo));
+    }
+
+    #[test]
+    fn test_trust_off_and_fixed() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(Trust::with_file(false, &[], None)
+            .unwrap()
+            .is_trusted(tmp.path()));
+
+        let trust = Trust::with_file(true, &[tmp.path().to_path_buf()], None).unwrap();
+        assert!(trust.is_trusted(&tmp.path().join("a/../b")));
+        assert!(!trust.revoke(tmp.path()).unwrap());
+        assert!(Trust::with_file(true, &[tmp.path().join("missing")], None).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_trust_file_entries_are_canonicalized() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::os::unix::fs::symlink(&repo, tmp.path().join("link")).unwrap();
+        let file = tmp.path().join("trusted.json");
+        let entries = [tmp.path().join("link"), repo.join("src/../../other")];
+        std::fs::write(&file, serde_json::to_string(&entries).unwrap()).unwrap();
+
+        let trust = Trust::with_file(true, &[], Some(file)).unwrap();
+        assert!(trust.is_trusted(&repo.join("src")));
+        assert!(trust.is_trusted(&tmp.path().join("other/file")));
+        assert!(!trust.is_trusted(&tmp.path().join("elsewhere")));
+        assert_eq!(
+            trust.roots(),
+            [
+                repo.canonicalize().unwrap(),
+                tmp.path().canonicalize().unwrap().join("other")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_untrusted_workspace_cannot_build_or_run() {
+        let tmp = tempfile::tempdir().unwrap();
+        let trust = Trust::with_file(true, &[], None).unwrap();
+        for (tool, command) in [
+            ("compose", "up"),
+            ("compose", "build"),
+            ("buildx", "build"),
+            ("buildx", "bake"),
+            ("podman", "run"),
+            ("kubectl", "exec"),
+            ("container", "exec"),
+        ] {
+            assert!(runs_code(tool, command), "{tool} {command}");
+        }
+        for (tool, command) in [("compose", "ps"), ("compose", "config"), ("buildx", "ls")] {
+            assert!(!runs_code(tool, command), "{tool} {command}");
+        }
+        let err = trust.check(tmp.path()).unwrap_err();
+        assert!(err.contains("Workspace is not trusted"));
+
+        let trust = Trust::with_file(true, &[tmp.path().to_path_buf()], None).unwrap();
+        assert!(trust.check(tmp.path()).is_ok());
+    }
+}
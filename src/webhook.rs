@@ -0,0 +1,379 @@
+// modern-cli-mcp/src/webhook.rs
+//! Forge webhook listener.
+//!
+//! A minimal HTTP/1.1 endpoint that accepts GitHub and GitLab webhook
+//! deliveries (push, pull/merge request, issue, comment, ...), verifies them
+//! against a shared secret, and records a normalized summary in the state
+//! database where the `events_poll` tool picks them up.
+
+use crate::state::StateManager;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest accepted header block
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+/// Largest accepted payload (GitHub caps deliveries at 25 MB; pushes of
+/// that size are not useful to an agent)
+const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// Time a client gets to send a complete request before it is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Bind the listener and serve deliveries until the process exits
+pub async fn serve(
+    addr: SocketAddr,
+    secret: Option<String>,
+    state: StateManager,
+) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!("Webhook listener on http://{}", listener.local_addr()?);
+    if secret.is_none() {
+        tracing::warn!("Webhook listener has no secret; deliveries are not authenticated");
+    }
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::warn!("Webhook accept failed: {}", e);
+                continue;
+            }
+        };
+        let secret = secret.clone();
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, secret.as_deref(), &state, READ_TIMEOUT).await {
+                tracing::debug!("Webhook connection from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+/// Parsed request: lowercased header names, raw body
+struct Request {
+    method: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    secret: Option<&str>,
+    state: &StateManager,
+    read_timeout: Duration,
+) -> std::io::Result<()> {
+    // A client that never finishes its request would hold the task and the
+    // socket forever; dropping the stream closes the connection
+    let read = tokio::time::timeout(read_timeout, read_request(&mut stream))
+        .await
+        .map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("no complete request within {:?}", read_timeout),
+            )
+        })?;
+    let reply = match read? {
+        Err(status) => status,
+        Ok(req) if req.method != "POST" => (405, "method not allowed".into()),
+        Ok(req) => accept(&req, secret, state),
+    };
+
+    let (code, message) = reply;
+    let reason = match code {
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    };
+    let body = json!({ "status": code, "message": message }).to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        code,
+        reason,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read one request, or the error status to reply with
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Result<Request, (u16, String)>> {
+    let mut buf = Vec::with_capacity(8192);
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_HEADER_BYTES {
+            return Ok(Err((413, "headers too large".into())));
+        }
+        let mut chunk = [0u8; 4096];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(Err((400, "incomplete request".into())));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let method = lines
+        .next()
+        .and_then(|l| l.split_whitespace().next())
+        .unwrap_or_default()
+        .to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let length: usize = headers
+        .iter()
+        .find(|(k, _)| k == "content-length")
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        return Ok(Err((413, "payload too large".into())));
+    }
+
+    let mut body = buf[header_end + 4..].to_vec();
+    while body.len() < length {
+        let mut chunk = vec![0u8; (length - body.len()).min(64 * 1024)];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(Err((400, "truncated body".into())));
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(length);
+
+    Ok(Ok(Request {
+        method,
+        headers,
+        body,
+    }))
+}
+
+/// Authenticate, normalize and store a delivery
+fn accept(req: &Request, secret: Option<&str>, state: &StateManager) -> (u16, String) {
+    let (source, raw_event) = if let Some(event) = req.header("x-github-event") {
+        ("github", event)
+    } else if let Some(event) = req.header("x-gitlab-event") {
+        ("gitlab", event)
+    } else {
+        return (400, "not a GitHub or GitLab webhook".into());
+    };
+
+    if let Some(secret) = secret {
+        let authentic = match source {
+            "github" => req
+                .header("x-hub-signature-256")
+                .is_some_and(|sig| verify_github_signature(secret, &req.body, sig)),
+            _ => req
+                .header("x-gitlab-token")
+                .is_some_and(|token| constant_time_eq(token.as_bytes(), secret.as_bytes())),
+        };
+        if !authentic {
+            return (401, "invalid signature".into());
+        }
+    }
+
+    let payload: Value = match serde_json::from_slice(&req.body) {
+        Ok(v) => v,
+        Err(e) => return (400, format!("invalid JSON payload: {}", e)),
+    };
+
+    let event = normalize_event(source, raw_event);
+    if event == "ping" {
+        return (202, "pong".into());
+    }
+    let summary = summarize_payload(&event, &payload);
+    let action = summary["action"].as_str();
+    let repo = summary["repo"].as_str();
+    match state.forge_event_add(source, &event, action, repo, &summary, &payload) {
+        Ok(id) => (202, format!("recorded event {}", id)),
+        Err(e) => (500, e),
+    }
+}
+
+/// Map forge-specific event names onto one vocabulary
+/// (`Merge Request Hook` -> `merge_request`, `pull_request` stays as-is)
+pub fn normalize_event(source: &str, raw: &str) -> String {
+    match source {
+        "gitlab" => raw
+            .trim_end_matches(" Hook")
+            .to_ascii_lowercase()
+            .replace(' ', "_"),
+        _ => raw.to_string(),
+    }
+}
+
+/// Pull the fields an agent acts on out of a GitHub or GitLab payload
+pub fn summarize_payload(event: &str, payload: &Value) -> Value {
+    let first = |paths: &[&str]| -> Value {
+        paths
+            .iter()
+            .map(|p| payload.pointer(p).unwrap_or(&Value::Null))
+            .find(|v| !v.is_null())
+            .cloned()
+            .unwrap_or(Value::Null)
+    };
+
+    let commits = payload["commits"].as_array();
+    json!({
+        "action": first(&["/action", "/object_attributes/action", "/object_attributes/state"]),
+        "repo": first(&["/repository/full_name", "/project/path_with_namespace"]),
+        "ref": first(&["/ref", "/object_attributes/source_branch"]),
+        "actor": first(&["/sender/login", "/user_username", "/user/username"]),
+        "number": first(&[
+            "/pull_request/number",
+            "/issue/number",
+            "/object_attributes/iid",
+            "/merge_request/iid",
+        ]),
+        "title": first(&[
+            "/pull_request/title",
+            "/issue/title",
+            "/object_attributes/title",
+            "/merge_request/title",
+            "/head_commit/message",
+        ]),
+        "url": first(&[
+            "/comment/html_url",
+            "/pull_request/html_url",
+            "/issue/html_url",
+            "/object_attributes/url",
+            "/compare",
+        ]),
+        "comment": match event {
+            "issue_comment" | "pull_request_review_comment" | "pull_request_review" | "note" => {
+                first(&["/comment/body", "/review/body", "/object_attributes/note"])
+            }
+            _ => Value::Null,
+        },
+        "commits": commits.map(|c| c.len()),
+        "head": first(&["/after", "/checkout_sha", "/pull_request/head/sha", "/object_attributes/last_commit/id"]),
+    })
+}
+
+/// Check a `sha256=<hex>` GitHub signature header against the body
+pub fn verify_github_signature(secret: &str, body: &[u8], header: &str) -> bool {
+    let Some(hex) = header.strip_prefix("sha256=") else {
+        return false;
+    };
+    let Some(expected) = decode_hex32(hex) else {
+        return false;
+    };
+    hmac_sha256::HMAC::verify(body, secret.as_bytes(), &expected)
+}
+
+fn decode_hex32(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(out)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_incomplete_request_is_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        client
+            .write_all(b"POST / HTTP/1.1\r\nHost: x\r\n")
+            .await
+            .unwrap();
+
+        let state = StateManager::new_in_memory().unwrap();
+        let err = handle(stream, None, &state, Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        // The server side is closed without a reply
+        let mut reply = vec![];
+        assert_eq!(client.read_to_end(&mut reply).await.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_github_signature() {
+        let body = br#"{"zen":"Keep it logically awesome."}"#;
+        let mac = hmac_sha256::HMAC::mac(body, b"s3cret");
+        let header: String = mac.iter().map(|b| format!("{:02x}", b)).collect();
+
+        assert!(verify_github_signature(
+            "s3cret",
+            body,
+            &format!("sha256={}", header)
+        ));
+        assert!(!verify_github_signature(
+            "other",
+            body,
+            &format!("sha256={}", header)
+        ));
+        assert!(!verify_github_signature("s3cret", body, "sha1=abc"));
+    }
+
+    #[test]
+    fn test_summarize_payloads() {
+        assert_eq!(
+            normalize_event("gitlab", "Merge Request Hook"),
+            "merge_request"
+        );
+        assert_eq!(normalize_event("github", "pull_request"), "pull_request");
+
+        let github = json!({
+            "action": "created",
+            "repository": {"full_name": "octo/repo"},
+            "sender": {"login": "octocat"},
+            "issue": {"number": 7, "title": "Bug", "html_url": "https://github.com/octo/repo/issues/7"},
+            "comment": {"body": "/retest", "html_url": "https://github.com/octo/repo/issues/7#c1"}
+        });
+        let summary = summarize_payload("issue_comment", &github);
+        assert_eq!(summary["repo"], "octo/repo");
+        assert_eq!(summary["number"], 7);
+        assert_eq!(summary["comment"], "/retest");
+        assert_eq!(summary["url"], "https://github.com/octo/repo/issues/7#c1");
+
+        let gitlab = json!({
+            "ref": "refs/heads/main",
+            "user_username": "dev",
+            "checkout_sha": "abc",
+            "project": {"path_with_namespace": "group/proj"},
+            "commits": [{}, {}]
+        });
+        let summary = summarize_payload("push", &gitlab);
+        assert_eq!(summary["repo"], "group/proj");
+        assert_eq!(summary["actor"], "dev");
+        assert_eq!(summary["commits"], 2);
+        assert!(summary["comment"].is_null());
+    }
+}
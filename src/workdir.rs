@@ -0,0 +1,174 @@
+// modern-cli-mcp/src/workdir.rs
+//! Per-call working directory.
+//!
+//! Every tool accepts an optional `cwd` argument. The server strips it from
+//! the call and runs the tool inside a task-local scope: spawned commands
+//! start there and relative paths resolve against it, instead of against
+//! wherever the server process happened to be launched. Outside a scope (no
+//! `cwd` given, or background work such as scheduled tasks) paths resolve
+//! against the process working directory as before.
+
+use rmcp::model::{JsonObject, Tool};
+use serde_json::json;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+tokio::task_local! {
+    static CWD: PathBuf;
+}
+
+/// Name of the argument every tool accepts
+pub const ARG: &str = "cwd";
+
+/// The working directory of the current call, if one was given
+pub fn current() -> Option<PathBuf> {
+    CWD.try_with(|cwd| cwd.clone()).ok()
+}
+
+/// Run `fut` with `cwd` as the working directory for everything it does
+pub async fn scope<F: Future>(cwd: PathBuf, fut: F) -> F::Output {
+    CWD.scope(cwd, fut).await
+}
+
+/// `path` joined onto the call's working directory when it is relative
+pub fn resolve(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    match current() {
+        Some(cwd) if path == Path::new(".") => cwd,
+        Some(cwd) if path.is_relative() => cwd.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// String form of [`resolve`] for request fields. `~/` paths, URLs and `-`
+/// (stdin) are left for the tool to interpret.
+pub fn resolve_str(path: &str) -> String {
+    if path == "-" || path.starts_with('~') || path.contains("://") {
+        return path.to_string();
+    }
+    resolve(path).to_string_lossy().into_owned()
+}
+
+/// [`resolve_str`] for each path of a whitespace-separated list
+pub fn resolve_list(paths: &str) -> String {
+    paths
+        .split_whitespace()
+        .map(resolve_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Directory a command should start in: an explicit (possibly relative)
+/// directory, else the call's working directory
+pub fn dir_for(explicit: Option<&str>) -> Option<PathBuf> {
+    match explicit {
+        Some(dir) => Some(resolve(dir)),
+        None => current(),
+    }
+}
+
+/// Remove `cwd` from call arguments and check that it is a directory.
+/// Relative values resolve against the server's own working directory.
+pub fn take(arguments: &mut Option<JsonObject>) -> Result<Option<PathBuf>, String> {
+    let Some(value) = arguments.as_mut().and_then(|args| args.remove(ARG)) else {
+        return Ok(None);
+    };
+    let Some(dir) = value.as_str().filter(|d| !d.is_empty()) else {
+        return match value.is_null() {
+            true => Ok(None),
+            false => Err(format!("{} must be a directory path string", ARG)),
+        };
+    };
+    let dir = match dir.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => PathBuf::from(dir),
+    };
+    match dir.canonicalize() {
+        Ok(dir) if dir.is_dir() => Ok(Some(dir)),
+        Ok(dir) => Err(format!("{} is not a directory: {}", ARG, dir.display())),
+        Err(e) => Err(format!("{} {}: {}", ARG, dir.display(), e)),
+    }
+}
+
+/// Whether the tool has its own `cwd` parameter (for the command it runs),
+/// which then takes precedence over the per-call one
+pub fn declared(tool: &Tool) -> bool {
+    tool.input_schema
+        .get("properties")
+        .and_then(|p| p.get(ARG))
+        .is_some()
+}
+
+/// Advertise `cwd` in a tool's input schema unless it declares its own
+pub fn with_param(mut tool: Tool) -> Tool {
+    if declared(&tool) {
+        return tool;
+    }
+    let mut schema = (*tool.input_schema).clone();
+    if let Some(properties) = schema
+        .entry("properties")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+    {
+        properties.insert(
+            ARG.into(),
+            json!({
+                "type": "string",
+                "description": "Working directory for this call: commands start here and relative \
+                    paths resolve against it (default: the server's working directory)",
+            }),
+        );
+    }
+    tool.input_schema = Arc::new(schema);
+    tool
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_in_scope() {
+        assert_eq!(resolve("src/main.rs"), PathBuf::from("src/main.rs"));
+        assert_eq!(current(), None);
+
+        let dir = PathBuf::from("/srv/app");
+        scope(dir.clone(), async {
+            assert_eq!(resolve("src/main.rs"), dir.join("src/main.rs"));
+            assert_eq!(resolve("/etc/hosts"), PathBuf::from("/etc/hosts"));
+            assert_eq!(resolve("."), dir);
+            assert_eq!(resolve_list("a.rs /b.rs"), "/srv/app/a.rs /b.rs");
+            assert_eq!(resolve_str("~/notes"), "~/notes");
+            assert_eq!(
+                resolve_str("https://example.com/a"),
+                "https://example.com/a"
+            );
+            assert_eq!(dir_for(None), Some(dir.clone()));
+            assert_eq!(dir_for(Some("build")), Some(dir.join("build")));
+        })
+        .await;
+    }
+
+    #[test]
+    fn test_take() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut args = Some(
+            json!({ "path": "a.txt", "cwd": tmp.path() })
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        let cwd = take(&mut args).unwrap();
+        assert_eq!(cwd, Some(tmp.path().canonicalize().unwrap()));
+        assert!(!args.as_ref().unwrap().contains_key("cwd"));
+        assert_eq!(take(&mut args).unwrap(), None);
+
+        let file = tmp.path().join("f");
+        std::fs::write(&file, "").unwrap();
+        let mut args = Some(json!({ "cwd": file }).as_object().unwrap().clone());
+        assert!(take(&mut args).unwrap_err().contains("not a directory"));
+        let mut args = Some(json!({ "cwd": 3 }).as_object().unwrap().clone());
+        assert!(take(&mut args).is_err());
+    }
+}
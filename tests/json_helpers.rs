@@ -136,6 +136,7 @@ fn test_file_json_structure() {
 
 /// Test parse_trash_list_to_json output structure
 #[test]
+#[allow(clippy::unnecessary_filter_map)]
 fn test_trash_list_json_structure() {
     let sample_output = "2024-01-15 10:30:00 /home/user/old_file.txt
 2024-01-14 09:00:00 /home/user/backup.zip";